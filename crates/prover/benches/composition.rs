@@ -0,0 +1,80 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use itertools::Itertools;
+use num_traits::Zero;
+use stwo_prover::constraint_framework::{FrameworkEval, TraceLocationAllocator};
+use stwo_prover::core::air::{Component, ComponentProver, ComponentProvers, Trace};
+use stwo_prover::core::backend::simd::m31::LOG_N_LANES;
+use stwo_prover::core::backend::simd::SimdBackend;
+use stwo_prover::core::fields::m31::BaseField;
+use stwo_prover::core::fields::qm31::SecureField;
+use stwo_prover::core::pcs::TreeVec;
+use stwo_prover::core::poly::circle::{CanonicCoset, PolyOps};
+use stwo_prover::examples::wide_fibonacci::{
+    generate_trace, FibInput, WideFibonacciComponent, WideFibonacciEval,
+};
+
+const FIB_SEQUENCE_LENGTH: usize = 100;
+
+// Framework components only implement `ComponentProver` for the SIMD backend.
+fn bench_composition(c: &mut Criterion, log_n_rows: u32) {
+    let eval = WideFibonacciEval::<FIB_SEQUENCE_LENGTH> { log_n_rows };
+    let component = WideFibonacciComponent::new(
+        &mut TraceLocationAllocator::default(),
+        eval.clone(),
+        (SecureField::zero(), None),
+    );
+
+    let inputs = (0..1 << (log_n_rows - LOG_N_LANES))
+        .map(|i| FibInput::from_seeds(BaseField::from(i), BaseField::from(i + 1)))
+        .collect_vec();
+    let twiddles = SimdBackend::precompute_twiddles(
+        CanonicCoset::new(eval.max_constraint_log_degree_bound())
+            .circle_domain()
+            .half_coset,
+    );
+    let trace_polys = generate_trace::<FIB_SEQUENCE_LENGTH>(log_n_rows, &inputs)
+        .into_iter()
+        .map(|eval| eval.interpolate_with_twiddles(&twiddles))
+        .collect_vec();
+    let eval_domain = CanonicCoset::new(eval.max_constraint_log_degree_bound()).circle_domain();
+    let trace_evals = trace_polys
+        .iter()
+        .map(|poly| poly.evaluate_with_twiddles(eval_domain, &twiddles))
+        .collect_vec();
+    let trace = Trace {
+        polys: TreeVec::new(vec![vec![], trace_polys.iter().collect()]),
+        evals: TreeVec::new(vec![vec![], trace_evals.iter().collect()]),
+    };
+    let component_provers = ComponentProvers {
+        components: vec![&component as &dyn ComponentProver<SimdBackend>],
+        n_preprocessed_columns: 0,
+    };
+    let random_coeff = SecureField::from_u32_unchecked(1, 2, 3, 4);
+
+    let mut group = c.benchmark_group("composition");
+    group.throughput(Throughput::Elements(
+        (component.n_constraints() << log_n_rows) as u64,
+    ));
+    group.bench_function(
+        format!("simd wide fibonacci {FIB_SEQUENCE_LENGTH} 2^{log_n_rows}"),
+        |b| {
+            b.iter_with_large_drop(|| {
+                component_provers
+                    .compute_composition_polynomial(black_box(random_coeff), black_box(&trace))
+            })
+        },
+    );
+    group.finish();
+}
+
+fn composition_benches(c: &mut Criterion) {
+    for log_n_rows in [16, 18, 20] {
+        bench_composition(c, log_n_rows);
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = composition_benches);
+criterion_main!(benches);
@@ -1,13 +1,16 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
-use stwo_prover::core::backend::cpu::CpuBackend;
+use stwo_prover::core::backend::cpu::{CpuBackend, CpuCircleEvaluation};
 use stwo_prover::core::backend::simd::SimdBackend;
 use stwo_prover::core::circle::CirclePoint;
 use stwo_prover::core::fields::m31::BaseField;
-use stwo_prover::core::poly::circle::{CirclePoly, PolyOps};
+use stwo_prover::core::poly::circle::{CanonicCoset, CirclePoly, PolyOps};
+use stwo_prover::core::poly::NaturalOrder;
 
 const LOG_SIZE: u32 = 20;
+const BARYCENTRIC_LOG_SIZE: u32 = 12;
+const N_EVALUATIONS: usize = 100;
 
 fn bench_eval_at_secure_point<B: PolyOps>(c: &mut Criterion, id: &str) {
     let poly = CirclePoly::new((0..1 << LOG_SIZE).map(BaseField::from).collect());
@@ -23,9 +26,47 @@ fn bench_eval_at_secure_point<B: PolyOps>(c: &mut Criterion, id: &str) {
     );
 }
 
+fn bench_barycentric_eval_at_secure_point(c: &mut Criterion) {
+    let domain = CanonicCoset::new(BARYCENTRIC_LOG_SIZE).circle_domain();
+    let mut rng = SmallRng::seed_from_u64(0);
+    let evaluations = (0..N_EVALUATIONS)
+        .map(|_| {
+            let values = (0..domain.size()).map(|_| rng.gen()).collect();
+            CpuCircleEvaluation::<BaseField, NaturalOrder>::new(domain, values)
+        })
+        .collect::<Vec<_>>();
+    let point = CirclePoint {
+        x: rng.gen(),
+        y: rng.gen(),
+    };
+    let weights = domain.barycentric_weights();
+    c.bench_function(
+        &format!("cpu barycentric_eval_at_point {N_EVALUATIONS}x2^{BARYCENTRIC_LOG_SIZE}"),
+        |b| {
+            b.iter(|| {
+                for evaluation in &evaluations {
+                    black_box(evaluation.eval_at_point(black_box(point), &weights));
+                }
+            });
+        },
+    );
+    c.bench_function(
+        &format!("cpu eval_with_barycentric_coefficients {N_EVALUATIONS}x2^{BARYCENTRIC_LOG_SIZE}"),
+        |b| {
+            b.iter(|| {
+                let coefficients = domain.barycentric_coefficients(black_box(point), &weights);
+                for evaluation in &evaluations {
+                    black_box(evaluation.eval_with_barycentric_coefficients(&coefficients));
+                }
+            });
+        },
+    );
+}
+
 fn eval_at_secure_point_benches(c: &mut Criterion) {
     bench_eval_at_secure_point::<SimdBackend>(c, "simd");
     bench_eval_at_secure_point::<CpuBackend>(c, "cpu");
+    bench_barycentric_eval_at_secure_point(c);
 }
 
 criterion_group!(
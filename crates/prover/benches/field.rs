@@ -2,10 +2,13 @@ use criterion::{criterion_group, criterion_main, Criterion};
 use num_traits::One;
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
+use stwo_prover::constraint_framework::logup::LookupElements;
 use stwo_prover::core::backend::simd::m31::{PackedBaseField, N_LANES};
+use stwo_prover::core::channel::Blake2sChannel;
 use stwo_prover::core::fields::cm31::CM31;
 use stwo_prover::core::fields::m31::{BaseField, M31};
 use stwo_prover::core::fields::qm31::SecureField;
+use stwo_prover::core::fields::FieldExpOps;
 
 pub const N_ELEMENTS: usize = 1 << 16;
 pub const N_STATE_ELEMENTS: usize = 8;
@@ -100,6 +103,55 @@ pub fn qm31_operations_bench(c: &mut Criterion) {
     });
 }
 
+pub fn qm31_mul_base_bench(c: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let elements: Vec<BaseField> = (0..N_ELEMENTS).map(|_| rng.gen()).collect();
+    let mut state: [SecureField; N_STATE_ELEMENTS] = rng.gen();
+
+    c.bench_function("SecureField mul_base", |b| {
+        b.iter(|| {
+            for elem in &elements {
+                for state_elem in &mut state {
+                    *state_elem = state_elem.mul_base(*elem);
+                }
+            }
+        })
+    });
+
+    c.bench_function("SecureField mul SecureField::from", |b| {
+        b.iter(|| {
+            for elem in &elements {
+                for state_elem in &mut state {
+                    *state_elem *= SecureField::from(*elem);
+                }
+            }
+        })
+    });
+
+    let lookup_elements = LookupElements::<16>::draw(&mut Blake2sChannel::default());
+    let rows: Vec<[BaseField; 16]> = (0..N_ELEMENTS / 16).map(|_| rng.gen()).collect();
+    c.bench_function("LookupElements<16> combine", |b| {
+        b.iter(|| {
+            rows.iter()
+                .map(|row| lookup_elements.combine::<BaseField, SecureField>(row))
+                .sum::<SecureField>()
+        })
+    });
+}
+
+pub fn qm31_batch_inverse_bench(c: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let elements: Vec<SecureField> = (0..N_ELEMENTS).map(|_| rng.gen()).collect();
+
+    c.bench_function("SecureField inverse", |b| {
+        b.iter(|| elements.iter().map(|e| e.inverse()).collect::<Vec<_>>())
+    });
+
+    c.bench_function("SecureField batch_inverse_allow_zeros", |b| {
+        b.iter(|| SecureField::batch_inverse_allow_zeros(&elements))
+    });
+}
+
 pub fn simd_m31_operations_bench(c: &mut Criterion) {
     let mut rng = SmallRng::seed_from_u64(0);
     let elements: Vec<PackedBaseField> = (0..N_ELEMENTS / N_LANES).map(|_| rng.gen()).collect();
@@ -145,6 +197,6 @@ pub fn simd_m31_operations_bench(c: &mut Criterion) {
 criterion_group!(
     name = benches;
     config = Criterion::default().sample_size(10);
-    targets = m31_operations_bench, cm31_operations_bench, qm31_operations_bench, 
-        simd_m31_operations_bench);
+    targets = m31_operations_bench, cm31_operations_bench, qm31_operations_bench,
+        qm31_mul_base_bench, qm31_batch_inverse_bench, simd_m31_operations_bench);
 criterion_main!(benches);
@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_traits::Zero;
+use stwo_prover::constraint_framework::TraceLocationAllocator;
+use stwo_prover::core::air::{Component, Components, MaskPointsCache};
+use stwo_prover::core::circle::CirclePoint;
+use stwo_prover::core::fields::qm31::SecureField;
+use stwo_prover::examples::wide_fibonacci::{WideFibonacciComponent, WideFibonacciEval};
+
+const N_COMPONENTS: usize = 10;
+const FIB_SEQUENCE_LENGTH: usize = 100;
+
+fn mask_points_benches(c: &mut Criterion) {
+    let allocator = &mut TraceLocationAllocator::default();
+    let components = (0..N_COMPONENTS as u32)
+        .map(|i| {
+            WideFibonacciComponent::new(
+                allocator,
+                WideFibonacciEval::<FIB_SEQUENCE_LENGTH> { log_n_rows: 10 + i },
+                (SecureField::zero(), None),
+            )
+        })
+        .collect::<Vec<_>>();
+    let components = Components {
+        components: components.iter().map(|c| c as &dyn Component).collect(),
+        n_preprocessed_columns: 0,
+    };
+    let point = CirclePoint::<SecureField>::get_point(98765);
+
+    c.bench_function(&format!("mask_points {N_COMPONENTS} components"), |b| {
+        b.iter(|| black_box(components.mask_points(black_box(point))));
+    });
+
+    let mut cache = MaskPointsCache::new(&components);
+    c.bench_function(
+        &format!("cached mask_points {N_COMPONENTS} components"),
+        |b| {
+            b.iter(|| black_box(cache.mask_points(black_box(point))));
+        },
+    );
+}
+
+criterion_group!(
+        name = benches;
+        config = Criterion::default().sample_size(10);
+        targets = mask_points_benches);
+criterion_main!(benches);
@@ -1,7 +1,11 @@
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
+
+use itertools::Itertools;
 use num_traits::Zero;
 
 use super::logup::{LogupAtRow, LogupSums};
-use super::{EvalAtRow, INTERACTION_TRACE_IDX};
+use super::{validate_mask, EvalAtRow, MaskError, INTERACTION_TRACE_IDX};
 use crate::core::backend::{Backend, Column};
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
@@ -11,12 +15,41 @@ use crate::core::pcs::TreeVec;
 use crate::core::poly::circle::{CanonicCoset, CirclePoly};
 use crate::core::utils::circle_domain_order_to_coset_order;
 
+/// Number of violations listed by [assert_constraints] when it panics.
+const N_REPORTED_VIOLATIONS: usize = 5;
+
+/// A constraint that doesn't vanish at a row of the trace domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConstraintViolation {
+    /// The row, in coset order.
+    pub row: usize,
+    /// The index of the constraint, in the order the constraints are added.
+    pub constraint_index: usize,
+    /// The value of the constraint at the row.
+    pub value: SecureField,
+}
+
+impl Display for ConstraintViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {}, constraint {}: {}",
+            self.row, self.constraint_index, self.value
+        )
+    }
+}
+
 /// Evaluates expressions at a trace domain row, and asserts constraints. Mainly used for testing.
 pub struct AssertEvaluator<'a> {
     pub trace: &'a TreeVec<Vec<Vec<BaseField>>>,
     pub col_index: TreeVec<usize>,
     pub row: usize,
     pub logup: LogupAtRow<Self>,
+    /// The index of the next constraint.
+    pub constraint_index: usize,
+    /// Where violated constraints are recorded. If `None`, a violated constraint panics right
+    /// away.
+    pub violations: Option<&'a RefCell<Vec<ConstraintViolation>>>,
 }
 impl<'a> AssertEvaluator<'a> {
     pub fn new(
@@ -30,18 +63,48 @@ impl<'a> AssertEvaluator<'a> {
             col_index: TreeVec::new(vec![0; trace.len()]),
             row,
             logup: LogupAtRow::new(INTERACTION_TRACE_IDX, logup_sums.0, logup_sums.1, log_size),
+            constraint_index: 0,
+            violations: None,
         }
     }
+
+    /// Records violated constraints in `violations` instead of panicking.
+    pub const fn with_violations(
+        mut self,
+        violations: &'a RefCell<Vec<ConstraintViolation>>,
+    ) -> Self {
+        self.violations = Some(violations);
+        self
+    }
 }
 impl<'a> EvalAtRow for AssertEvaluator<'a> {
     type F = BaseField;
     type EF = SecureField;
 
+    fn validate_next_interaction_mask(
+        &self,
+        interaction: usize,
+        offsets: &[isize],
+    ) -> Result<(), MaskError> {
+        validate_mask(
+            self.trace,
+            &self.col_index,
+            interaction,
+            offsets,
+            self.logup.log_size,
+        )
+    }
+
     fn next_interaction_mask<const N: usize>(
         &mut self,
         interaction: usize,
         offsets: [isize; N],
     ) -> [Self::F; N] {
+        if cfg!(debug_assertions) {
+            if let Err(err) = self.validate_next_interaction_mask(interaction, &offsets) {
+                panic!("{err}");
+            }
+        }
         let col_index = self.col_index[interaction];
         self.col_index[interaction] += 1;
         offsets.map(|off| {
@@ -59,12 +122,21 @@ impl<'a> EvalAtRow for AssertEvaluator<'a> {
         // Cast to SecureField.
         // The constraint should be zero at the given row, since we are evaluating on the trace
         // domain.
-        assert_eq!(
-            Self::EF::from(constraint),
-            SecureField::zero(),
-            "row: {}",
-            self.row
-        );
+        let value = Self::EF::from(constraint);
+        let constraint_index = self.constraint_index;
+        self.constraint_index += 1;
+        if value.is_zero() {
+            return;
+        }
+        let violation = ConstraintViolation {
+            row: self.row,
+            constraint_index,
+            value,
+        };
+        match self.violations {
+            Some(violations) => violations.borrow_mut().push(violation),
+            None => panic!("Constraint violated at {violation}"),
+        }
     }
 
     fn combine_ef(values: [Self::F; SECURE_EXTENSION_DEGREE]) -> Self::EF {
@@ -74,12 +146,64 @@ impl<'a> EvalAtRow for AssertEvaluator<'a> {
     super::logup_proxy!();
 }
 
+/// Asserts that the constraints of `assert_func` vanish on every row of `trace_domain`.
+///
+/// # Panics
+///
+/// Panics if any constraint is violated, listing the first violations. See
+/// [try_assert_constraints].
 pub fn assert_constraints<B: Backend>(
     trace_polys: &TreeVec<Vec<CirclePoly<B>>>,
     trace_domain: CanonicCoset,
     assert_func: impl Fn(AssertEvaluator<'_>),
     logup_sums: LogupSums,
 ) {
+    assert_constraints_on_polys(
+        &trace_polys.as_ref().map(|tree| tree.iter().collect()),
+        trace_domain,
+        assert_func,
+        logup_sums,
+    );
+}
+
+/// Like [assert_constraints], but returns all the violated constraints instead of panicking.
+pub fn try_assert_constraints<B: Backend>(
+    trace_polys: &TreeVec<Vec<CirclePoly<B>>>,
+    trace_domain: CanonicCoset,
+    assert_func: impl Fn(AssertEvaluator<'_>),
+    logup_sums: LogupSums,
+) -> Result<(), Vec<ConstraintViolation>> {
+    try_assert_constraints_on_polys(
+        &trace_polys.as_ref().map(|tree| tree.iter().collect()),
+        trace_domain,
+        assert_func,
+        logup_sums,
+    )
+}
+
+pub(super) fn assert_constraints_on_polys<B: Backend>(
+    trace_polys: &TreeVec<Vec<&CirclePoly<B>>>,
+    trace_domain: CanonicCoset,
+    assert_func: impl Fn(AssertEvaluator<'_>),
+    logup_sums: LogupSums,
+) {
+    if let Err(violations) =
+        try_assert_constraints_on_polys(trace_polys, trace_domain, assert_func, logup_sums)
+    {
+        panic!(
+            "{} constraint violations, the first are:\n{}",
+            violations.len(),
+            violations.iter().take(N_REPORTED_VIOLATIONS).join("\n")
+        );
+    }
+}
+
+fn try_assert_constraints_on_polys<B: Backend>(
+    trace_polys: &TreeVec<Vec<&CirclePoly<B>>>,
+    trace_domain: CanonicCoset,
+    assert_func: impl Fn(AssertEvaluator<'_>),
+    logup_sums: LogupSums,
+) -> Result<(), Vec<ConstraintViolation>> {
     let traces = trace_polys.as_ref().map(|tree| {
         tree.iter()
             .map(|poly| {
@@ -93,9 +217,290 @@ pub fn assert_constraints<B: Backend>(
             })
             .collect()
     });
+    let violations = RefCell::new(vec![]);
     for row in 0..trace_domain.size() {
-        let eval = AssertEvaluator::new(&traces, row, trace_domain.log_size(), logup_sums);
+        let eval = AssertEvaluator::new(&traces, row, trace_domain.log_size(), logup_sums)
+            .with_violations(&violations);
 
         assert_func(eval);
     }
+    let violations = violations.into_inner();
+    match violations.is_empty() {
+        true => Ok(()),
+        false => Err(violations),
+    }
+}
+
+/// A component whose constraints can be asserted on the trace of a whole AIR.
+pub trait AssertComponent<B: Backend> {
+    /// Asserts the constraints of the component on its own columns of `trace_polys`, which holds
+    /// the columns of all the components.
+    fn assert_constraints_on_trace(&self, trace_polys: &TreeVec<Vec<CirclePoly<B>>>);
+}
+
+/// Asserts the constraints of every component on the trace of a whole AIR.
+///
+/// Each component is checked on its own columns and trace domain, so the components may be of
+/// different sizes.
+pub fn assert_constraints_multi<B: Backend>(
+    components: &[&dyn AssertComponent<B>],
+    trace_polys: &TreeVec<Vec<CirclePoly<B>>>,
+) {
+    for component in components {
+        component.assert_constraints_on_trace(trace_polys);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use itertools::Itertools;
+    use num_traits::Zero;
+
+    use super::{
+        assert_constraints, assert_constraints_multi, try_assert_constraints, AssertEvaluator,
+        ConstraintViolation,
+    };
+    use crate::constraint_framework::{
+        EvalAtRow, FrameworkComponent, FrameworkEval, InfoEvaluator, TraceLocationAllocator,
+        ORIGINAL_TRACE_IDX,
+    };
+    use crate::core::backend::CpuBackend;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, CirclePoly};
+    use crate::core::poly::BitReversedOrder;
+
+    #[test]
+    fn test_next_trace_masks_matches_sequential_calls() {
+        let trace = TreeVec::new(vec![
+            vec![],
+            (0..3)
+                .map(|col| {
+                    (0..4)
+                        .map(|row| BaseField::from(10 * col + row))
+                        .collect_vec()
+                })
+                .collect_vec(),
+            (0..2)
+                .map(|col| {
+                    (0..4)
+                        .map(|row| BaseField::from(100 + col * row))
+                        .collect_vec()
+                })
+                .collect_vec(),
+        ]);
+        let logup_sums = (SecureField::zero(), None);
+
+        for row in 0..4 {
+            let mut batched = AssertEvaluator::new(&trace, row, 2, logup_sums);
+            let mut sequential = AssertEvaluator::new(&trace, row, 2, logup_sums);
+
+            let batched_masks = batched.next_trace_masks::<3>();
+            let batched_interaction_masks = batched.next_interaction_masks::<2>(2);
+            let sequential_masks = std::array::from_fn(|_| sequential.next_trace_mask());
+            let sequential_interaction_masks =
+                std::array::from_fn(|_| sequential.next_interaction_mask(2, [0])[0]);
+
+            assert_eq!(batched_masks, sequential_masks);
+            assert_eq!(batched_interaction_masks, sequential_interaction_masks);
+            assert_eq!(batched.col_index.0, sequential.col_index.0);
+        }
+
+        let mut info = InfoEvaluator::empty();
+        info.next_trace_masks::<3>();
+        info.next_interaction_masks::<2>(2);
+        assert_eq!(
+            info.mask_offsets.0,
+            [vec![], vec![vec![0]; 3], vec![vec![0]; 2]]
+        );
+    }
+
+    #[test]
+    fn test_next_trace_mask_and_next() {
+        let trace = TreeVec::new(vec![
+            vec![],
+            (0..2)
+                .map(|col| {
+                    (0..4)
+                        .map(|row| BaseField::from(10 * col + row))
+                        .collect_vec()
+                })
+                .collect_vec(),
+        ]);
+        let logup_sums = (SecureField::zero(), None);
+
+        for row in 0..4 {
+            let mut paired = AssertEvaluator::new(&trace, row, 2, logup_sums);
+            let mut interaction = AssertEvaluator::new(&trace, row, 2, logup_sums);
+
+            for _ in 0..2 {
+                let (value, next_value) = paired.next_trace_mask_and_next();
+                let [expected_value, expected_next_value] =
+                    interaction.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+                assert_eq!((value, next_value), (expected_value, expected_next_value));
+            }
+            assert_eq!(paired.col_index.0, interaction.col_index.0);
+        }
+
+        let mut info = InfoEvaluator::empty();
+        info.next_trace_mask_and_next();
+        assert_eq!(info.mask_offsets.0, [vec![], vec![vec![0, 1]]]);
+    }
+
+    #[test]
+    fn test_next_trace_mask_and_next_fibonacci_step() {
+        const LOG_SIZE: u32 = 4;
+        let (mut a, mut b) = (vec![BaseField::from(1)], vec![BaseField::from(1)]);
+        for row in 1..1 << LOG_SIZE {
+            let (prev_a, prev_b) = (a[row - 1], b[row - 1]);
+            a.push(prev_b);
+            b.push(prev_a + prev_b);
+        }
+        let trace = TreeVec::new(vec![vec![], vec![a, b]]);
+        let violations = RefCell::new(vec![]);
+
+        for row in 0..1 << LOG_SIZE {
+            let mut eval = AssertEvaluator::new(&trace, row, LOG_SIZE, (SecureField::zero(), None))
+                .with_violations(&violations);
+            let (a, next_a) = eval.next_trace_mask_and_next();
+            let (b, next_b) = eval.next_trace_mask_and_next();
+            eval.add_constraint(next_a - b);
+            eval.add_constraint(next_b - (a + b));
+        }
+
+        // Only the wrap around from the last row to the first breaks the step.
+        let violations = violations.into_inner();
+        assert!(!violations.is_empty());
+        assert!(violations
+            .iter()
+            .all(|violation| violation.row == (1 << LOG_SIZE) - 1));
+    }
+
+    /// Constrains its second column to be the square of its first column.
+    struct SquareEval {
+        log_size: u32,
+    }
+
+    impl FrameworkEval for SquareEval {
+        fn name(&self) -> String {
+            "SquareEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            self.log_size
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let value = eval.next_trace_mask();
+            let square = eval.next_trace_mask();
+            eval.add_constraint(square - value.clone() * value);
+            eval
+        }
+    }
+
+    fn square_columns(log_size: u32, offset: u32) -> [CirclePoly<CpuBackend>; 2] {
+        let domain = CanonicCoset::new(log_size).circle_domain();
+        let values = (0..1 << log_size)
+            .map(|i| BaseField::from(offset + i))
+            .collect_vec();
+        let squares = values.iter().map(|&v| v * v).collect_vec();
+        [values, squares].map(|column| {
+            CircleEvaluation::<CpuBackend, BaseField, BitReversedOrder>::new(domain, column)
+                .interpolate()
+        })
+    }
+
+    fn components() -> [FrameworkComponent<SquareEval>; 2] {
+        let allocator = &mut TraceLocationAllocator::default();
+        [5, 6].map(|log_size| {
+            FrameworkComponent::new(
+                allocator,
+                SquareEval { log_size },
+                (SecureField::zero(), None),
+            )
+        })
+    }
+
+    #[test]
+    fn test_assert_constraints_multi() {
+        let [small, large] = components();
+        let trace_polys = TreeVec::new(vec![
+            vec![],
+            [square_columns(5, 0), square_columns(6, 100)].concat(),
+        ]);
+
+        assert_constraints_multi(&[&small, &large], &trace_polys);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_constraints_multi_fails() {
+        let [small, large] = components();
+        let [large_values, _] = square_columns(6, 100);
+        let [_, wrong_squares] = square_columns(6, 101);
+        let trace_polys = TreeVec::new(vec![
+            vec![],
+            [
+                square_columns(5, 0).to_vec(),
+                vec![large_values, wrong_squares],
+            ]
+            .concat(),
+        ]);
+
+        assert_constraints_multi(&[&small, &large], &trace_polys);
+    }
+
+    #[test]
+    fn test_try_assert_constraints_reports_violations() {
+        let eval = SquareEval { log_size: 5 };
+        let [values, mut squares] = square_columns(5, 0);
+        // Shifts every square by one.
+        squares.coeffs[0] += BaseField::from(1);
+        let trace_polys = TreeVec::new(vec![vec![], vec![values, squares]]);
+
+        let violations = try_assert_constraints(
+            &trace_polys,
+            CanonicCoset::new(5),
+            |eval_at_row| {
+                eval.evaluate(eval_at_row);
+            },
+            (SecureField::zero(), None),
+        )
+        .unwrap_err();
+
+        assert_eq!(violations.len(), 1 << 5);
+        assert_eq!(
+            violations[3],
+            ConstraintViolation {
+                row: 3,
+                constraint_index: 0,
+                value: SecureField::from(1)
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "32 constraint violations, the first are:\nrow 0, constraint 0")]
+    fn test_assert_constraints_lists_violations() {
+        let eval = SquareEval { log_size: 5 };
+        let [values, mut squares] = square_columns(5, 0);
+        squares.coeffs[0] += BaseField::from(1);
+        let trace_polys = TreeVec::new(vec![vec![], vec![values, squares]]);
+
+        assert_constraints(
+            &trace_polys,
+            CanonicCoset::new(5),
+            |eval_at_row| {
+                eval.evaluate(eval_at_row);
+            },
+            (SecureField::zero(), None),
+        );
+    }
 }
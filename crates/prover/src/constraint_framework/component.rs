@@ -5,15 +5,19 @@ use std::iter::zip;
 use std::ops::Deref;
 
 use itertools::Itertools;
+use rand::Rng;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 use tracing::{span, Level};
 
+use super::assert::assert_constraints_on_polys;
 use super::cpu_domain::CpuDomainEvaluator;
+use super::expr::ExprEvaluator;
 use super::logup::LogupSums;
 use super::preprocessed_columns::PreprocessedColumn;
 use super::{
-    EvalAtRow, InfoEvaluator, PointEvaluator, SimdDomainEvaluator, PREPROCESSED_TRACE_IDX,
+    AssertComponent, EvalAtRow, InfoEvaluator, PointEvaluator, SimdDomainEvaluator,
+    INTERACTION_TRACE_IDX, PREPROCESSED_TRACE_IDX,
 };
 use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
 use crate::core::air::{Component, ComponentProver, Trace};
@@ -21,15 +25,23 @@ use crate::core::backend::simd::column::VeryPackedSecureColumnByCoords;
 use crate::core::backend::simd::m31::LOG_N_LANES;
 use crate::core::backend::simd::very_packed_m31::{VeryPackedBaseField, LOG_N_VERY_PACKED_ELEMS};
 use crate::core::backend::simd::SimdBackend;
+use crate::core::backend::{Backend, BackendForChannel};
+use crate::core::channel::MerkleChannel;
 use crate::core::circle::CirclePoint;
 use crate::core::constraints::coset_vanishing;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
-use crate::core::fields::secure_column::SecureColumnByCoords;
+use crate::core::fields::secure_column::{SecureColumnByCoords, SECURE_EXTENSION_DEGREE};
 use crate::core::fields::FieldExpOps;
-use crate::core::pcs::{TreeSubspan, TreeVec};
-use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, PolyOps};
+use crate::core::pcs::{
+    CommitmentSchemeProver, CommitmentSchemeVerifier, PcsConfig, TreeSubspan, TreeVec,
+};
+use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, CirclePoly, PolyOps};
 use crate::core::poly::BitReversedOrder;
+use crate::core::prover::{
+    prove, verify_with_logup_balance, ProvingError, StarkProof, VerificationError,
+};
+use crate::core::vcs::blake2_hash::Blake2sHasher;
 use crate::core::{utils, ColumnVec};
 
 const CHUNK_SIZE: usize = 1;
@@ -51,6 +63,8 @@ pub struct TraceLocationAllocator {
     preprocessed_columns: HashMap<PreprocessedColumn, usize>,
     /// Controls whether the preprocessed columns are dynamic or static (default=Dynamic).
     preprocessed_columns_allocation_mode: PreprocessedColumnsAllocationMode,
+    /// Log sizes of the columns reserved upfront, if the layout was preallocated.
+    preallocated_log_sizes: Option<TreeVec<Vec<u32>>>,
 }
 
 impl TraceLocationAllocator {
@@ -68,6 +82,14 @@ impl TraceLocationAllocator {
                 .map(|(tree_index, (offset, cols))| {
                     let col_start = *offset;
                     let col_end = col_start + cols.len();
+                    if let Some(preallocated) = &self.preallocated_log_sizes {
+                        let n_reserved = preallocated.get(tree_index).map_or(0, |t| t.len());
+                        assert!(
+                            col_end <= n_reserved,
+                            "Columns {col_start}..{col_end} of tree {tree_index} exceed the \
+                            {n_reserved} preallocated columns",
+                        );
+                    }
                     *offset = col_end;
                     TreeSubspan {
                         tree_index,
@@ -89,6 +111,37 @@ impl TraceLocationAllocator {
                 .map(|(i, &col)| (col, i))
                 .collect(),
             preprocessed_columns_allocation_mode: PreprocessedColumnsAllocationMode::Static,
+            preallocated_log_sizes: None,
+        }
+    }
+
+    /// Create a new `TraceLocationAllocator` with a fixed layout of column log sizes.
+    ///
+    /// Components are assigned the reserved columns of each tree in registration order.
+    /// Registering a component that doesn't fit in the reserved columns, or whose columns have a
+    /// different log size than reserved, panics.
+    pub fn with_preallocated(sizes: TreeVec<Vec<u32>>) -> Self {
+        Self {
+            preallocated_log_sizes: Some(sizes),
+            ..Default::default()
+        }
+    }
+
+    /// Asserts that the columns at `locations` were preallocated with the given log size.
+    fn validate_preallocated_log_size(&self, locations: &[TreeSubspan], log_size: u32) {
+        let Some(preallocated) = &self.preallocated_log_sizes else {
+            return;
+        };
+        for location in locations {
+            let reserved = &preallocated[location.tree_index][location.col_start..location.col_end];
+            assert!(
+                reserved.iter().all(|&reserved| reserved == log_size),
+                "Columns {}..{} of tree {} were preallocated with log sizes {reserved:?}, \
+                but are allocated with log size {log_size}",
+                location.col_start,
+                location.col_end,
+                location.tree_index,
+            );
         }
     }
 
@@ -113,11 +166,116 @@ impl TraceLocationAllocator {
 /// the SIMD backend.
 /// Note that the constraint framework only support components with columns of the same size.
 pub trait FrameworkEval {
+    /// Returns the name of the component, see [`Component::name()`]. Defaults to its type name,
+    /// which is not guaranteed to be the same across builds.
+    fn name(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+
     fn log_size(&self) -> u32;
 
     fn max_constraint_log_degree_bound(&self) -> u32;
 
     fn evaluate<E: EvalAtRow>(&self, eval: E) -> E;
+
+    /// Returns a human-readable form of each constraint, in the order they are added, with the
+    /// intermediates inlined. See the [Display] implementation of
+    /// [`ExtExpr`](super::expr::ExtExpr).
+    fn format_constraints(&self) -> Vec<String> {
+        self.evaluate(ExprEvaluator::new(self.log_size(), false))
+            .inlined_constraints()
+            .iter()
+            .map(|constraint| constraint.simplify().to_string())
+            .collect()
+    }
+
+    /// Returns the number of constraints, as counted by an [InfoEvaluator], without building a
+    /// [FrameworkComponent].
+    fn n_constraints(&self) -> usize {
+        self.evaluate(InfoEvaluator::new(
+            self.log_size(),
+            vec![],
+            (SecureField::default(), None),
+        ))
+        .n_constraints
+    }
+}
+
+/// A [FrameworkEval] made of two halves that are evaluated on the same evaluator, one after the
+/// other.
+///
+/// The logup state lives in the evaluator, so the fractions written by both halves accumulate into
+/// a single running sum. The halves must not call [`EvalAtRow::finalize_logup`]; it is called once,
+/// after both halves are evaluated, so at least one of them must write a fraction.
+/// The columns of `first` are allocated before the columns of `second`.
+pub struct SharedLogupEval<A: FrameworkEval, B: FrameworkEval> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<A: FrameworkEval, B: FrameworkEval> FrameworkEval for SharedLogupEval<A, B> {
+    fn name(&self) -> String {
+        format!(
+            "SharedLogupEval<{}, {}>",
+            self.first.name(),
+            self.second.name()
+        )
+    }
+
+    fn log_size(&self) -> u32 {
+        assert_eq!(
+            self.first.log_size(),
+            self.second.log_size(),
+            "Halves sharing a logup must have the same size"
+        );
+        self.first.log_size()
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.first
+            .max_constraint_log_degree_bound()
+            .max(self.second.max_constraint_log_degree_bound())
+    }
+
+    fn evaluate<E: EvalAtRow>(&self, eval: E) -> E {
+        let eval = self.first.evaluate(eval);
+        let mut eval = self.second.evaluate(eval);
+        eval.finalize_logup();
+        eval
+    }
+}
+
+/// Returns a trace of `2^log_size` rows for `eval`, with the number of columns it requests from
+/// each interaction, as counted by an [InfoEvaluator], filled with random values.
+///
+/// The trace doesn't satisfy the constraints of `eval`, but has its shape, so it can be used to
+/// fuzz the evaluation of the constraints, e.g. with
+/// [`try_assert_constraints()`](super::try_assert_constraints).
+pub fn random_trace<B: Backend>(
+    eval: &impl FrameworkEval,
+    log_size: u32,
+    rng: &mut impl Rng,
+) -> TreeVec<Vec<CircleEvaluation<B, BaseField, BitReversedOrder>>> {
+    let info = eval.evaluate(InfoEvaluator::new(
+        log_size,
+        vec![],
+        (SecureField::default(), None),
+    ));
+    let domain = CanonicCoset::new(log_size).circle_domain();
+    let n_columns_per_interaction = info.n_columns_per_interaction();
+    TreeVec::new(
+        n_columns_per_interaction
+            .iter()
+            .map(|&n_columns| {
+                (0..n_columns)
+                    .map(|_| {
+                        let values = (0..domain.size()).map(|_| rng.gen()).collect();
+                        CircleEvaluation::new(domain, values)
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
 }
 
 pub struct FrameworkComponent<C: FrameworkEval> {
@@ -136,6 +294,7 @@ impl<E: FrameworkEval> FrameworkComponent<E> {
     ) -> Self {
         let info = eval.evaluate(InfoEvaluator::new(eval.log_size(), vec![], logup_sums));
         let trace_locations = location_allocator.next_for_structure(&info.mask_offsets);
+        location_allocator.validate_preallocated_log_size(&trace_locations, eval.log_size());
 
         let preprocessed_column_indices = info
             .preprocessed_columns
@@ -169,12 +328,57 @@ impl<E: FrameworkEval> FrameworkComponent<E> {
         }
     }
 
+    /// Creates a component for each of `evals`, in canonical order: sorted by
+    /// [`FrameworkEval::name()`], then by a hash of the columns and constraints they allocate.
+    ///
+    /// The columns are allocated in the returned order, so equivalent lists of evals yield the
+    /// same components, with the same trace locations, whatever order they are given in.
+    pub fn new_in_canonical_order(
+        location_allocator: &mut TraceLocationAllocator,
+        evals: impl IntoIterator<Item = (E, LogupSums)>,
+    ) -> Vec<Self> {
+        evals
+            .into_iter()
+            .sorted_by_cached_key(|(eval, logup_sums)| canonical_key(eval, *logup_sums))
+            .map(|(eval, logup_sums)| Self::new(location_allocator, eval, logup_sums))
+            .collect()
+    }
+
     pub fn trace_locations(&self) -> &[TreeSubspan] {
         &self.trace_locations
     }
 }
 
+/// The key [`FrameworkComponent::new_in_canonical_order()`] sorts by: the name of the eval and a
+/// hash of its layout. The layout doesn't depend on the allocator, so the key is known before
+/// allocation.
+fn canonical_key<E: FrameworkEval>(eval: &E, logup_sums: LogupSums) -> (String, [u8; 32]) {
+    let info = eval.evaluate(InfoEvaluator::new(eval.log_size(), vec![], logup_sums));
+    let mut hasher = Blake2sHasher::new();
+    let sizes = [
+        info.n_constraints,
+        eval.max_constraint_log_degree_bound() as usize,
+        eval.log_size() as usize,
+        info.mask_offsets.len(),
+    ]
+    .into_iter()
+    .chain(info.mask_offsets.iter().map(|tree| tree.len()));
+    for size in sizes {
+        hasher.update(&(size as u64).to_le_bytes());
+    }
+    for column in &info.preprocessed_columns {
+        let column = format!("{column:?}");
+        hasher.update(&(column.len() as u64).to_le_bytes());
+        hasher.update(column.as_bytes());
+    }
+    (eval.name(), hasher.finalize().into())
+}
+
 impl<E: FrameworkEval> Component for FrameworkComponent<E> {
+    fn name(&self) -> String {
+        self.eval.name()
+    }
+
     fn n_constraints(&self) -> usize {
         self.info.n_constraints
     }
@@ -183,6 +387,15 @@ impl<E: FrameworkEval> Component for FrameworkComponent<E> {
         self.eval.max_constraint_log_degree_bound()
     }
 
+    fn constraint_degrees(&self) -> Option<Vec<u32>> {
+        let has_partial_sum = self.logup_sums.1.is_some();
+        let degrees = self
+            .eval
+            .evaluate(ExprEvaluator::new(self.eval.log_size(), has_partial_sum))
+            .constraint_degrees();
+        Some(degrees.into_iter().map(|degree| degree as u32).collect())
+    }
+
     fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
         let mut log_degree_bounds = self
             .info
@@ -216,6 +429,32 @@ impl<E: FrameworkEval> Component for FrameworkComponent<E> {
         self.preprocessed_column_indices.clone()
     }
 
+    fn preprocessed_columns(&self) -> Vec<PreprocessedColumn> {
+        self.info.preprocessed_columns.clone()
+    }
+
+    fn allocated_locations(&self) -> Option<TreeVec<TreeSubspan>> {
+        Some(self.trace_locations.clone())
+    }
+
+    fn claimed_logup_sum(&self) -> Option<SecureField> {
+        // Only components with a logup argument have interaction columns.
+        self.info
+            .mask_offsets
+            .get(INTERACTION_TRACE_IDX)
+            .is_some_and(|columns| !columns.is_empty())
+            .then(|| {
+                self.logup_sums.0
+                    + self
+                        .info
+                        .logup
+                        .sub_sums
+                        .iter()
+                        .map(|(total_sum, _)| total_sum)
+                        .sum::<SecureField>()
+            })
+    }
+
     fn evaluate_constraint_quotients_at_point(
         &self,
         point: CirclePoint<SecureField>,
@@ -241,7 +480,99 @@ impl<E: FrameworkEval> Component for FrameworkComponent<E> {
     }
 }
 
+impl<E: FrameworkEval + Sync> FrameworkComponent<E> {
+    /// Proves the component on its own, as the only component of an AIR.
+    ///
+    /// `trace` holds the trees of the component, starting with the preprocessed trace. The trees
+    /// are committed one after the other without drawing from `channel` in between, so components
+    /// whose interaction trace depends on channel randomness must be proven with [prove].
+    pub fn prove_standalone<MC: MerkleChannel>(
+        &self,
+        config: PcsConfig,
+        trace: TreeVec<ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>>,
+        channel: &mut MC::C,
+    ) -> Result<StarkProof<MC::H>, ProvingError>
+    where
+        SimdBackend: BackendForChannel<MC>,
+    {
+        assert_eq!(trace.len(), self.trace_log_degree_bounds().len());
+        let twiddles = SimdBackend::precompute_twiddles(
+            CanonicCoset::new(
+                self.max_constraint_log_degree_bound() + config.fri_config.log_blowup_factor,
+            )
+            .circle_domain()
+            .half_coset,
+        );
+        let mut commitment_scheme =
+            CommitmentSchemeProver::<SimdBackend, MC>::new(config, &twiddles);
+        for tree in trace.0 {
+            let mut tree_builder = commitment_scheme.tree_builder();
+            tree_builder.extend_evals(tree);
+            tree_builder.commit(channel);
+        }
+        prove(&[self], channel, commitment_scheme)
+    }
+
+    /// Verifies a proof of [Self::prove_standalone], where `channel` is in the same state as the
+    /// prover's was.
+    pub fn verify_standalone<MC: MerkleChannel>(
+        &self,
+        config: PcsConfig,
+        proof: StarkProof<MC::H>,
+        channel: &mut MC::C,
+    ) -> Result<(), VerificationError> {
+        let commitment_scheme = &mut CommitmentSchemeVerifier::<MC>::new(config);
+        let sizes = self.trace_log_degree_bounds();
+        if proof.commitments.len() != sizes.len() + 1 {
+            return Err(VerificationError::InvalidStructure(
+                "Unexpected number of commitments".to_string(),
+            ));
+        }
+        for (commitment, tree_sizes) in zip(proof.commitments.iter(), sizes.iter()) {
+            commitment_scheme.commit(*commitment, tree_sizes, channel);
+        }
+        // The component is the whole AIR, so its lookups must balance on their own.
+        verify_with_logup_balance(&[self], channel, commitment_scheme, proof)
+    }
+}
+
 impl<E: FrameworkEval + Sync> ComponentProver<SimdBackend> for FrameworkComponent<E> {
+    fn trace_logup_sum(&self, trace: &Trace<'_, SimdBackend>) -> Option<SecureField> {
+        let interaction = self.trace_locations.get(INTERACTION_TRACE_IDX)?;
+        if interaction.col_start == interaction.col_end {
+            return None;
+        }
+
+        // The cumulative sum column of each logup sub-sum holds the prefix sum of its fractions,
+        // so its value at the last row is the total sum of the sub-sum. The logup columns are the
+        // last columns of the interaction trace.
+        let log_size = self.eval.log_size();
+        let last_row = CanonicCoset::new(log_size)
+            .circle_domain()
+            .at(utils::coset_index_to_circle_domain_index(
+                (1 << log_size) - 1,
+                log_size,
+            ))
+            .into_ef();
+        let logup = &self.info.logup;
+        let logup_col_start = interaction.col_end - logup.n_columns * SECURE_EXTENSION_DEGREE;
+        Some(
+            logup
+                .cumsum_columns
+                .iter()
+                .map(|&cumsum_column| {
+                    let cumsum_col_start =
+                        logup_col_start + cumsum_column * SECURE_EXTENSION_DEGREE;
+                    let cumsum_polys = &trace.polys[INTERACTION_TRACE_IDX]
+                        [cumsum_col_start..cumsum_col_start + SECURE_EXTENSION_DEGREE];
+                    SecureField::from_partial_evals(std::array::from_fn(|i| {
+                        cumsum_polys[i].eval_at_point(last_row)
+                    }))
+                })
+                .sum(),
+        )
+    }
+
     fn evaluate_constraint_quotients_on_domain(
         &self,
         trace: &Trace<'_, SimdBackend>,
@@ -381,6 +712,26 @@ impl<E: FrameworkEval + Sync> ComponentProver<SimdBackend> for FrameworkComponen
     }
 }
 
+impl<B: Backend, E: FrameworkEval> AssertComponent<B> for FrameworkComponent<E> {
+    fn assert_constraints_on_trace(&self, trace_polys: &TreeVec<Vec<CirclePoly<B>>>) {
+        let mut component_polys = trace_polys.sub_tree(&self.trace_locations);
+        component_polys[PREPROCESSED_TRACE_IDX] = self
+            .preprocessed_column_indices
+            .iter()
+            .map(|idx| &trace_polys[PREPROCESSED_TRACE_IDX][*idx])
+            .collect();
+
+        assert_constraints_on_polys(
+            &component_polys,
+            CanonicCoset::new(self.eval.log_size()),
+            |eval| {
+                self.eval.evaluate(eval);
+            },
+            self.logup_sums,
+        );
+    }
+}
+
 impl<E: FrameworkEval> Deref for FrameworkComponent<E> {
     type Target = E;
 
@@ -418,3 +769,182 @@ impl<E: FrameworkEval> Display for FrameworkComponent<E> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{One, Zero};
+
+    use super::{FrameworkComponent, FrameworkEval, SharedLogupEval, TraceLocationAllocator};
+    use crate::constraint_framework::logup::LogupTraceGenerator;
+    use crate::constraint_framework::preprocessed_columns::gen_is_first;
+    use crate::constraint_framework::{
+        assert_constraints, relation, EvalAtRow, Relation, RelationEntry,
+    };
+    use crate::core::backend::simd::column::BaseColumn;
+    use crate::core::backend::simd::m31::LOG_N_LANES;
+    use crate::core::backend::simd::qm31::PackedSecureField;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::channel::Blake2sChannel;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::pcs::{TreeSubspan, TreeVec};
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+    use crate::core::poly::BitReversedOrder;
+
+    const LOG_SIZE: u32 = LOG_N_LANES;
+
+    relation!(TestRelation, 1);
+
+    /// Adds its column to the relation with the given multiplicity.
+    struct Half {
+        relation: TestRelation,
+        multiplicity: SecureField,
+    }
+    impl FrameworkEval for Half {
+        fn name(&self) -> String {
+            "Half".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            LOG_SIZE
+        }
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            LOG_SIZE + 1
+        }
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let value = eval.next_trace_mask();
+            eval.add_to_relation(&[RelationEntry::new(
+                &self.relation,
+                E::EF::from(self.multiplicity),
+                &[value],
+            )]);
+            eval
+        }
+    }
+
+    /// Reads `n_columns` trace columns without constraining them.
+    struct Columns {
+        n_columns: usize,
+        log_size: u32,
+    }
+    impl FrameworkEval for Columns {
+        fn name(&self) -> String {
+            "Columns".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            self.log_size
+        }
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size + 1
+        }
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            for _ in 0..self.n_columns {
+                eval.next_trace_mask();
+            }
+            eval
+        }
+    }
+
+    fn component(
+        allocator: &mut TraceLocationAllocator,
+        n_columns: usize,
+        log_size: u32,
+    ) -> FrameworkComponent<Columns> {
+        FrameworkComponent::new(
+            allocator,
+            Columns {
+                n_columns,
+                log_size,
+            },
+            (SecureField::zero(), None),
+        )
+    }
+
+    #[test]
+    fn test_with_preallocated() {
+        let allocator = &mut TraceLocationAllocator::with_preallocated(TreeVec::new(vec![
+            vec![],
+            vec![4, 4, 6, 6, 6],
+        ]));
+
+        let first = component(allocator, 2, 4);
+        let second = component(allocator, 3, 6);
+
+        let span = |col_start, col_end| TreeSubspan {
+            tree_index: 1,
+            col_start,
+            col_end,
+        };
+        assert_eq!(first.trace_locations()[1], span(0, 2));
+        assert_eq!(second.trace_locations()[1], span(2, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Columns 2..6 of tree 1 exceed the 5 preallocated columns")]
+    fn test_with_preallocated_overflow() {
+        let allocator =
+            &mut TraceLocationAllocator::with_preallocated(TreeVec::new(vec![vec![], vec![4; 5]]));
+
+        component(allocator, 2, 4);
+        component(allocator, 4, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "were preallocated with log sizes [4, 4]")]
+    fn test_with_preallocated_log_size_mismatch() {
+        let allocator =
+            &mut TraceLocationAllocator::with_preallocated(TreeVec::new(vec![vec![], vec![4; 2]]));
+
+        component(allocator, 2, 5);
+    }
+
+    #[test]
+    fn test_shared_logup_eval() {
+        let relation = TestRelation::draw(&mut Blake2sChannel::default());
+        let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+        let values = (0..1 << LOG_SIZE).map(BaseField::from).collect::<Vec<_>>();
+        let trace = [values.clone(), values.into_iter().rev().collect()].map(|values| {
+            CircleEvaluation::<SimdBackend, BaseField, BitReversedOrder>::new(
+                domain,
+                BaseColumn::from_iter(values),
+            )
+        });
+        let mut logup_gen = LogupTraceGenerator::new(LOG_SIZE);
+        for (col, multiplicity) in trace.iter().zip([SecureField::one(), -SecureField::one()]) {
+            let mut col_gen = logup_gen.new_col();
+            for vec_row in 0..1 << (LOG_SIZE - LOG_N_LANES) {
+                let denom: PackedSecureField = relation.combine(&[col.values.data[vec_row]]);
+                col_gen.write_frac(vec_row, multiplicity.into(), denom);
+            }
+            col_gen.finalize_col();
+        }
+        let (interaction_trace, total_sum) = logup_gen.finalize_last();
+        let trace = TreeVec::new(vec![
+            vec![gen_is_first(LOG_SIZE)],
+            trace.to_vec(),
+            interaction_trace,
+        ]);
+        let trace_polys = TreeVec::<Vec<_>>::map_cols(trace, |c| c.interpolate());
+        let eval = SharedLogupEval {
+            first: Half {
+                relation: relation.clone(),
+                multiplicity: SecureField::one(),
+            },
+            second: Half {
+                relation,
+                multiplicity: -SecureField::one(),
+            },
+        };
+
+        assert_eq!(total_sum, SecureField::zero());
+        assert_constraints(
+            &trace_polys,
+            CanonicCoset::new(LOG_SIZE),
+            |e| {
+                eval.evaluate(e);
+            },
+            (total_sum, None),
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::ops::Mul;
 use num_traits::Zero;
 
 use super::logup::{LogupAtRow, LogupSums};
-use super::{EvalAtRow, INTERACTION_TRACE_IDX};
+use super::{validate_mask, EvalAtRow, MaskError, INTERACTION_TRACE_IDX};
 use crate::core::backend::CpuBackend;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
@@ -56,12 +56,31 @@ impl<'a> EvalAtRow for CpuDomainEvaluator<'a> {
     type F = BaseField;
     type EF = SecureField;
 
+    fn validate_next_interaction_mask(
+        &self,
+        interaction: usize,
+        offsets: &[isize],
+    ) -> Result<(), MaskError> {
+        validate_mask(
+            self.trace_eval,
+            &self.column_index_per_interaction,
+            interaction,
+            offsets,
+            self.domain_log_size,
+        )
+    }
+
     // TODO(spapini): Remove all boundary checks.
     fn next_interaction_mask<const N: usize>(
         &mut self,
         interaction: usize,
         offsets: [isize; N],
     ) -> [Self::F; N] {
+        if cfg!(debug_assertions) {
+            if let Err(err) = self.validate_next_interaction_mask(interaction, &offsets) {
+                panic!("{err}");
+            }
+        }
         let col_index = self.column_index_per_interaction[interaction];
         self.column_index_per_interaction[interaction] += 1;
         offsets.map(|off| {
@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 use std::ops::{Add, AddAssign, Index, Mul, MulAssign, Neg, Sub};
 
 use itertools::sorted;
@@ -6,7 +7,11 @@ use num_traits::{One, Zero};
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
 
-use super::{AssertEvaluator, EvalAtRow, Relation, RelationEntry, INTERACTION_TRACE_IDX};
+use super::logup::LogupSums;
+use super::{
+    AssertEvaluator, EvalAtRow, FrameworkEval, Relation, RelationEntry, INTERACTION_TRACE_IDX,
+    PREPROCESSED_TRACE_IDX,
+};
 use crate::core::fields::cm31::CM31;
 use crate::core::fields::m31::{self, BaseField};
 use crate::core::fields::qm31::{SecureField, QM31};
@@ -249,6 +254,56 @@ impl BaseExpr {
         }
     }
 
+    /// Returns the multiplicative degree of the expression in the trace columns.
+    ///
+    /// Columns have degree 1, constants and parameters have degree 0. Sums take the maximal degree
+    /// of their terms, products the sum of the degrees of their factors, and an inverse the degree
+    /// of its argument.
+    pub fn degree(&self) -> usize {
+        self.degree_with_params(&HashMap::new())
+    }
+
+    /// Like [`Self::degree()`], where the parameters in `param_degrees`, e.g. intermediates, have
+    /// the given degrees.
+    pub fn degree_with_params(&self, param_degrees: &HashMap<String, usize>) -> usize {
+        match self {
+            BaseExpr::Col(_) => 1,
+            BaseExpr::Const(_) => 0,
+            BaseExpr::Param(param) => param_degrees.get(param).copied().unwrap_or_default(),
+            BaseExpr::Add(a, b) | BaseExpr::Sub(a, b) => a
+                .degree_with_params(param_degrees)
+                .max(b.degree_with_params(param_degrees)),
+            BaseExpr::Mul(a, b) => {
+                a.degree_with_params(param_degrees) + b.degree_with_params(param_degrees)
+            }
+            BaseExpr::Neg(a) | BaseExpr::Inv(a) => a.degree_with_params(param_degrees),
+        }
+    }
+
+    /// Replaces the parameters in `params` by their expressions.
+    pub fn substitute_params(&self, params: &HashMap<String, BaseExpr>) -> Self {
+        match self {
+            BaseExpr::Col(_) | BaseExpr::Const(_) => self.clone(),
+            BaseExpr::Param(param) => params.get(param).cloned().unwrap_or_else(|| self.clone()),
+            BaseExpr::Add(a, b) => a.substitute_params(params) + b.substitute_params(params),
+            BaseExpr::Sub(a, b) => a.substitute_params(params) - b.substitute_params(params),
+            BaseExpr::Mul(a, b) => a.substitute_params(params) * b.substitute_params(params),
+            BaseExpr::Neg(a) => -a.substitute_params(params),
+            BaseExpr::Inv(a) => BaseExpr::Inv(Box::new(a.substitute_params(params))),
+        }
+    }
+
+    /// Binding strength of the outermost operator, used by the [Display] implementation to
+    /// parenthesize operands.
+    const fn precedence(&self) -> u8 {
+        match self {
+            BaseExpr::Add(..) | BaseExpr::Sub(..) => 0,
+            BaseExpr::Mul(..) | BaseExpr::Inv(..) => 1,
+            BaseExpr::Neg(..) => 2,
+            BaseExpr::Col(..) | BaseExpr::Const(..) | BaseExpr::Param(..) => 3,
+        }
+    }
+
     pub fn random_eval(&self) -> BaseField {
         let assignment = self.collect_variables().random_assignment();
         assert!(assignment.2.is_empty());
@@ -256,6 +311,92 @@ impl BaseExpr {
     }
 }
 
+/// Writes `expr`, parenthesized if `parenthesize` is set.
+fn fmt_operand(f: &mut Formatter<'_>, expr: &impl Display, parenthesize: bool) -> std::fmt::Result {
+    if parenthesize {
+        write!(f, "({expr})")
+    } else {
+        write!(f, "{expr}")
+    }
+}
+
+/// Displays masks as `col{idx}[{interaction}][{offset}]`, constants as their field value, and
+/// parenthesizes operands only where the precedence of the operators requires it.
+impl Display for BaseExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let prec = self.precedence();
+        match self {
+            BaseExpr::Col(ColumnExpr {
+                interaction,
+                idx,
+                offset,
+            }) => {
+                if *offset == CLAIMED_SUM_DUMMY_OFFSET as isize {
+                    write!(f, "col{idx}[{interaction}][claimed_sum_offset]")
+                } else {
+                    write!(f, "col{idx}[{interaction}][{offset}]")
+                }
+            }
+            BaseExpr::Const(c) => write!(f, "{c}"),
+            BaseExpr::Param(v) => write!(f, "{v}"),
+            BaseExpr::Add(a, b) => write!(f, "{a} + {b}"),
+            BaseExpr::Sub(a, b) => {
+                write!(f, "{a} - ")?;
+                fmt_operand(f, b, b.precedence() <= prec)
+            }
+            BaseExpr::Mul(a, b) => {
+                fmt_operand(f, a, a.precedence() < prec)?;
+                write!(f, " * ")?;
+                fmt_operand(f, b, b.precedence() < prec)
+            }
+            BaseExpr::Neg(a) => {
+                write!(f, "-")?;
+                fmt_operand(f, a, a.precedence() <= prec)
+            }
+            BaseExpr::Inv(a) => {
+                write!(f, "1 / ")?;
+                fmt_operand(f, a, a.precedence() <= prec)
+            }
+        }
+    }
+}
+
+/// See the [Display] implementation of [BaseExpr]. Secure columns whose non-base coordinates are
+/// zero, and base field constants, are displayed as base field expressions.
+impl Display for ExtExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(base) = self.as_base() {
+            return write!(f, "{base}");
+        }
+        let prec = self.precedence();
+        match self {
+            ExtExpr::SecureCol([a, b, c, d]) => write!(f, "SecureCol({a}, {b}, {c}, {d})"),
+            ExtExpr::Const(c) => {
+                if c.0 .1.is_zero() && c.1 .0.is_zero() && c.1 .1.is_zero() {
+                    write!(f, "{}", c.0 .0)
+                } else {
+                    write!(f, "{c}")
+                }
+            }
+            ExtExpr::Param(v) => write!(f, "{v}"),
+            ExtExpr::Add(a, b) => write!(f, "{a} + {b}"),
+            ExtExpr::Sub(a, b) => {
+                write!(f, "{a} - ")?;
+                fmt_operand(f, b, b.precedence() <= prec)
+            }
+            ExtExpr::Mul(a, b) => {
+                fmt_operand(f, a, a.precedence() < prec)?;
+                write!(f, " * ")?;
+                fmt_operand(f, b, b.precedence() < prec)
+            }
+            ExtExpr::Neg(a) => {
+                write!(f, "-")?;
+                fmt_operand(f, a, a.precedence() <= prec)
+            }
+        }
+    }
+}
+
 impl ExtExpr {
     pub fn format_expr(&self) -> String {
         match self {
@@ -382,6 +523,93 @@ impl ExtExpr {
         }
     }
 
+    /// Returns the multiplicative degree of the expression in the trace columns, see
+    /// [`BaseExpr::degree()`]. A secure column has the maximal degree of its coordinates.
+    pub fn degree(&self) -> usize {
+        self.degree_with_params(&HashMap::new())
+    }
+
+    /// Like [`Self::degree()`], where the parameters in `param_degrees`, e.g. intermediates, have
+    /// the given degrees.
+    pub fn degree_with_params(&self, param_degrees: &HashMap<String, usize>) -> usize {
+        match self {
+            ExtExpr::SecureCol(coordinates) => coordinates
+                .iter()
+                .map(|coordinate| coordinate.degree_with_params(param_degrees))
+                .max()
+                .unwrap(),
+            ExtExpr::Const(_) => 0,
+            ExtExpr::Param(param) => param_degrees.get(param).copied().unwrap_or_default(),
+            ExtExpr::Add(a, b) | ExtExpr::Sub(a, b) => a
+                .degree_with_params(param_degrees)
+                .max(b.degree_with_params(param_degrees)),
+            ExtExpr::Mul(a, b) => {
+                a.degree_with_params(param_degrees) + b.degree_with_params(param_degrees)
+            }
+            ExtExpr::Neg(a) => a.degree_with_params(param_degrees),
+        }
+    }
+
+    /// Replaces the base field parameters in `params` and the extension field parameters in
+    /// `ext_params` by their expressions.
+    pub fn substitute_params(
+        &self,
+        params: &HashMap<String, BaseExpr>,
+        ext_params: &HashMap<String, ExtExpr>,
+    ) -> Self {
+        match self {
+            ExtExpr::SecureCol(coordinates) => ExtExpr::SecureCol(
+                coordinates
+                    .each_ref()
+                    .map(|coordinate| Box::new(coordinate.substitute_params(params))),
+            ),
+            ExtExpr::Const(_) => self.clone(),
+            ExtExpr::Param(param) => ext_params
+                .get(param)
+                .cloned()
+                .unwrap_or_else(|| self.clone()),
+            ExtExpr::Add(a, b) => {
+                a.substitute_params(params, ext_params) + b.substitute_params(params, ext_params)
+            }
+            ExtExpr::Sub(a, b) => {
+                a.substitute_params(params, ext_params) - b.substitute_params(params, ext_params)
+            }
+            ExtExpr::Mul(a, b) => {
+                a.substitute_params(params, ext_params) * b.substitute_params(params, ext_params)
+            }
+            ExtExpr::Neg(a) => -a.substitute_params(params, ext_params),
+        }
+    }
+
+    /// Returns the base field expression if the non-base coordinates are all constant zeroes.
+    fn as_base(&self) -> Option<&BaseExpr> {
+        match self {
+            ExtExpr::SecureCol([a, b, c, d])
+                if **b == BaseExpr::zero()
+                    && **c == BaseExpr::zero()
+                    && **d == BaseExpr::zero() =>
+            {
+                Some(a)
+            }
+            _ => None,
+        }
+    }
+
+    /// See [`BaseExpr::precedence()`].
+    fn precedence(&self) -> u8 {
+        if let Some(base) = self.as_base() {
+            return base.precedence();
+        }
+        match self {
+            ExtExpr::Add(..) | ExtExpr::Sub(..) => 0,
+            ExtExpr::Mul(..) => 1,
+            ExtExpr::Neg(..) => 2,
+            // A non-base constant is displayed as a sum.
+            ExtExpr::Const(c) if !(c.0 .1.is_zero() && c.1 .0.is_zero() && c.1 .1.is_zero()) => 0,
+            ExtExpr::SecureCol(..) | ExtExpr::Const(..) | ExtExpr::Param(..) => 3,
+        }
+    }
+
     pub fn random_eval(&self) -> SecureField {
         let assignment = self.collect_variables().random_assignment();
         self.eval_expr::<AssertEvaluator<'_>, _, _, _>(&assignment.0, &assignment.1, &assignment.2)
@@ -760,6 +988,9 @@ pub struct FormalLogupAtRow {
     pub is_finalized: bool,
     pub is_first: BaseExpr,
     pub log_size: u32,
+    pub sub_sums: Vec<LogupSums>,
+    pub n_columns: usize,
+    pub cumsum_columns: Vec<usize>,
 }
 
 // P is an offset no column can reach, it signifies the variable
@@ -782,6 +1013,9 @@ impl FormalLogupAtRow {
             is_finalized: true,
             is_first: BaseExpr::zero(),
             log_size,
+            sub_sums: vec![],
+            n_columns: 0,
+            cumsum_columns: vec![],
         }
     }
 }
@@ -789,6 +1023,10 @@ impl FormalLogupAtRow {
 /// An Evaluator that saves all constraint expressions.
 pub struct ExprEvaluator {
     pub cur_var_index: usize,
+    /// The interaction, and the index of the column in it, of each mask variable.
+    pub var_columns: Vec<(usize, usize)>,
+    /// The number of columns of each interaction requested so far.
+    pub n_interaction_columns: Vec<usize>,
     pub constraints: Vec<ExtExpr>,
     pub logup: FormalLogupAtRow,
     pub intermediates: Vec<(String, BaseExpr)>,
@@ -799,6 +1037,8 @@ impl ExprEvaluator {
     pub fn new(log_size: u32, has_partial_sum: bool) -> Self {
         Self {
             cur_var_index: Default::default(),
+            var_columns: vec![],
+            n_interaction_columns: vec![],
             constraints: Default::default(),
             logup: FormalLogupAtRow::new(INTERACTION_TRACE_IDX, has_partial_sum, log_size),
             intermediates: vec![],
@@ -838,6 +1078,81 @@ impl ExprEvaluator {
     }
 }
 
+impl ExprEvaluator {
+    /// Returns the constraints with all intermediates replaced by their expressions.
+    pub fn inlined_constraints(&self) -> Vec<ExtExpr> {
+        let mut params = HashMap::new();
+        for (name, expr) in &self.intermediates {
+            params.insert(name.clone(), expr.substitute_params(&params));
+        }
+        let mut ext_params = HashMap::new();
+        for (name, expr) in &self.ext_intermediates {
+            ext_params.insert(name.clone(), expr.substitute_params(&params, &ext_params));
+        }
+        self.constraints
+            .iter()
+            .map(|constraint| constraint.substitute_params(&params, &ext_params))
+            .collect()
+    }
+
+    /// Returns the degree of each constraint, see [`ExtExpr::degree()`].
+    ///
+    /// Intermediates count with the degree of the expression they stand for.
+    pub fn constraint_degrees(&self) -> Vec<usize> {
+        let mut param_degrees = HashMap::new();
+        for (name, expr) in &self.intermediates {
+            param_degrees.insert(name.clone(), expr.degree_with_params(&param_degrees));
+        }
+        for (name, expr) in &self.ext_intermediates {
+            param_degrees.insert(name.clone(), expr.degree_with_params(&param_degrees));
+        }
+        self.constraints
+            .iter()
+            .map(|constraint| constraint.degree_with_params(&param_degrees))
+            .collect()
+    }
+
+    /// Returns the maximal degree of the constraints, see [`Self::constraint_degrees()`].
+    pub fn max_constraint_degree(&self) -> usize {
+        self.constraint_degrees()
+            .into_iter()
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the maximal degree of the constraints of `eval`, see [`ExtExpr::degree()`].
+pub fn max_constraint_degree(eval: &impl FrameworkEval) -> usize {
+    eval.evaluate(ExprEvaluator::new(eval.log_size(), false))
+        .max_constraint_degree()
+}
+
+/// Returns the columns of the original and interaction traces of `eval` that no constraint
+/// refers to, as `(interaction, column index)` pairs, ordered by interaction then column.
+///
+/// Such columns are committed but never constrained, so they can usually be removed from the
+/// trace. Preprocessed columns are not reported.
+pub fn unused_columns(eval: &impl FrameworkEval) -> Vec<(usize, usize)> {
+    let expr_eval = eval.evaluate(ExprEvaluator::new(eval.log_size(), false));
+    let used_columns = expr_eval
+        .inlined_constraints()
+        .iter()
+        .flat_map(|constraint| constraint.collect_variables().cols)
+        .map(|col| expr_eval.var_columns[col.idx])
+        .collect::<HashSet<_>>();
+
+    expr_eval
+        .n_interaction_columns
+        .iter()
+        .enumerate()
+        .skip(PREPROCESSED_TRACE_IDX + 1)
+        .flat_map(|(interaction, &n_columns)| {
+            (0..n_columns).map(move |col_index| (interaction, col_index))
+        })
+        .filter(|column| !used_columns.contains(column))
+        .collect()
+}
+
 impl EvalAtRow for ExprEvaluator {
     // TODO(alont): Should there be a version of this that disallows Secure fields for F?
     type F = BaseExpr;
@@ -848,9 +1163,15 @@ impl EvalAtRow for ExprEvaluator {
         interaction: usize,
         offsets: [isize; N],
     ) -> [Self::F; N] {
+        if self.n_interaction_columns.len() <= interaction {
+            self.n_interaction_columns.resize(interaction + 1, 0);
+        }
+        let col_index = self.n_interaction_columns[interaction];
+        self.n_interaction_columns[interaction] += 1;
         std::array::from_fn(|i| {
             let col = ColumnExpr::from((interaction, self.cur_var_index, offsets[i]));
             self.cur_var_index += 1;
+            self.var_columns.push((interaction, col_index));
             BaseExpr::Col(col)
         })
     }
@@ -923,14 +1244,16 @@ mod tests {
     use rand::rngs::SmallRng;
     use rand::{Rng, SeedableRng};
 
-    use super::{BaseExpr, ExtExpr};
+    use super::{max_constraint_degree, unused_columns, BaseExpr, ExtExpr};
     use crate::constraint_framework::expr::ExprEvaluator;
     use crate::constraint_framework::{
-        relation, AssertEvaluator, EvalAtRow, FrameworkEval, RelationEntry,
+        relation, AssertEvaluator, EvalAtRow, FrameworkEval, InfoEvaluator, RelationEntry,
+        ORIGINAL_TRACE_IDX,
     };
     use crate::core::fields::m31::{self, BaseField};
     use crate::core::fields::qm31::SecureField;
     use crate::core::fields::FieldExpOps;
+    use crate::examples::wide_fibonacci::WideFibonacciEval;
 
     macro_rules! secure_col {
         ($a:expr, $b:expr, $c:expr, $d:expr) => {
@@ -1099,10 +1422,213 @@ mod tests {
         assert_eq!(eval.format_constraints(), expected);
     }
 
+    #[test]
+    fn test_expr_degree() {
+        let x = col!(1, 0, 0);
+        let y = col!(1, 1, -1);
+
+        assert_eq!(felt!(3).degree(), 0);
+        assert_eq!(var!("a").degree(), 0);
+        assert_eq!(x.degree(), 1);
+        assert_eq!((x.clone() * y.clone() + felt!(5)).degree(), 2);
+        assert_eq!((x.clone() * x.clone() * y.clone() - x.clone()).degree(), 3);
+        assert_eq!(
+            (secure_col!(x.clone() * y.clone(), x.clone(), felt!(0), felt!(0)) * qvar!("c"))
+                .degree(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_expr_display() {
+        let x = col!(1, 0, 0);
+        let y = col!(1, 1, -1);
+
+        assert_eq!(x.to_string(), "col0[1][0]");
+        assert_eq!(
+            (x.clone() + y.clone() * felt!(3)).to_string(),
+            "col0[1][0] + col1[1][-1] * 3"
+        );
+        assert_eq!(
+            ((x.clone() + y.clone()) * (x.clone() - y.clone())).to_string(),
+            "(col0[1][0] + col1[1][-1]) * (col0[1][0] - col1[1][-1])"
+        );
+        assert_eq!(
+            (x.clone() - (y.clone() - x.clone())).to_string(),
+            "col0[1][0] - (col1[1][-1] - col0[1][0])"
+        );
+        assert_eq!(
+            (-(x.clone() * y.clone())).to_string(),
+            "-(col0[1][0] * col1[1][-1])"
+        );
+        assert_eq!(
+            BaseExpr::Inv(Box::new(x.clone() * y.clone())).to_string(),
+            "1 / (col0[1][0] * col1[1][-1])"
+        );
+        assert_eq!(
+            (qvar!("a") * ExtExpr::from(x.clone() + y) - qvar!("b")).to_string(),
+            "a * (col0[1][0] + col1[1][-1]) - b"
+        );
+    }
+
+    #[test]
+    fn test_format_constraints_wide_fibonacci() {
+        let eval = WideFibonacciEval::<4> { log_n_rows: 4 };
+
+        assert_eq!(
+            eval.format_constraints(),
+            [
+                "col2[1][0] - (col0[1][0] * col0[1][0] + col1[1][0] * col1[1][0])",
+                "col3[1][0] - (col1[1][0] * col1[1][0] + col2[1][0] * col2[1][0])",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_constraints_inlines_intermediates() {
+        let constraints = TestStruct {}.format_constraints();
+
+        assert_eq!(constraints.len(), 2);
+        assert!(!constraints[0].contains("intermediate"));
+    }
+
+    struct ConditionalEval {
+        explicit: bool,
+        squared_selector: bool,
+    }
+    impl FrameworkEval for ConditionalEval {
+        fn name(&self) -> String {
+            "ConditionalEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            4
+        }
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            6
+        }
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let [selector, a, b, c] = eval.next_trace_masks();
+            let selector = if self.squared_selector {
+                selector.clone() * selector
+            } else {
+                selector
+            };
+            if self.explicit {
+                eval.add_constraint(selector * (a * b - c));
+            } else {
+                eval.add_constraint_if(selector, a * b, c);
+            }
+            eval
+        }
+    }
+
+    #[test]
+    fn test_add_constraint_if() {
+        for (squared_selector, expected_degree) in [(false, 3), (true, 4)] {
+            let conditional = ConditionalEval {
+                explicit: false,
+                squared_selector,
+            };
+            let info = conditional.evaluate(InfoEvaluator::empty());
+
+            assert_eq!(
+                conditional.format_constraints(),
+                ConditionalEval {
+                    explicit: true,
+                    squared_selector,
+                }
+                .format_constraints()
+            );
+            assert_eq!(max_constraint_degree(&conditional), expected_degree);
+            assert_eq!(info.n_constraints, 1);
+        }
+    }
+
+    #[test]
+    fn test_max_constraint_degree() {
+        // `col_1_0 * intermediate0 / (col_1_0 + col_1_1)`, where `intermediate0 = col_1_1 *
+        // col_1_2`.
+        assert_eq!(max_constraint_degree(&TestStruct {}), 4);
+    }
+
+    /// Has a trace column that no constraint refers to.
+    struct UnusedColumnEval;
+    impl FrameworkEval for UnusedColumnEval {
+        fn name(&self) -> String {
+            "UnusedColumnEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            4
+        }
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            5
+        }
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let [a, _unused, b] = eval.next_trace_masks();
+            let [c, _] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+            eval.add_constraint(a * b - c);
+            eval
+        }
+    }
+
+    #[test]
+    fn test_unused_columns() {
+        assert_eq!(unused_columns(&UnusedColumnEval), [(ORIGINAL_TRACE_IDX, 1)]);
+        // Columns used only in intermediates and in logup fractions count as used.
+        assert_eq!(unused_columns(&TestStruct {}), []);
+        assert_eq!(unused_columns(&RangeCheckEval { explicit: false }), []);
+    }
+
+    #[test]
+    fn test_add_range_check() {
+        let range_check =
+            RangeCheckEval { explicit: false }.evaluate(ExprEvaluator::new(16, false));
+        let explicit = RangeCheckEval { explicit: true }.evaluate(ExprEvaluator::new(16, false));
+
+        assert_eq!(
+            range_check.format_constraints(),
+            explicit.format_constraints()
+        );
+    }
+
     relation!(TestRelation, 3);
+    relation!(RangeCheckRelation, 1);
+
+    struct RangeCheckEval {
+        explicit: bool,
+    }
+    impl FrameworkEval for RangeCheckEval {
+        fn name(&self) -> String {
+            "RangeCheckEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            0
+        }
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            0
+        }
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let value = eval.next_trace_mask();
+            let relation = RangeCheckRelation::dummy();
+            if self.explicit {
+                eval.add_to_relation(&[RelationEntry::new(&relation, E::EF::one(), &[value])]);
+            } else {
+                eval.add_range_check(&relation, value);
+            }
+            eval.finalize_logup();
+            eval
+        }
+    }
 
     struct TestStruct {}
     impl FrameworkEval for TestStruct {
+        fn name(&self) -> String {
+            "TestStruct".to_string()
+        }
+
         fn log_size(&self) -> u32 {
             0
         }
@@ -1,7 +1,6 @@
 use std::array;
-use std::cell::{RefCell, RefMut};
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub};
-use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use num_traits::{One, Zero};
 
@@ -46,6 +45,21 @@ impl InfoEvaluator {
     pub fn empty() -> Self {
         Self::new(16, vec![], (SecureField::default(), None))
     }
+
+    /// Returns the number of columns requested from each interaction, in the tree structure of the
+    /// component's trace. Preprocessed columns are counted once per request, see
+    /// [`EvalAtRow::get_preprocessed_column`].
+    pub fn n_columns_per_interaction(&self) -> TreeVec<usize> {
+        let mut n_columns = self
+            .mask_offsets
+            .as_ref()
+            .map(|tree_offsets| tree_offsets.len());
+        if n_columns.is_empty() {
+            n_columns.push(0);
+        }
+        n_columns[PREPROCESSED_TRACE_IDX] = self.preprocessed_columns.len();
+        n_columns
+    }
 }
 impl EvalAtRow for InfoEvaluator {
     type F = FieldCounter;
@@ -148,7 +162,7 @@ impl ArithmeticCounts {
 }
 
 #[derive(Debug, Default, Clone)]
-pub struct ArithmeticCounter<const IS_EXT_FIELD: bool>(Rc<RefCell<ArithmeticCounts>>);
+pub struct ArithmeticCounter<const IS_EXT_FIELD: bool>(Arc<Mutex<ArithmeticCounts>>);
 
 /// Counts operations on [`EvalAtRow::F`].
 pub type FieldCounter = ArithmeticCounter<false>;
@@ -162,7 +176,7 @@ impl<const IS_EXT_FIELD: bool> ArithmeticCounter<IS_EXT_FIELD> {
         other: ArithmeticCounter<OTHER_IS_EXT_FIELD>,
     ) {
         // Skip if they come from the same source.
-        if Rc::ptr_eq(&self.0, &other.0) {
+        if Arc::ptr_eq(&self.0, &other.0) {
             return;
         }
 
@@ -170,11 +184,11 @@ impl<const IS_EXT_FIELD: bool> ArithmeticCounter<IS_EXT_FIELD> {
     }
 
     fn drain(self) -> ArithmeticCounts {
-        self.0.take()
+        std::mem::take(&mut *self.0.lock().unwrap())
     }
 
-    fn counts(&mut self) -> RefMut<'_, ArithmeticCounts> {
-        self.0.borrow_mut()
+    fn counts(&mut self) -> MutexGuard<'_, ArithmeticCounts> {
+        self.0.lock().unwrap()
     }
 }
 
@@ -388,8 +402,31 @@ mod tests {
 
     use super::ExtensionFieldCounter;
     use crate::constraint_framework::info::{ArithmeticCounts, FieldCounter};
+    use crate::constraint_framework::preprocessed_columns::PreprocessedColumn;
+    use crate::constraint_framework::{
+        EvalAtRow, FrameworkEval, InfoEvaluator, INTERACTION_TRACE_IDX,
+    };
     use crate::core::fields::m31::BaseField;
     use crate::core::fields::qm31::SecureField;
+    use crate::examples::wide_fibonacci::WideFibonacciEval;
+
+    const FIB_SEQUENCE_LENGTH: usize = 16;
+
+    #[test]
+    fn test_n_columns_per_interaction() {
+        let eval = WideFibonacciEval::<FIB_SEQUENCE_LENGTH> { log_n_rows: 4 };
+        let info = eval.evaluate(InfoEvaluator::empty());
+
+        assert_eq!(info.n_columns_per_interaction().0, [0, FIB_SEQUENCE_LENGTH]);
+
+        let mut info = InfoEvaluator::empty();
+        info.get_preprocessed_column(PreprocessedColumn::IsFirst(4));
+        info.next_trace_masks::<2>();
+        info.next_interaction_mask(INTERACTION_TRACE_IDX, [0, -1]);
+
+        assert_eq!(info.n_columns_per_interaction().0, [1, 2, 1]);
+        assert_eq!(InfoEvaluator::empty().n_columns_per_interaction().0, [0]);
+    }
 
     #[test]
     fn test_arithmetic_counter() {
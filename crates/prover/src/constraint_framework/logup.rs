@@ -3,7 +3,7 @@ use std::ops::{Mul, Sub};
 use itertools::Itertools;
 use num_traits::{One, Zero};
 
-use super::EvalAtRow;
+use super::{EvalAtRow, Relation, RelationEFTraitBound};
 use crate::core::backend::simd::column::SecureColumn;
 use crate::core::backend::simd::m31::LOG_N_LANES;
 use crate::core::backend::simd::prefix_sum::inclusive_prefix_sum;
@@ -18,7 +18,7 @@ use crate::core::fields::FieldExpOps;
 use crate::core::lookups::utils::Fraction;
 use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
 use crate::core::poly::BitReversedOrder;
-use crate::core::utils::{bit_reverse_index, coset_index_to_circle_domain_index};
+use crate::core::utils::{bit_reverse_index, coset_index_to_circle_domain_index, powers_of};
 use crate::core::ColumnVec;
 
 /// Represents the value of the prefix sum column at some index.
@@ -37,6 +37,25 @@ impl LogupSumsExt for LogupSums {
     }
 }
 
+/// Checks that the values of `column` sum up to the claimed sum, from the first row up to and
+/// including the claimed index.
+///
+/// `column` is in bit-reversed circle domain order, like the logup trace columns, and the claimed
+/// index is a row index, as in [`LogupTraceGenerator::finalize_at()`].
+pub fn validate_prefix_sum(column: &SecureColumn, (claimed_sum, index): ClaimedPrefixSum) -> bool {
+    if index >= column.len() {
+        return false;
+    }
+    let log_size = column.len().ilog2();
+    let prefix_sum: SecureField = (0..=index)
+        .map(|row| {
+            let circle_domain_index = coset_index_to_circle_domain_index(row, log_size);
+            column.at(bit_reverse_index(circle_domain_index, log_size))
+        })
+        .sum();
+    prefix_sum == claimed_sum
+}
+
 /// Evaluates constraints for batched logups.
 /// These constraint enforce the sum of multiplicity_i / (z + sum_j alpha^j * x_j) = claimed_sum.
 pub struct LogupAtRow<E: EvalAtRow> {
@@ -56,6 +75,13 @@ pub struct LogupAtRow<E: EvalAtRow> {
     /// See [`super::preprocessed_columns::gen_is_first()`].
     pub is_first: E::F,
     pub log_size: u32,
+    /// The sums of the sub-sums started after the first, see [`EvalAtRow::start_logup_sub_sum()`].
+    pub sub_sums: Vec<LogupSums>,
+    /// The number of [SecureField] columns of the interaction trace written so far.
+    pub n_columns: usize,
+    /// The index, among the columns written, of the cumulative sum column of each finalized
+    /// sub-sum.
+    pub cumsum_columns: Vec<usize>,
 }
 
 impl<E: EvalAtRow> Default for LogupAtRow<E> {
@@ -79,6 +105,9 @@ impl<E: EvalAtRow> LogupAtRow<E> {
             is_finalized: true,
             is_first: E::F::zero(),
             log_size,
+            sub_sums: vec![],
+            n_columns: 0,
+            cumsum_columns: vec![],
         }
     }
 
@@ -93,6 +122,9 @@ impl<E: EvalAtRow> LogupAtRow<E> {
             is_finalized: true,
             is_first: E::F::zero(),
             log_size: 10,
+            sub_sums: vec![],
+            n_columns: 0,
+            cumsum_columns: vec![],
         }
     }
 }
@@ -115,12 +147,7 @@ pub struct LookupElements<const N: usize> {
 impl<const N: usize> LookupElements<N> {
     pub fn draw(channel: &mut impl Channel) -> Self {
         let [z, alpha] = channel.draw_felts(2).try_into().unwrap();
-        let mut cur = SecureField::one();
-        let alpha_powers = std::array::from_fn(|_| {
-            let res = cur;
-            cur *= alpha;
-            res
-        });
+        let alpha_powers = powers_of(alpha, N).try_into().unwrap();
         Self {
             z,
             alpha,
@@ -153,6 +180,46 @@ impl<const N: usize> LookupElements<N> {
     }
 }
 
+/// A relation whose entries are products of consecutive pairs of values, i.e. for values
+/// `(a_0, b_0, a_1, b_1, ...)` combines to `sum_i alpha^i * a_i * b_i - z`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProductRelation<const N: usize>(pub LookupElements<N>);
+
+impl<const N: usize> ProductRelation<N> {
+    pub fn draw(channel: &mut impl Channel) -> Self {
+        Self(LookupElements::draw(channel))
+    }
+
+    pub fn dummy() -> Self {
+        Self(LookupElements::dummy())
+    }
+}
+
+impl<F: Clone, EF: RelationEFTraitBound<F>, const N: usize> Relation<F, EF> for ProductRelation<N> {
+    fn combine(&self, values: &[F]) -> EF {
+        assert!(values.len() % 2 == 0, "Values must come in pairs");
+        values
+            .chunks_exact(2)
+            .zip(self.0.alpha_powers)
+            .fold(EF::zero(), |acc, (pair, power)| {
+                acc + EF::from(power) * pair[0].clone() * pair[1].clone()
+            })
+            - EF::from(self.0.z)
+    }
+
+    fn get_name(&self) -> &str {
+        "ProductRelation"
+    }
+
+    fn get_size(&self) -> usize {
+        2 * N
+    }
+
+    fn combine_degree(&self) -> usize {
+        2
+    }
+}
+
 // SIMD backend generator for logup interaction trace.
 pub struct LogupTraceGenerator {
     log_size: u32,
@@ -284,11 +351,161 @@ impl<'a> LogupColGenerator<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::LookupElements;
+    use num_traits::{One, Zero};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{
+        validate_prefix_sum, LogupSums, LogupTraceGenerator, LookupElements, ProductRelation,
+    };
+    use crate::constraint_framework::preprocessed_columns::gen_is_first;
+    use crate::constraint_framework::{
+        assert_constraints, relation, relation_with_tag, EvalAtRow, FrameworkComponent,
+        FrameworkEval, Relation, RelationEntry, TraceLocationAllocator,
+    };
+    use crate::core::air::{Component, ComponentProvers, Trace};
+    use crate::core::backend::simd::column::{BaseColumn, SecureColumn};
+    use crate::core::backend::simd::m31::{PackedBaseField, LOG_N_LANES};
+    use crate::core::backend::simd::qm31::PackedSecureField;
     use crate::core::channel::Blake2sChannel;
-    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::m31::{BaseField, P};
     use crate::core::fields::qm31::SecureField;
     use crate::core::fields::FieldExpOps;
+    use crate::core::lookups::utils::Fraction;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+
+    relation!(FirstRelation, 1);
+    relation!(SecondRelation, 1);
+    relation_with_tag!(TaggedRelation, 2, 1);
+    relation!(ShiftedRelation, 3);
+
+    /// Uses `a` in two independent logup arguments, the second with a claimed prefix sum.
+    struct TwoSumsEval {
+        log_size: u32,
+        first: FirstRelation,
+        second: SecondRelation,
+        second_sums: LogupSums,
+    }
+
+    impl FrameworkEval for TwoSumsEval {
+        fn name(&self) -> String {
+            "TwoSumsEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            self.log_size
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let a = eval.next_trace_mask();
+
+            eval.add_to_relation(&[RelationEntry::new(&self.first, E::EF::one(), &[a.clone()])]);
+            eval.finalize_logup();
+
+            eval.start_logup_sub_sum(self.second_sums);
+            eval.add_to_relation(&[RelationEntry::new(&self.second, E::EF::one(), &[a.clone()])]);
+            eval.add_to_relation(&[RelationEntry::new(
+                &self.second,
+                -E::EF::one(),
+                &[a + E::F::one()],
+            )]);
+            eval.finalize_logup();
+            eval
+        }
+    }
+
+    #[test]
+    fn test_logup_sub_sums() {
+        const LOG_SIZE: u32 = 5;
+        const CLAIMED_ROW: usize = 20;
+        let mut rng = SmallRng::seed_from_u64(0);
+        let channel = &mut Blake2sChannel::default();
+        let (first, second) = (FirstRelation::draw(channel), SecondRelation::draw(channel));
+        let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+        let a = (0..1 << LOG_SIZE)
+            .map(|_| rng.gen::<BaseField>())
+            .collect::<BaseColumn>();
+        let write_fracs = |logup_gen: &mut LogupTraceGenerator,
+                           relation: &dyn Fn(PackedBaseField) -> PackedSecureField,
+                           sign: PackedSecureField| {
+            let mut col_gen = logup_gen.new_col();
+            for vec_row in 0..1 << (LOG_SIZE - LOG_N_LANES) {
+                col_gen.write_frac(vec_row, sign, relation(a.data[vec_row]));
+            }
+            col_gen.finalize_col();
+        };
+
+        let mut first_gen = LogupTraceGenerator::new(LOG_SIZE);
+        write_fracs(
+            &mut first_gen,
+            &|a| first.combine(&[a]),
+            PackedSecureField::one(),
+        );
+        let (first_trace, first_total_sum) = first_gen.finalize_last();
+        let mut second_gen = LogupTraceGenerator::new(LOG_SIZE);
+        write_fracs(
+            &mut second_gen,
+            &|a| second.combine(&[a]),
+            PackedSecureField::one(),
+        );
+        write_fracs(
+            &mut second_gen,
+            &|a| second.combine(&[a + PackedBaseField::one()]),
+            -PackedSecureField::one(),
+        );
+        let (second_trace, [second_total_sum, second_claimed_sum]) =
+            second_gen.finalize_at([(1 << LOG_SIZE) - 1, CLAIMED_ROW]);
+
+        let first_sums = (first_total_sum, None);
+        let component = FrameworkComponent::new(
+            &mut TraceLocationAllocator::default(),
+            TwoSumsEval {
+                log_size: LOG_SIZE,
+                first,
+                second,
+                second_sums: (second_total_sum, Some((second_claimed_sum, CLAIMED_ROW))),
+            },
+            first_sums,
+        );
+        let trace = TreeVec::new(vec![
+            vec![gen_is_first(LOG_SIZE)],
+            vec![CircleEvaluation::new(domain, a)],
+            [first_trace, second_trace].concat(),
+        ]);
+        let trace_polys = trace.map_cols(|c| c.interpolate());
+
+        assert_constraints(
+            &trace_polys,
+            CanonicCoset::new(LOG_SIZE),
+            |eval| {
+                component.evaluate(eval);
+            },
+            first_sums,
+        );
+        assert_ne!(first_total_sum, second_total_sum);
+        assert_eq!(
+            component.claimed_logup_sum(),
+            Some(first_total_sum + second_total_sum)
+        );
+        assert_eq!(component.preprocessed_columns().len(), 1);
+        let trace = Trace {
+            polys: trace_polys.as_cols_ref(),
+            evals: TreeVec::new(vec![vec![], vec![], vec![]]),
+        };
+        let component_provers = ComponentProvers {
+            components: vec![&component],
+            n_preprocessed_columns: 1,
+        };
+        assert_eq!(
+            component_provers.prove_lookups_only(&trace),
+            vec![component.claimed_logup_sum()]
+        );
+    }
 
     #[test]
     fn test_lookup_elements_combine() {
@@ -308,4 +525,236 @@ mod tests {
                 - lookup_elements.z
         );
     }
+
+    #[test]
+    fn test_validate_prefix_sum() {
+        const LOG_SIZE: u32 = 6;
+        const INDEX: usize = 37;
+        let mut rng = SmallRng::seed_from_u64(0);
+        let column = (0..1 << LOG_SIZE)
+            .map(|_| rng.gen::<SecureField>())
+            .collect::<SecureColumn>();
+        let mut logup_gen = LogupTraceGenerator::new(LOG_SIZE);
+        let mut col_gen = logup_gen.new_col();
+        for (vec_row, &value) in column.data.iter().enumerate() {
+            col_gen.write_frac(vec_row, value, PackedSecureField::one());
+        }
+        col_gen.finalize_col();
+        let (_, [prefix_sum]) = logup_gen.finalize_at([INDEX]);
+
+        assert!(validate_prefix_sum(&column, (prefix_sum, INDEX)));
+        assert!(!validate_prefix_sum(&column, (prefix_sum, INDEX + 1)));
+    }
+
+    #[test]
+    fn test_relation_combine_single() {
+        relation!(SingleValueRelation, 3);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut channel = Blake2sChannel::default();
+        for _ in 0..10 {
+            let relation = SingleValueRelation::draw(&mut channel);
+            let value: BaseField = rng.gen();
+
+            let expected = relation.0.alpha_powers[0] * value - relation.0.z;
+            assert_eq!(
+                Relation::<BaseField, SecureField>::combine_single(&relation, &value),
+                expected
+            );
+            assert_eq!(
+                Relation::<BaseField, SecureField>::combine(&relation, &[value]),
+                expected
+            );
+            assert_eq!(
+                relation.0.combine::<BaseField, SecureField>(&[value]),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_relation_with_tag() {
+        relation_with_tag!(OpcodeRelation, 2, 1);
+        relation!(UntaggedRelation, 3);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let channel = &mut Blake2sChannel::default();
+        let add = OpcodeRelation::draw(&mut channel.clone(), BaseField::from(1));
+        let mul = add.with_tag(BaseField::from(2));
+        let untagged = UntaggedRelation::draw(channel);
+        let [a, b] = rng.gen::<[BaseField; 2]>();
+        let combine = |relation: &OpcodeRelation| {
+            Relation::<BaseField, SecureField>::combine(relation, &[a, b])
+        };
+
+        assert_eq!(combine(&add), add.elements.combine(&[a, add.tag, b]));
+        assert_eq!(combine(&add), untagged.0.combine(&[a, add.tag, b]));
+        assert_ne!(combine(&add), combine(&mul));
+        // Entering values with a tag and yielding them with another doesn't cancel out.
+        let fraction = |relation: &OpcodeRelation, multiplicity: SecureField| {
+            Fraction::new(multiplicity, combine(relation))
+        };
+        let one = SecureField::one();
+        assert!((fraction(&add, one) + fraction(&add, -one))
+            .numerator
+            .is_zero());
+        assert!(!(fraction(&add, one) + fraction(&mul, -one))
+            .numerator
+            .is_zero());
+    }
+
+    /// Enters `(a, b)` into a tagged relation and yields `(a, tag, b)` from an untagged relation
+    /// with the same lookup elements.
+    struct TaggedLookupEval {
+        log_size: u32,
+        tagged: TaggedRelation,
+        shifted: ShiftedRelation,
+    }
+
+    impl FrameworkEval for TaggedLookupEval {
+        fn name(&self) -> String {
+            "TaggedLookupEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            self.log_size
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let [a, b] = eval.next_trace_masks();
+            let tag = E::F::from(self.tagged.tag);
+
+            eval.add_to_relation(&[RelationEntry::new(
+                &self.tagged,
+                E::EF::one(),
+                &[a.clone(), b.clone()],
+            )]);
+            eval.add_to_relation(&[RelationEntry::new(
+                &self.shifted,
+                -E::EF::one(),
+                &[a, tag, b],
+            )]);
+            eval.finalize_logup();
+            eval
+        }
+    }
+
+    #[test]
+    fn test_tagged_relation_balances_shifted_entry() {
+        const LOG_SIZE: u32 = 5;
+        let mut rng = SmallRng::seed_from_u64(0);
+        let tagged = TaggedRelation::draw(&mut Blake2sChannel::default(), BaseField::from(3));
+        let shifted = ShiftedRelation(tagged.elements.clone());
+        let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+        let [a, b] = std::array::from_fn(|_| {
+            (0..1 << LOG_SIZE)
+                .map(|_| rng.gen::<BaseField>())
+                .collect::<BaseColumn>()
+        });
+        let tag = PackedBaseField::broadcast(tagged.tag);
+        let gen_interaction_trace = |entered: &TaggedRelation| {
+            let mut logup_gen = LogupTraceGenerator::new(LOG_SIZE);
+            let mut tagged_col_gen = logup_gen.new_col();
+            for vec_row in 0..1 << (LOG_SIZE - LOG_N_LANES) {
+                let (a, b) = (a.data[vec_row], b.data[vec_row]);
+                tagged_col_gen.write_frac(
+                    vec_row,
+                    PackedSecureField::one(),
+                    entered.combine(&[a, b]),
+                );
+            }
+            tagged_col_gen.finalize_col();
+            let mut shifted_col_gen = logup_gen.new_col();
+            for vec_row in 0..1 << (LOG_SIZE - LOG_N_LANES) {
+                let (a, b) = (a.data[vec_row], b.data[vec_row]);
+                shifted_col_gen.write_frac(
+                    vec_row,
+                    -PackedSecureField::one(),
+                    shifted.combine(&[a, tag, b]),
+                );
+            }
+            shifted_col_gen.finalize_col();
+            logup_gen.finalize_last()
+        };
+
+        let (interaction_trace, total_sum) = gen_interaction_trace(&tagged);
+        let trace = TreeVec::new(vec![
+            vec![gen_is_first(LOG_SIZE)],
+            vec![
+                CircleEvaluation::new(domain, a.clone()),
+                CircleEvaluation::new(domain, b.clone()),
+            ],
+            interaction_trace,
+        ]);
+        let trace_polys = trace.map_cols(|c| c.interpolate());
+        let eval = TaggedLookupEval {
+            log_size: LOG_SIZE,
+            tagged: tagged.clone(),
+            shifted: shifted.clone(),
+        };
+
+        assert_eq!(total_sum, SecureField::zero());
+        assert_constraints(
+            &trace_polys,
+            CanonicCoset::new(LOG_SIZE),
+            |eval_at_row| {
+                eval.evaluate(eval_at_row);
+            },
+            (total_sum, None),
+        );
+        // Entering the values with another tag doesn't cancel the shifted entries.
+        let (_, other_tag_total_sum) = gen_interaction_trace(&tagged.with_tag(BaseField::from(4)));
+        assert_ne!(other_tag_total_sum, SecureField::zero());
+    }
+
+    #[test]
+    fn test_relation_entry_with_count() {
+        relation!(Foo, 1);
+        let relation = Foo::dummy();
+        let values = [BaseField::from(7)];
+        let multiplicity = |count| {
+            RelationEntry::<BaseField, SecureField, Foo>::with_count(&relation, count, &values)
+                .multiplicity
+        };
+
+        assert_eq!(multiplicity(3), SecureField::from(3));
+        assert_eq!(multiplicity(-3), -SecureField::from(3));
+        assert_eq!(multiplicity(0), SecureField::zero());
+        assert_eq!(multiplicity(P as i64 + 2), SecureField::from(2));
+        // 2^63 = 2^(2 * 31 + 1) = 2 (mod P).
+        assert_eq!(multiplicity(i64::MIN), -SecureField::from(2));
+    }
+
+    #[test]
+    fn test_relation_size() {
+        relation!(Foo, 5);
+
+        assert_eq!(
+            Relation::<BaseField, SecureField>::get_size(&Foo::dummy()),
+            5
+        );
+        assert_eq!(Foo::dummy().0.alpha_powers.len(), 5);
+    }
+
+    #[test]
+    fn test_relation_combine_degree() {
+        relation!(LinearRelation, 2);
+        let values = [BaseField::from(3), BaseField::from(5)];
+        let product_relation = ProductRelation::<1>::dummy();
+
+        assert_eq!(
+            Relation::<BaseField, SecureField>::combine_degree(&LinearRelation::dummy()),
+            1
+        );
+        assert_eq!(
+            Relation::<BaseField, SecureField>::combine_degree(&product_relation),
+            2
+        );
+        assert_eq!(
+            Relation::<BaseField, SecureField>::combine(&product_relation, &values),
+            SecureField::from(BaseField::from(15)) - SecureField::from(1)
+        );
+    }
 }
@@ -14,15 +14,22 @@ use std::array;
 use std::fmt::Debug;
 use std::ops::{Add, AddAssign, Mul, Neg, Sub};
 
-pub use assert::{assert_constraints, AssertEvaluator};
-pub use component::{FrameworkComponent, FrameworkEval, TraceLocationAllocator};
+pub use assert::{
+    assert_constraints, assert_constraints_multi, try_assert_constraints, AssertComponent,
+    AssertEvaluator, ConstraintViolation,
+};
+pub use component::{
+    random_trace, FrameworkComponent, FrameworkEval, SharedLogupEval, TraceLocationAllocator,
+};
 pub use info::InfoEvaluator;
+use logup::LogupSums;
 use num_traits::{One, Zero};
 pub use point::PointEvaluator;
 use preprocessed_columns::PreprocessedColumn;
 pub use simd_domain::SimdDomainEvaluator;
+use thiserror::Error;
 
-use crate::core::fields::m31::BaseField;
+use crate::core::fields::m31::{BaseField, P};
 use crate::core::fields::qm31::SecureField;
 use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
 use crate::core::fields::FieldExpOps;
@@ -32,6 +39,61 @@ pub const PREPROCESSED_TRACE_IDX: usize = 0;
 pub const ORIGINAL_TRACE_IDX: usize = 1;
 pub const INTERACTION_TRACE_IDX: usize = 2;
 
+/// A mask requested outside the trace, see [`EvalAtRow::try_next_interaction_mask()`].
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum MaskError {
+    #[error(
+        "Interaction {interaction} is out of range, the trace has {n_interactions} interactions"
+    )]
+    InteractionOutOfRange {
+        interaction: usize,
+        n_interactions: usize,
+    },
+    #[error(
+        "Column {column} of interaction {interaction} is out of range, the interaction has \
+        {n_columns} columns"
+    )]
+    ColumnOutOfRange {
+        interaction: usize,
+        column: usize,
+        n_columns: usize,
+    },
+    #[error("Offset {offset} is out of range for a trace of log size {log_size}")]
+    OffsetOutOfRange { offset: isize, log_size: u32 },
+}
+
+/// Implements [`EvalAtRow::validate_next_interaction_mask()`] for an evaluator reading the mask of
+/// column `col_index[interaction]` from `trace`, of size `2^log_size`.
+fn validate_mask<T>(
+    trace: &[Vec<T>],
+    col_index: &[usize],
+    interaction: usize,
+    offsets: &[isize],
+    log_size: u32,
+) -> Result<(), MaskError> {
+    let columns = trace
+        .get(interaction)
+        .ok_or(MaskError::InteractionOutOfRange {
+            interaction,
+            n_interactions: trace.len(),
+        })?;
+    let column = col_index[interaction];
+    if column >= columns.len() {
+        return Err(MaskError::ColumnOutOfRange {
+            interaction,
+            column,
+            n_columns: columns.len(),
+        });
+    }
+    if let Some(&offset) = offsets
+        .iter()
+        .find(|offset| offset.unsigned_abs() >> log_size != 0)
+    {
+        return Err(MaskError::OffsetOutOfRange { offset, log_size });
+    }
+    Ok(())
+}
+
 /// A trait for evaluating expressions at some point or row.
 pub trait EvalAtRow {
     // TODO(Ohad): Use a better trait for these, like 'Algebra' or something.
@@ -80,18 +142,69 @@ pub trait EvalAtRow {
         mask_item
     }
 
+    /// Returns the mask values of the next `N` columns of the first interaction at offset 0, in the
+    /// order of `N` calls to [`Self::next_trace_mask()`].
+    fn next_trace_masks<const N: usize>(&mut self) -> [Self::F; N] {
+        array::from_fn(|_| self.next_trace_mask())
+    }
+
+    /// Returns the mask values of the next column of the first interaction at offsets 0 and 1, i.e.
+    /// its values at the current and at the next row.
+    fn next_trace_mask_and_next(&mut self) -> (Self::F, Self::F) {
+        let [mask_item, next_mask_item] = self.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        (mask_item, next_mask_item)
+    }
+
+    /// Returns the mask values of the next `N` columns of the interaction at offset 0.
+    fn next_interaction_masks<const N: usize>(&mut self, interaction: usize) -> [Self::F; N] {
+        array::from_fn(|_| {
+            let [mask_item] = self.next_interaction_mask(interaction, [0]);
+            mask_item
+        })
+    }
+
     fn get_preprocessed_column(&mut self, _column: PreprocessedColumn) -> Self::F {
         let [mask_item] = self.next_interaction_mask(PREPROCESSED_TRACE_IDX, [0]);
         mask_item
     }
 
     /// Returns the mask values of the given offsets for the next column in the interaction.
+    ///
+    /// Offsets are in rows of the trace and wrap around it, i.e. the value at offset `offset` of
+    /// row `row` is the value at row `(row + offset) mod 2^log_size`. In particular, offset
+    /// `-1` of the first row is the last row.
+    ///
+    /// In debug builds, evaluators that know the shape of the trace panic on masks outside of it,
+    /// see [`Self::validate_next_interaction_mask()`].
     fn next_interaction_mask<const N: usize>(
         &mut self,
         interaction: usize,
         offsets: [isize; N],
     ) -> [Self::F; N];
 
+    /// Checks that the interaction has a next column, and that the offsets are smaller than the
+    /// trace size in absolute value.
+    ///
+    /// Defaults to accepting any mask, for evaluators that don't know the shape of the trace.
+    fn validate_next_interaction_mask(
+        &self,
+        _interaction: usize,
+        _offsets: &[isize],
+    ) -> Result<(), MaskError> {
+        Ok(())
+    }
+
+    /// Like [`Self::next_interaction_mask()`], but returns an error for a mask outside the trace,
+    /// see [`Self::validate_next_interaction_mask()`].
+    fn try_next_interaction_mask<const N: usize>(
+        &mut self,
+        interaction: usize,
+        offsets: [isize; N],
+    ) -> Result<[Self::F; N], MaskError> {
+        self.validate_next_interaction_mask(interaction, &offsets)?;
+        Ok(self.next_interaction_mask(interaction, offsets))
+    }
+
     /// Returns the extension mask values of the given offsets for the next extension degree many
     /// columns in the interaction.
     fn next_extension_interaction_mask<const N: usize>(
@@ -107,10 +220,23 @@ pub trait EvalAtRow {
     }
 
     /// Adds a constraint to the component.
+    ///
+    /// The prover and verifier evaluators fold the constraint into their random linear combination
+    /// right away rather than buffering it, so their memory doesn't grow with the number of
+    /// constraints.
     fn add_constraint<G>(&mut self, constraint: G)
     where
         Self::EF: Mul<G, Output = Self::EF> + From<G>;
 
+    /// Adds the constraint `selector * (lhs - rhs)`, which enforces `lhs = rhs` on the rows where
+    /// the boolean `selector` is one.
+    ///
+    /// The selector raises the degree of the constraint by its own degree, which
+    /// [`expr::max_constraint_degree()`] accounts for.
+    fn add_constraint_if(&mut self, selector: Self::F, lhs: Self::F, rhs: Self::F) {
+        self.add_constraint(selector * (lhs - rhs));
+    }
+
     /// Adds an intermediate value in the base field to the component and returns its value.
     /// Does nothing by default.
     fn add_intermediate(&mut self, val: Self::F) -> Self::F {
@@ -144,6 +270,15 @@ pub trait EvalAtRow {
         self.write_logup_frac(fracs.sum());
     }
 
+    /// Adds `value` to the range check `relation` with multiplicity one.
+    ///
+    /// The range itself is expected to be entered into `relation` by a table component, with a
+    /// companion multiplicity column holding the negated number of times each value of the range
+    /// was checked.
+    fn add_range_check<R: Relation<Self::F, Self::EF>>(&mut self, relation: &R, value: Self::F) {
+        self.add_to_relation(&[RelationEntry::new(relation, Self::EF::one(), &[value])]);
+    }
+
     // TODO(alont): Remove these once LogupAtRow is no longer used.
     fn write_logup_frac(&mut self, _fraction: Fraction<Self::EF, Self::EF>) {
         unimplemented!()
@@ -151,6 +286,23 @@ pub trait EvalAtRow {
     fn finalize_logup(&mut self) {
         unimplemented!()
     }
+    /// Starts a new logup sub-sum, for components with several independent logup arguments.
+    ///
+    /// The fractions written after this call, up to the next [`Self::finalize_logup()`], are
+    /// summed into their own cumulative sum column with the given sums, placed after the columns
+    /// of the previous sub-sum. Single-sum components never call it; their sums are the ones the
+    /// evaluator was created with.
+    ///
+    /// Fractions are routed to sub-sums by evaluation order rather than by a relation name or
+    /// index: a sub-sum takes every fraction written between its start and its finalization, so
+    /// the trace generator must lay out its columns in the same order.
+    ///
+    /// # Panics
+    ///
+    /// If the previous sub-sum was not finalized.
+    fn start_logup_sub_sum(&mut self, _sums: LogupSums) {
+        unimplemented!()
+    }
 }
 
 /// Default implementation for evaluators that have an element called "logup" that works like a
@@ -163,18 +315,38 @@ macro_rules! logup_proxy {
             if let Some(cur_frac) = self.logup.cur_frac.clone() {
                 let [cur_cumsum] =
                     self.next_extension_interaction_mask(self.logup.interaction, [0]);
+                self.logup.n_columns += 1;
                 let diff = cur_cumsum.clone() - self.logup.prev_col_cumsum.clone();
                 self.logup.prev_col_cumsum = cur_cumsum;
                 self.add_constraint(diff * cur_frac.denominator - cur_frac.numerator);
             } else {
-                self.logup.is_first = self.get_preprocessed_column(
-                    super::preprocessed_columns::PreprocessedColumn::IsFirst(self.logup.log_size),
-                );
+                // The sub-sums share the `is_first` column of the first sub-sum.
+                if self.logup.sub_sums.is_empty() {
+                    self.logup.is_first = self.get_preprocessed_column(
+                        super::preprocessed_columns::PreprocessedColumn::IsFirst(
+                            self.logup.log_size,
+                        ),
+                    );
+                }
                 self.logup.is_finalized = false;
             }
             self.logup.cur_frac = Some(fraction);
         }
 
+        fn start_logup_sub_sum(&mut self, sums: super::logup::LogupSums) {
+            assert!(
+                self.logup.is_finalized && self.logup.cur_frac.is_some(),
+                "The previous logup sub-sum was not finalized"
+            );
+            self.logup.total_sum = sums.0.into();
+            self.logup.claimed_sum = sums
+                .1
+                .map(|(claimed_sum, claimed_row_index)| (claimed_sum.into(), claimed_row_index));
+            self.logup.prev_col_cumsum = num_traits::Zero::zero();
+            self.logup.cur_frac = None;
+            self.logup.sub_sums.push(sums);
+        }
+
         fn finalize_logup(&mut self) {
             assert!(!self.logup.is_finalized, "LogupAtRow was already finalized");
 
@@ -202,6 +374,9 @@ macro_rules! logup_proxy {
                     (cur_cumsum, prev_row_cumsum)
                 }
             };
+            self.logup.cumsum_columns.push(self.logup.n_columns);
+            self.logup.n_columns += 1;
+
             // Fix `prev_row_cumsum` by subtracting `total_sum` if this is the first row.
             let fixed_prev_row_cumsum =
                 prev_row_cumsum - self.logup.is_first.clone() * self.logup.total_sum.clone();
@@ -231,8 +406,23 @@ where
 pub trait Relation<F: Clone, EF: RelationEFTraitBound<F>>: Sized {
     fn combine(&self, values: &[F]) -> EF;
 
+    /// Combines a single value, e.g. of a range check. Equivalent to [`Self::combine`] on
+    /// `[value]`.
+    fn combine_single(&self, value: &F) -> EF {
+        self.combine(std::slice::from_ref(value))
+    }
+
     fn get_name(&self) -> &str;
+    /// Returns the number of values the relation combines, e.g. the `N` of the
+    /// [`logup::LookupElements<N>`] of a relation defined with [`relation!`].
     fn get_size(&self) -> usize;
+
+    /// Returns the algebraic degree of [`Self::combine`] in the trace values.
+    /// Linear combinations of the values, like [`logup::LookupElements::combine`], have degree 1.
+    /// Used to bound the degree of the batched logup denominators.
+    fn combine_degree(&self) -> usize {
+        1
+    }
 }
 
 /// A struct representing a relation entry.
@@ -254,6 +444,17 @@ impl<'a, F: Clone, EF: RelationEFTraitBound<F>, R: Relation<F, EF>> RelationEntr
             values,
         }
     }
+
+    /// Creates an entry whose multiplicity is the signed integer `count`, e.g. the number of times
+    /// the values are used (positive) or yielded (negative).
+    pub fn with_count(relation: &'a R, count: i64, values: &'a [F]) -> Self {
+        let abs_count = BaseField::from_u32_unchecked((count.unsigned_abs() % P as u64) as u32);
+        let multiplicity = match count.is_negative() {
+            true => -abs_count,
+            false => abs_count,
+        };
+        Self::new(relation, SecureField::from(multiplicity).into(), values)
+    }
 }
 
 #[macro_export]
@@ -278,6 +479,9 @@ macro_rules! relation {
             $crate::constraint_framework::Relation<F, EF> for $name
         {
             fn combine(&self, values: &[F]) -> EF {
+                if let [value] = values {
+                    return self.combine_single(value);
+                }
                 values
                     .iter()
                     .zip(self.0.alpha_powers)
@@ -287,6 +491,10 @@ macro_rules! relation {
                     - self.0.z.into()
             }
 
+            fn combine_single(&self, value: &F) -> EF {
+                EF::from(self.0.alpha_powers[0]) * value.clone() - self.0.z.into()
+            }
+
             fn get_name(&self) -> &str {
                 stringify!($name)
             }
@@ -298,3 +506,273 @@ macro_rules! relation {
     };
 }
 pub(crate) use relation;
+
+/// Defines a relation like [`relation!`], combining `$size` values together with a constant tag,
+/// e.g. an opcode. The tag is combined as if it were inserted into the values at index
+/// `$tag_position`, and the values are combined with the remaining alpha powers.
+///
+/// Relations differing only in their tag share lookup elements, see `with_tag()`, so their entries
+/// are distinct while being entered into the same logup argument.
+#[macro_export]
+macro_rules! relation_with_tag {
+    ($name:tt, $size:tt, $tag_position:tt) => {
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $name {
+            pub elements: $crate::constraint_framework::logup::LookupElements<{ $size + 1 }>,
+            pub tag: $crate::core::fields::m31::BaseField,
+        }
+
+        const _: () = assert!($tag_position <= $size, "Tag position out of range");
+
+        #[allow(dead_code)]
+        impl $name {
+            pub fn dummy(tag: $crate::core::fields::m31::BaseField) -> Self {
+                Self {
+                    elements: $crate::constraint_framework::logup::LookupElements::dummy(),
+                    tag,
+                }
+            }
+            pub fn draw(
+                channel: &mut impl $crate::core::channel::Channel,
+                tag: $crate::core::fields::m31::BaseField,
+            ) -> Self {
+                Self {
+                    elements: $crate::constraint_framework::logup::LookupElements::draw(channel),
+                    tag,
+                }
+            }
+            /// Returns the relation with the same lookup elements and a different tag.
+            pub fn with_tag(&self, tag: $crate::core::fields::m31::BaseField) -> Self {
+                Self {
+                    elements: self.elements.clone(),
+                    tag,
+                }
+            }
+        }
+
+        impl<F: Clone, EF: $crate::constraint_framework::RelationEFTraitBound<F>>
+            $crate::constraint_framework::Relation<F, EF> for $name
+        {
+            fn combine(&self, values: &[F]) -> EF {
+                assert!(values.len() <= $size, "Too many values to combine");
+                let powers = &self.elements.alpha_powers;
+                let value_powers = powers[..$tag_position]
+                    .iter()
+                    .chain(&powers[$tag_position + 1..]);
+                let tag_term = EF::from(powers[$tag_position] * self.tag);
+                values
+                    .iter()
+                    .zip(value_powers)
+                    .fold(tag_term, |acc, (value, &power)| {
+                        acc + EF::from(power) * value.clone()
+                    })
+                    - self.elements.z.into()
+            }
+
+            fn get_name(&self) -> &str {
+                stringify!($name)
+            }
+
+            fn get_size(&self) -> usize {
+                $size
+            }
+        }
+    };
+}
+// Only used by tests for now, unlike `relation!`.
+#[allow(unused_imports)]
+pub(crate) use relation_with_tag;
+
+/// Defines a unit struct `$name` implementing [`FrameworkEval`] with the given log size and
+/// constraint degree bound. Its `evaluate()` runs `$body` on the evaluator, bound to `$eval`, whose
+/// type is the generic `E: EvalAtRow`.
+///
+/// ```ignore
+/// framework_eval!(ConstantEval, 4, 5, |eval| {
+///     let (curr, next) = eval.next_trace_mask_and_next();
+///     eval.add_constraint(curr - next);
+/// });
+/// ```
+#[macro_export]
+macro_rules! framework_eval {
+    ($name:ident, $log_size:expr, $max_constraint_log_degree_bound:expr, |$eval:ident| $body:block) => {
+        pub struct $name;
+
+        impl $crate::constraint_framework::FrameworkEval for $name {
+            fn name(&self) -> String {
+                stringify!($name).to_string()
+            }
+
+            fn log_size(&self) -> u32 {
+                $log_size
+            }
+
+            fn max_constraint_log_degree_bound(&self) -> u32 {
+                $max_constraint_log_degree_bound
+            }
+
+            fn evaluate<E: $crate::constraint_framework::EvalAtRow>(&self, mut $eval: E) -> E {
+                $body
+                $eval
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::Zero;
+
+    use super::cpu_domain::CpuDomainEvaluator;
+    use super::preprocessed_columns::gen_seq;
+    use super::{
+        assert_constraints, AssertEvaluator, EvalAtRow, FrameworkComponent, FrameworkEval,
+        SimdDomainEvaluator, TraceLocationAllocator, ORIGINAL_TRACE_IDX,
+    };
+    use crate::core::air::Component;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::CpuBackend;
+    use crate::core::circle::CirclePoint;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+    use crate::core::poly::BitReversedOrder;
+    use crate::framework_eval;
+
+    const LOG_SIZE: u32 = 5;
+
+    /// Reads a single column at the current and at the previous row.
+    struct PreviousRowEval;
+
+    impl FrameworkEval for PreviousRowEval {
+        fn name(&self) -> String {
+            "PreviousRowEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            LOG_SIZE
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            LOG_SIZE + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let [curr, prev] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, -1]);
+            eval.add_constraint(curr - prev);
+            eval
+        }
+    }
+
+    /// Returns the value of the `[0, -1]` mask of [PreviousRowEval] at `point`.
+    fn previous_row_at_point(point: CirclePoint<SecureField>) -> SecureField {
+        let component = FrameworkComponent::new(
+            &mut TraceLocationAllocator::default(),
+            PreviousRowEval,
+            (SecureField::zero(), None),
+        );
+        let poly = gen_seq::<CpuBackend>(LOG_SIZE).interpolate();
+        let [curr, prev] = component.mask_points(point)[ORIGINAL_TRACE_IDX][0][..] else {
+            panic!("Expected a mask of two points");
+        };
+        assert_eq!(curr, point);
+        poly.eval_at_point(prev)
+    }
+
+    #[test]
+    fn test_negative_offset_wraps_around() {
+        let last_row = BaseField::from((1 << LOG_SIZE) - 1);
+        let trace = TreeVec::new(vec![
+            vec![],
+            vec![(0..1 << LOG_SIZE).map(BaseField::from).collect()],
+        ]);
+        let mut assert_eval =
+            AssertEvaluator::new(&trace, 0, LOG_SIZE, (SecureField::zero(), None));
+        let first_row_point = CanonicCoset::new(LOG_SIZE).at(0).into_ef();
+
+        let [_, assert_prev] = assert_eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, -1]);
+
+        assert_eq!(assert_prev, last_row);
+        assert_eq!(previous_row_at_point(first_row_point), last_row.into());
+    }
+
+    #[test]
+    fn test_negative_offset_wraps_around_on_extended_domain() {
+        let eval_domain = CanonicCoset::new(LOG_SIZE + 1).circle_domain();
+        let cpu_eval = gen_seq::<CpuBackend>(LOG_SIZE)
+            .interpolate()
+            .evaluate(eval_domain);
+        let simd_eval = gen_seq::<SimdBackend>(LOG_SIZE)
+            .interpolate()
+            .evaluate(eval_domain);
+        let cpu_trace = TreeVec::new(vec![vec![], vec![&cpu_eval]]);
+        let simd_trace = TreeVec::new(vec![vec![], vec![&simd_eval]]);
+        let mut cpu_domain_eval = CpuDomainEvaluator::new(
+            &cpu_trace,
+            0,
+            &[],
+            LOG_SIZE,
+            LOG_SIZE + 1,
+            LOG_SIZE,
+            (SecureField::zero(), None),
+        );
+        let mut simd_domain_eval = SimdDomainEvaluator::new(
+            &simd_trace,
+            0,
+            &[],
+            LOG_SIZE,
+            LOG_SIZE + 1,
+            LOG_SIZE,
+            (SecureField::zero(), None),
+        );
+        // The first row of a bit reversed evaluation is the first point of its domain.
+        let expected = previous_row_at_point(eval_domain.at(0).into_ef());
+
+        let [_, cpu_prev] = cpu_domain_eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, -1]);
+        let [_, simd_prev] = simd_domain_eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, -1]);
+
+        assert_eq!(SecureField::from(cpu_prev), expected);
+        assert_eq!(SecureField::from(simd_prev.to_array()[0]), expected);
+    }
+
+    framework_eval!(EqualsNextEval, LOG_SIZE, LOG_SIZE + 1, |eval| {
+        let (curr, next) = eval.next_trace_mask_and_next();
+        eval.add_constraint(curr - next);
+    });
+
+    fn assert_equals_next_constraints(
+        trace: CircleEvaluation<CpuBackend, BaseField, BitReversedOrder>,
+    ) {
+        let trace_polys = TreeVec::new(vec![vec![], vec![trace.interpolate()]]);
+
+        assert_constraints(
+            &trace_polys,
+            CanonicCoset::new(EqualsNextEval.log_size()),
+            |eval| {
+                EqualsNextEval.evaluate(eval);
+            },
+            (SecureField::zero(), None),
+        );
+    }
+
+    #[test]
+    fn test_framework_eval_macro() {
+        let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+
+        assert_eq!(
+            EqualsNextEval.max_constraint_log_degree_bound(),
+            LOG_SIZE + 1
+        );
+        assert_equals_next_constraints(CircleEvaluation::new(
+            domain,
+            vec![BaseField::from(3); 1 << LOG_SIZE],
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "32 constraint violations")]
+    fn test_framework_eval_macro_violated() {
+        assert_equals_next_constraints(gen_seq(LOG_SIZE));
+    }
+}
@@ -1,7 +1,7 @@
 use std::ops::Mul;
 
 use super::logup::{LogupAtRow, LogupSums};
-use super::{EvalAtRow, INTERACTION_TRACE_IDX};
+use super::{validate_mask, EvalAtRow, MaskError, INTERACTION_TRACE_IDX};
 use crate::core::air::accumulation::PointEvaluationAccumulator;
 use crate::core::fields::qm31::SecureField;
 use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
@@ -39,11 +39,30 @@ impl<'a> EvalAtRow for PointEvaluator<'a> {
     type F = SecureField;
     type EF = SecureField;
 
+    fn validate_next_interaction_mask(
+        &self,
+        interaction: usize,
+        offsets: &[isize],
+    ) -> Result<(), MaskError> {
+        validate_mask(
+            &self.mask,
+            &self.col_index,
+            interaction,
+            offsets,
+            self.logup.log_size,
+        )
+    }
+
     fn next_interaction_mask<const N: usize>(
         &mut self,
         interaction: usize,
-        _offsets: [isize; N],
+        offsets: [isize; N],
     ) -> [Self::F; N] {
+        if cfg!(debug_assertions) {
+            if let Err(err) = self.validate_next_interaction_mask(interaction, &offsets) {
+                panic!("{err}");
+            }
+        }
         let col_index = self.col_index[interaction];
         self.col_index[interaction] += 1;
         let mask = self.mask[interaction][col_index].clone();
@@ -63,3 +82,164 @@ impl<'a> EvalAtRow for PointEvaluator<'a> {
 
     super::logup_proxy!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Mul;
+
+    use num_traits::{One, Zero};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::PointEvaluator;
+    use crate::constraint_framework::{EvalAtRow, FrameworkEval, MaskError};
+    use crate::core::air::accumulation::PointEvaluationAccumulator;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+    use crate::core::pcs::TreeVec;
+
+    const N_CONSTRAINTS: usize = 1000;
+
+    /// Has [N_CONSTRAINTS] constraints on two columns.
+    struct ManyConstraintsEval;
+
+    impl FrameworkEval for ManyConstraintsEval {
+        fn name(&self) -> String {
+            "ManyConstraintsEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            4
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size() + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let [a, b] = eval.next_trace_masks();
+            for i in 0..N_CONSTRAINTS {
+                eval.add_constraint(a.clone() * b.clone() - E::F::from(BaseField::from(i)));
+            }
+            eval
+        }
+    }
+
+    /// Buffers the values of the constraints at a point.
+    struct BufferingEvaluator {
+        mask: Vec<SecureField>,
+        constraints: Vec<SecureField>,
+    }
+
+    impl EvalAtRow for BufferingEvaluator {
+        type F = SecureField;
+        type EF = SecureField;
+
+        fn next_interaction_mask<const N: usize>(
+            &mut self,
+            _interaction: usize,
+            _offsets: [isize; N],
+        ) -> [Self::F; N] {
+            [self.mask.remove(0); N]
+        }
+
+        fn add_constraint<G>(&mut self, constraint: G)
+        where
+            Self::EF: Mul<G, Output = Self::EF>,
+        {
+            // `G` can only be multiplied into an extension field value.
+            self.constraints.push(SecureField::one() * constraint);
+        }
+
+        fn combine_ef(values: [Self::F; SECURE_EXTENSION_DEGREE]) -> Self::EF {
+            SecureField::from_partial_evals(values)
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_buffered_constraints() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mask = vec![vec![rng.gen::<SecureField>()], vec![rng.gen()]];
+        let random_coeff = rng.gen();
+        let mut accumulator = PointEvaluationAccumulator::new(random_coeff);
+
+        ManyConstraintsEval.evaluate(PointEvaluator::new(
+            TreeVec::new(vec![vec![], mask.iter().collect()]),
+            &mut accumulator,
+            SecureField::from(1),
+            4,
+            (SecureField::zero(), None),
+        ));
+        let buffered = ManyConstraintsEval.evaluate(BufferingEvaluator {
+            mask: mask.concat(),
+            constraints: vec![],
+        });
+
+        assert_eq!(buffered.constraints.len(), N_CONSTRAINTS);
+        let combined = buffered
+            .constraints
+            .iter()
+            .fold(SecureField::zero(), |acc, &constraint| {
+                acc * random_coeff + constraint
+            });
+        assert_eq!(accumulator.finalize(), combined);
+    }
+
+    fn point_evaluator<'a>(
+        mask: &'a [Vec<SecureField>],
+        accumulator: &'a mut PointEvaluationAccumulator,
+    ) -> PointEvaluator<'a> {
+        PointEvaluator::new(
+            TreeVec::new(vec![vec![], mask.iter().collect()]),
+            accumulator,
+            SecureField::one(),
+            4,
+            (SecureField::zero(), None),
+        )
+    }
+
+    #[test]
+    fn test_try_next_interaction_mask() {
+        let mask = [vec![SecureField::one()]];
+        let mut accumulator = PointEvaluationAccumulator::new(SecureField::one());
+        let mut eval = point_evaluator(&mask, &mut accumulator);
+
+        assert_eq!(
+            eval.try_next_interaction_mask(2, [0]),
+            Err(MaskError::InteractionOutOfRange {
+                interaction: 2,
+                n_interactions: 2
+            })
+        );
+        assert_eq!(
+            eval.try_next_interaction_mask(1, [16]),
+            Err(MaskError::OffsetOutOfRange {
+                offset: 16,
+                log_size: 4
+            })
+        );
+        assert_eq!(
+            eval.try_next_interaction_mask(1, [0]),
+            Ok([SecureField::one()])
+        );
+        assert_eq!(
+            eval.try_next_interaction_mask(1, [0]),
+            Err(MaskError::ColumnOutOfRange {
+                interaction: 1,
+                column: 1,
+                n_columns: 1
+            })
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "Interaction 2 is out of range, the trace has 2 interactions")]
+    fn test_next_interaction_mask_out_of_range() {
+        let mask = [vec![SecureField::one()]];
+        let mut accumulator = PointEvaluationAccumulator::new(SecureField::one());
+
+        point_evaluator(&mask, &mut accumulator).next_interaction_mask(2, [0]);
+    }
+}
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+use std::iter::zip;
+
 use num_traits::One;
 
+use crate::core::air::Component;
 use crate::core::backend::{Backend, Col, Column};
 use crate::core::fields::m31::BaseField;
 use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
@@ -11,6 +15,15 @@ use crate::core::utils::{bit_reverse_index, coset_index_to_circle_domain_index};
 pub enum PreprocessedColumn {
     XorTable(u32, u32, usize),
     IsFirst(u32),
+    IsLast(u32),
+    /// The row indices `0, 1, ..., 2^log_size - 1`.
+    Seq(u32),
+    /// `pattern` repeated over the rows, e.g. round constants that repeat every few rows.
+    /// `pattern.len()` must divide the column size.
+    Periodic {
+        log_size: u32,
+        pattern: &'static [BaseField],
+    },
     Plonk(usize),
 }
 
@@ -21,6 +34,17 @@ pub fn gen_is_first<B: Backend>(log_size: u32) -> CircleEvaluation<B, BaseField,
     CircleEvaluation::new(CanonicCoset::new(log_size).circle_domain(), col)
 }
 
+/// Generates a column with a single one at the last row, and zeros elsewhere.
+pub fn gen_is_last<B: Backend>(log_size: u32) -> CircleEvaluation<B, BaseField, BitReversedOrder> {
+    let mut col = Col::<B, BaseField>::zeros(1 << log_size);
+    let circle_domain_index = coset_index_to_circle_domain_index((1 << log_size) - 1, log_size);
+    col.set(
+        bit_reverse_index(circle_domain_index, log_size),
+        BaseField::one(),
+    );
+    CircleEvaluation::new(CanonicCoset::new(log_size).circle_domain(), col)
+}
+
 /// Generates a column with `1` at every `2^log_step` positions, `0` elsewhere, shifted by offset.
 // TODO(andrew): Consider optimizing. Is a quotients of two coset_vanishing (use succinct rep for
 // verifier).
@@ -44,11 +68,49 @@ pub fn gen_is_step_with_offset<B: Backend>(
     CircleEvaluation::new(CanonicCoset::new(log_size).circle_domain(), col)
 }
 
+/// Generates a column whose value at each row is the row index.
+pub fn gen_seq<B: Backend>(log_size: u32) -> CircleEvaluation<B, BaseField, BitReversedOrder> {
+    let mut col = Col::<B, BaseField>::zeros(1 << log_size);
+    for i in 0..1 << log_size {
+        let circle_domain_index = coset_index_to_circle_domain_index(i, log_size);
+        let circle_domain_index_bit_rev = bit_reverse_index(circle_domain_index, log_size);
+        col.set(circle_domain_index_bit_rev, BaseField::from(i));
+    }
+    CircleEvaluation::new(CanonicCoset::new(log_size).circle_domain(), col)
+}
+
+/// Generates a column whose value at row `i` is `pattern[i % pattern.len()]`.
+///
+/// # Panics
+///
+/// Panics if `pattern.len()` does not divide `2^log_size`.
+pub fn gen_periodic<B: Backend>(
+    log_size: u32,
+    pattern: &[BaseField],
+) -> CircleEvaluation<B, BaseField, BitReversedOrder> {
+    let size = 1 << log_size;
+    assert!(
+        !pattern.is_empty() && size % pattern.len() == 0,
+        "Pattern length {} does not divide the column size {size}",
+        pattern.len()
+    );
+    let mut col = Col::<B, BaseField>::zeros(size);
+    for i in 0..size {
+        let circle_domain_index = coset_index_to_circle_domain_index(i, log_size);
+        let circle_domain_index_bit_rev = bit_reverse_index(circle_domain_index, log_size);
+        col.set(circle_domain_index_bit_rev, pattern[i % pattern.len()]);
+    }
+    CircleEvaluation::new(CanonicCoset::new(log_size).circle_domain(), col)
+}
+
 pub fn gen_preprocessed_column<B: Backend>(
     preprocessed_column: &PreprocessedColumn,
 ) -> CircleEvaluation<B, BaseField, BitReversedOrder> {
     match preprocessed_column {
         PreprocessedColumn::IsFirst(log_size) => gen_is_first(*log_size),
+        PreprocessedColumn::IsLast(log_size) => gen_is_last(*log_size),
+        PreprocessedColumn::Seq(log_size) => gen_seq(*log_size),
+        PreprocessedColumn::Periodic { log_size, pattern } => gen_periodic(*log_size, pattern),
         PreprocessedColumn::Plonk(_) | PreprocessedColumn::XorTable(..) => {
             unimplemented!("eval_preprocessed_column: Plonk and XorTable are not supported.")
         }
@@ -60,3 +122,194 @@ pub fn gen_preprocessed_columns<'a, B: Backend>(
 ) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
     columns.map(gen_preprocessed_column).collect()
 }
+
+/// Returns the distinct preprocessed columns requested by `components`, ordered by the index
+/// allocated to them in the preprocessed trace tree.
+///
+/// # Panics
+///
+/// If two components were allocated the same index for different columns, if a component doesn't
+/// report its preprocessed columns, or if some index is requested by none of the components.
+pub fn gather_preprocessed_columns(components: &[&dyn Component]) -> Vec<PreprocessedColumn> {
+    let mut columns = BTreeMap::new();
+    for component in components {
+        let indices = component.preproccessed_column_indices();
+        let component_columns = component.preprocessed_columns();
+        assert_eq!(
+            indices.len(),
+            component_columns.len(),
+            "Component {} does not report its preprocessed columns",
+            component.name()
+        );
+        for (index, column) in zip(indices, component_columns) {
+            let allocated_column = *columns.entry(index).or_insert(column);
+            assert_eq!(
+                allocated_column, column,
+                "Preprocessed column {index} is allocated to different columns"
+            );
+        }
+    }
+    assert!(
+        columns.keys().copied().eq(0..columns.len()),
+        "Preprocessed column indices are not contiguous"
+    );
+    columns.into_values().collect()
+}
+
+/// Generates the preprocessed trace tree of `components`, each distinct column generated once,
+/// see [gather_preprocessed_columns].
+pub fn gen_gathered_preprocessed_columns<B: Backend>(
+    components: &[&dyn Component],
+) -> Vec<CircleEvaluation<B, BaseField, BitReversedOrder>> {
+    gen_preprocessed_columns(gather_preprocessed_columns(components).iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::zip;
+
+    use itertools::Itertools;
+    use num_traits::{One, Zero};
+
+    use super::{
+        gather_preprocessed_columns, gen_gathered_preprocessed_columns, gen_preprocessed_column,
+        PreprocessedColumn,
+    };
+    use crate::constraint_framework::{
+        EvalAtRow, FrameworkComponent, FrameworkEval, InfoEvaluator, TraceLocationAllocator,
+    };
+    use crate::core::air::Component;
+    use crate::core::backend::{Column, CpuBackend};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::utils::{bit_reverse_index, coset_index_to_circle_domain_index};
+    use crate::m31;
+
+    #[test]
+    fn test_gen_seq() {
+        const LOG_SIZE: u32 = 5;
+
+        let seq = gen_preprocessed_column::<CpuBackend>(&PreprocessedColumn::Seq(LOG_SIZE));
+
+        for row_index in 0..1 << LOG_SIZE {
+            let circle_domain_index = coset_index_to_circle_domain_index(row_index, LOG_SIZE);
+            let bit_rev_index = bit_reverse_index(circle_domain_index, LOG_SIZE);
+            assert_eq!(seq.values.at(bit_rev_index), BaseField::from(row_index));
+        }
+    }
+
+    #[test]
+    fn test_gen_is_last() {
+        const LOG_SIZE: u32 = 5;
+        let last_row = (1 << LOG_SIZE) - 1;
+        let last_row_index = bit_reverse_index(
+            coset_index_to_circle_domain_index(last_row, LOG_SIZE),
+            LOG_SIZE,
+        );
+
+        let is_last = gen_preprocessed_column::<CpuBackend>(&PreprocessedColumn::IsLast(LOG_SIZE));
+
+        let non_zero_indices = (0..1 << LOG_SIZE)
+            .filter(|&i| !is_last.values.at(i).is_zero())
+            .collect_vec();
+        assert_eq!(non_zero_indices, [last_row_index]);
+        assert_eq!(is_last.values.at(last_row_index), BaseField::one());
+    }
+
+    #[test]
+    fn test_gen_periodic() {
+        const LOG_SIZE: u32 = 5;
+        const PATTERN: [BaseField; 4] = [m31!(7), m31!(0), m31!(3), m31!(9)];
+
+        let periodic = gen_preprocessed_column::<CpuBackend>(&PreprocessedColumn::Periodic {
+            log_size: LOG_SIZE,
+            pattern: &PATTERN,
+        });
+
+        for row_index in 0..1 << LOG_SIZE {
+            let circle_domain_index = coset_index_to_circle_domain_index(row_index, LOG_SIZE);
+            let bit_rev_index = bit_reverse_index(circle_domain_index, LOG_SIZE);
+            assert_eq!(
+                periodic.values.at(bit_rev_index),
+                PATTERN[row_index % PATTERN.len()]
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "does not divide")]
+    fn test_gen_periodic_rejects_non_dividing_pattern() {
+        const PATTERN: [BaseField; 3] = [m31!(1), m31!(2), m31!(3)];
+
+        gen_preprocessed_column::<CpuBackend>(&PreprocessedColumn::Periodic {
+            log_size: 4,
+            pattern: &PATTERN,
+        });
+    }
+
+    /// Requests the given preprocessed columns, and has no constraints.
+    struct PreprocessedEval(Vec<PreprocessedColumn>);
+
+    impl FrameworkEval for PreprocessedEval {
+        fn name(&self) -> String {
+            "PreprocessedEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            5
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size() + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            for &column in &self.0 {
+                eval.get_preprocessed_column(column);
+            }
+            eval
+        }
+    }
+
+    #[test]
+    fn test_gather_preprocessed_columns() {
+        let allocator = &mut TraceLocationAllocator::default();
+        let [first, second] = [
+            vec![PreprocessedColumn::IsFirst(5)],
+            vec![PreprocessedColumn::Seq(5), PreprocessedColumn::IsFirst(5)],
+        ]
+        .map(|columns| {
+            FrameworkComponent::new(
+                allocator,
+                PreprocessedEval(columns),
+                (SecureField::zero(), None),
+            )
+        });
+        let components: [&dyn Component; 2] = [&first, &second];
+
+        let columns = gather_preprocessed_columns(&components);
+        let trace = gen_gathered_preprocessed_columns::<CpuBackend>(&components);
+
+        assert_eq!(
+            columns,
+            [PreprocessedColumn::IsFirst(5), PreprocessedColumn::Seq(5)]
+        );
+        assert_eq!(second.preproccessed_column_indices(), [1, 0]);
+        assert_eq!(trace.len(), 2);
+        for (eval, column) in zip(&trace, &columns) {
+            assert_eq!(
+                eval.values,
+                gen_preprocessed_column::<CpuBackend>(column).values
+            );
+        }
+    }
+
+    #[test]
+    fn test_seq_counts_as_one_preprocessed_column() {
+        let mut eval = InfoEvaluator::empty();
+
+        eval.get_preprocessed_column(PreprocessedColumn::Seq(5));
+
+        assert_eq!(eval.preprocessed_columns, [PreprocessedColumn::Seq(5)]);
+    }
+}
@@ -154,6 +154,8 @@ impl<'a> EvalAtRow for RelationTrackerEvaluator<'a> {
 
     fn finalize_logup(&mut self) {}
 
+    fn start_logup_sub_sum(&mut self, _sums: LogupSums) {}
+
     fn add_to_relation<R: Relation<Self::F, Self::EF>>(
         &mut self,
         entries: &[RelationEntry<'_, Self::F, Self::EF, R>],
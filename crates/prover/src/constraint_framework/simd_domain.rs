@@ -3,7 +3,7 @@ use std::ops::Mul;
 use num_traits::Zero;
 
 use super::logup::{LogupAtRow, LogupSums};
-use super::{EvalAtRow, INTERACTION_TRACE_IDX};
+use super::{validate_mask, EvalAtRow, MaskError, INTERACTION_TRACE_IDX};
 use crate::core::backend::simd::column::VeryPackedBaseColumn;
 use crate::core::backend::simd::m31::LOG_N_LANES;
 use crate::core::backend::simd::very_packed_m31::{
@@ -61,12 +61,30 @@ impl<'a> EvalAtRow for SimdDomainEvaluator<'a> {
     type F = VeryPackedBaseField;
     type EF = VeryPackedSecureField;
 
-    // TODO(Ohad): Add debug boundary checks.
+    fn validate_next_interaction_mask(
+        &self,
+        interaction: usize,
+        offsets: &[isize],
+    ) -> Result<(), MaskError> {
+        validate_mask(
+            self.trace_eval,
+            &self.column_index_per_interaction,
+            interaction,
+            offsets,
+            self.domain_log_size,
+        )
+    }
+
     fn next_interaction_mask<const N: usize>(
         &mut self,
         interaction: usize,
         offsets: [isize; N],
     ) -> [Self::F; N] {
+        if cfg!(debug_assertions) {
+            if let Err(err) = self.validate_next_interaction_mask(interaction, &offsets) {
+                panic!("{err}");
+            }
+        }
         let col_index = self.column_index_per_interaction[interaction];
         self.column_index_per_interaction[interaction] += 1;
         offsets.map(|off| {
@@ -112,3 +130,64 @@ impl<'a> EvalAtRow for SimdDomainEvaluator<'a> {
 
     super::logup_proxy!();
 }
+
+#[cfg(test)]
+mod tests {
+    use num_traits::Zero;
+
+    use super::SimdDomainEvaluator;
+    use crate::constraint_framework::{EvalAtRow, MaskError};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Col, Column};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation};
+    use crate::core::poly::BitReversedOrder;
+
+    const LOG_SIZE: u32 = 6;
+
+    #[test]
+    fn test_try_next_interaction_mask() {
+        let domain = CanonicCoset::new(LOG_SIZE + 1).circle_domain();
+        let column = CircleEvaluation::<SimdBackend, BaseField, BitReversedOrder>::new(
+            domain,
+            Col::<SimdBackend, BaseField>::zeros(domain.size()),
+        );
+        let trace = TreeVec::new(vec![vec![], vec![&column]]);
+        let mut eval = SimdDomainEvaluator::new(
+            &trace,
+            0,
+            &[],
+            LOG_SIZE,
+            LOG_SIZE + 1,
+            LOG_SIZE,
+            (SecureField::zero(), None),
+        );
+
+        assert_eq!(
+            eval.try_next_interaction_mask(2, [0]).unwrap_err(),
+            MaskError::InteractionOutOfRange {
+                interaction: 2,
+                n_interactions: 2
+            }
+        );
+        assert_eq!(
+            eval.try_next_interaction_mask(1, [-(1 << LOG_SIZE)])
+                .unwrap_err(),
+            MaskError::OffsetOutOfRange {
+                offset: -(1 << LOG_SIZE),
+                log_size: LOG_SIZE
+            }
+        );
+        assert!(eval.try_next_interaction_mask(1, [0, 1]).is_ok());
+        assert_eq!(
+            eval.try_next_interaction_mask(1, [0]).unwrap_err(),
+            MaskError::ColumnOutOfRange {
+                interaction: 1,
+                column: 1,
+                n_columns: 1
+            }
+        );
+    }
+}
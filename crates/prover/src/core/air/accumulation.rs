@@ -13,7 +13,7 @@ use crate::core::fields::secure_column::SecureColumnByCoords;
 use crate::core::fields::FieldOps;
 use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, CirclePoly, SecureCirclePoly};
 use crate::core::poly::BitReversedOrder;
-use crate::core::utils::generate_secure_powers;
+use crate::core::utils::powers_of;
 
 /// Accumulates N evaluations of u_i(P0) at a single point.
 /// Computes f(P0), the combined polynomial at that point.
@@ -22,6 +22,8 @@ use crate::core::utils::generate_secure_powers;
 pub struct PointEvaluationAccumulator {
     random_coeff: SecureField,
     accumulation: SecureField,
+    /// The accumulated evaluations, in accumulation order.
+    terms: Vec<SecureField>,
 }
 
 impl PointEvaluationAccumulator {
@@ -31,22 +33,39 @@ impl PointEvaluationAccumulator {
         Self {
             random_coeff,
             accumulation: SecureField::default(),
+            terms: Vec::new(),
         }
     }
 
     /// Accumulates u_i(P0), a polynomial evaluation at a P0 in reverse order.
     pub fn accumulate(&mut self, evaluation: SecureField) {
         self.accumulation = self.accumulation * self.random_coeff + evaluation;
+        self.terms.push(evaluation);
     }
 
-    pub const fn finalize(self) -> SecureField {
+    pub fn finalize(self) -> SecureField {
         self.accumulation
     }
+
+    /// Returns the accumulated evaluations u_0(P0), ..., u_{N-1}(P0), in accumulation order.
+    ///
+    /// Components accumulate their constraints one after the other, so the terms of each component
+    /// are a consecutive run of [`Component::n_constraints()`] terms. The i'th term contributes
+    /// `alpha^(N-1-i) * u_i(P0)` to [`Self::finalize()`].
+    ///
+    /// [`Component::n_constraints()`]: crate::core::air::Component::n_constraints
+    pub fn into_terms(self) -> Vec<SecureField> {
+        self.terms
+    }
 }
 
 // TODO(ShaharS), rename terminology to constraints instead of columns.
 /// Accumulates evaluations of u_i(P), each at an evaluation domain of the size of that polynomial.
 /// Computes the coefficients of f(P).
+///
+/// Evaluations of different sizes are kept in separate sub-accumulations, allocated on first use,
+/// so small components don't pay for the size of the largest one. Each sub-accumulation is only
+/// extended to the next larger size in use when finalizing.
 pub struct DomainEvaluationAccumulator<B: Backend> {
     random_coeff_powers: Vec<SecureField>,
     /// Accumulated evaluations for each log_size.
@@ -63,7 +82,7 @@ impl<B: Backend> DomainEvaluationAccumulator<B> {
     pub fn new(random_coeff: SecureField, max_log_size: u32, total_columns: usize) -> Self {
         let max_log_size = max_log_size as usize;
         Self {
-            random_coeff_powers: generate_secure_powers(random_coeff, total_columns),
+            random_coeff_powers: powers_of(random_coeff, total_columns),
             sub_accumulations: (0..(max_log_size + 1)).map(|_| None).collect(),
         }
     }
@@ -96,6 +115,48 @@ impl<B: Backend> DomainEvaluationAccumulator<B> {
             .unwrap_or_else(|_| unreachable!())
     }
 
+    /// Splits off an accumulator for each entry of `n_cols_per_accumulator`, expecting to
+    /// accumulate that many evaluations.
+    ///
+    /// The random coefficients are handed out in the same order as consecutive calls to
+    /// [Self::columns], so accumulating into the split accumulators and [merging] them back gives
+    /// the same result as accumulating into `self`.
+    ///
+    /// [merging]: Self::merge
+    pub fn split(&mut self, n_cols_per_accumulator: &[usize]) -> Vec<Self> {
+        n_cols_per_accumulator
+            .iter()
+            .map(|&n_cols| Self {
+                random_coeff_powers: self
+                    .random_coeff_powers
+                    .split_off(self.random_coeff_powers.len() - n_cols),
+                sub_accumulations: (0..self.sub_accumulations.len()).map(|_| None).collect(),
+            })
+            .collect()
+    }
+
+    /// Adds the evaluations accumulated in `other`, an accumulator returned by [Self::split].
+    pub fn merge(&mut self, other: Self) {
+        assert_eq!(
+            other.random_coeff_powers.len(),
+            0,
+            "not all random coefficients were used"
+        );
+        for (accumulation, other_accumulation) in self
+            .sub_accumulations
+            .iter_mut()
+            .zip(other.sub_accumulations)
+        {
+            let Some(other_accumulation) = other_accumulation else {
+                continue;
+            };
+            match accumulation {
+                Some(accumulation) => B::accumulate(accumulation, &other_accumulation),
+                None => *accumulation = Some(other_accumulation),
+            }
+        }
+    }
+
     /// Returns the log size of the resulting polynomial.
     pub fn log_size(&self) -> u32 {
         (self.sub_accumulations.len() - 1) as u32
@@ -181,6 +242,7 @@ mod tests {
     use crate::core::backend::cpu::CpuCircleEvaluation;
     use crate::core::circle::CirclePoint;
     use crate::core::fields::m31::{M31, P};
+    use crate::core::fields::FieldExpOps;
     use crate::qm31;
 
     #[test]
@@ -216,6 +278,28 @@ mod tests {
         assert_eq!(accumulator_res, res);
     }
 
+    #[test]
+    fn test_point_evaluation_accumulator_terms() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let evaluations: [SecureField; 10] = rng.gen();
+        let alpha = qm31!(2, 3, 4, 5);
+        let mut accumulator = PointEvaluationAccumulator::new(alpha);
+        for evaluation in evaluations {
+            accumulator.accumulate(evaluation);
+        }
+
+        let terms = accumulator.clone().into_terms();
+
+        assert_eq!(terms, evaluations);
+        let n_terms = terms.len();
+        let reconstructed: SecureField = terms
+            .into_iter()
+            .enumerate()
+            .map(|(i, term)| alpha.pow((n_terms - 1 - i) as u128) * term)
+            .sum();
+        assert_eq!(reconstructed, accumulator.finalize());
+    }
+
     #[test]
     fn test_domain_evaluation_accumulator() {
         // Generate a vector of random sizes with a constant seed.
@@ -295,4 +379,43 @@ mod tests {
 
         assert_eq!(accumulator_res, res);
     }
+
+    #[test]
+    fn test_domain_evaluation_accumulator_mixed_log_sizes_matches_padded() {
+        const SMALL_LOG_SIZE: u32 = 10;
+        const LARGE_LOG_SIZE: u32 = 16;
+        let mut rng = SmallRng::seed_from_u64(0);
+        let small_domain = CanonicCoset::new(SMALL_LOG_SIZE).circle_domain();
+        let large_domain = CanonicCoset::new(LARGE_LOG_SIZE).circle_domain();
+        let small_eval = CpuCircleEvaluation::<_, BitReversedOrder>::new(
+            small_domain,
+            (0..small_domain.size()).map(|_| rng.gen()).collect(),
+        );
+        let large_eval = CpuCircleEvaluation::<_, BitReversedOrder>::new(
+            large_domain,
+            (0..large_domain.size()).map(|_| rng.gen()).collect(),
+        );
+        let padded_small_eval = small_eval.clone().interpolate().evaluate(large_domain);
+        let alpha = qm31!(2, 3, 4, 5);
+        let accumulate =
+            |accumulator: &mut DomainEvaluationAccumulator<CpuBackend>,
+             eval: &CpuCircleEvaluation<BaseField, BitReversedOrder>| {
+                let [mut col] = accumulator.columns([(eval.domain.log_size(), 1)]);
+                for (index, &value) in eval.values.iter().enumerate() {
+                    col.accumulate(index, col.random_coeff_powers[0] * value);
+                }
+            };
+
+        let mut mixed = DomainEvaluationAccumulator::new(alpha, LARGE_LOG_SIZE, 2);
+        accumulate(&mut mixed, &small_eval);
+        accumulate(&mut mixed, &large_eval);
+        let mut padded = DomainEvaluationAccumulator::new(alpha, LARGE_LOG_SIZE, 2);
+        accumulate(&mut padded, &padded_small_eval);
+        accumulate(&mut padded, &large_eval);
+
+        let [mixed_poly, padded_poly] = [mixed, padded].map(|acc| acc.finalize());
+        for (mixed_coord, padded_coord) in mixed_poly.iter().zip(padded_poly.iter()) {
+            assert_eq!(mixed_coord.coeffs, padded_coord.coeffs);
+        }
+    }
 }
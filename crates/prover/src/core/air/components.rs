@@ -1,23 +1,219 @@
-use std::iter::zip;
+use std::collections::{BTreeMap, HashMap};
+use std::iter::{self, zip};
 
 use itertools::Itertools;
+use num_traits::Zero;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use thiserror::Error;
 
 use super::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
 use super::{Component, ComponentProver, Trace};
-use crate::constraint_framework::PREPROCESSED_TRACE_IDX;
+use crate::constraint_framework::preprocessed_columns::PreprocessedColumn;
+use crate::constraint_framework::{
+    INTERACTION_TRACE_IDX, ORIGINAL_TRACE_IDX, PREPROCESSED_TRACE_IDX,
+};
 use crate::core::backend::Backend;
+use crate::core::channel::ChannelScript;
 use crate::core::circle::CirclePoint;
 use crate::core::fields::qm31::SecureField;
+use crate::core::fri::{FriConfig, CIRCLE_TO_LINE_FOLD_STEP, FOLD_STEP};
 use crate::core::pcs::TreeVec;
 use crate::core::poly::circle::SecureCirclePoly;
+use crate::core::prover::VerificationError;
+use crate::core::vcs::blake2_hash::{Blake2sHash, Blake2sHasher};
 use crate::core::ColumnVec;
 
+/// The log sizes of the columns committed in each tree of the trace of an AIR, see
+/// [`Components::commitment_layout()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitmentLayout {
+    pub preprocessed: Vec<u32>,
+    pub original: Vec<u32>,
+    pub interaction: Vec<u32>,
+}
+
+/// The index of a component in [`Components::components`].
+pub type ComponentIndex = usize;
+
+/// An inconsistency between a component and the trace it reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum ComponentError {
+    #[error("Component {component_index} has mask points for {got} trees, expected {expected}.")]
+    TreeCountMismatch {
+        expected: usize,
+        got: usize,
+        component_index: ComponentIndex,
+    },
+    #[error(
+        "Component {component_index} uses preprocessed column {column_index}, but there are only \
+         {n_preprocessed_columns}."
+    )]
+    PreprocessedColumnOutOfRange {
+        column_index: usize,
+        n_preprocessed_columns: usize,
+        component_index: ComponentIndex,
+    },
+}
+
 pub struct Components<'a> {
     pub components: Vec<&'a dyn Component>,
     pub n_preprocessed_columns: usize,
 }
 
 impl<'a> Components<'a> {
+    /// Appends the components of `other`, after the components of `self`.
+    ///
+    /// The preprocessed columns are shared, so the merged list spans the preprocessed columns of
+    /// both.
+    pub fn extend(&mut self, other: Components<'a>) {
+        self.components.extend(other.components);
+        self.n_preprocessed_columns = self
+            .n_preprocessed_columns
+            .max(other.n_preprocessed_columns);
+    }
+
+    /// Returns the components of `a` followed by the components of `b`, see [`Self::extend()`].
+    pub fn concat(mut a: Components<'a>, b: Components<'a>) -> Components<'a> {
+        a.extend(b);
+        a
+    }
+
+    /// Returns the Fiat-Shamir operations of the composition stage, which both
+    /// [`prove`](crate::core::prover::prove) and [`verify`](crate::core::prover::verify) run
+    /// through a [ChannelScriptRunner](crate::core::channel::ChannelScriptRunner): mixing the
+    /// [claimed logup sums](Self::claimed_logup_sums) of the components, drawing the composition
+    /// random coefficient, mixing the composition polynomial commitment root and drawing the OODS
+    /// point.
+    pub fn channel_mix_sequence(&self) -> ChannelScript {
+        ChannelScript::new()
+            .mix_felts(self.claimed_logup_sums().len())
+            .draw_felts(1)
+            .mix_root()
+            .draw_point()
+    }
+
+    /// Returns the components in the order their columns were allocated, see
+    /// [`Component::allocated_locations()`]. The components that don't track their columns keep
+    /// their position.
+    ///
+    /// The verifier reads the mask values in the order of the committed columns, so a list of
+    /// components can only be proven in its allocation order. To get the same order for equivalent
+    /// lists, whatever order they are built in, allocate the columns in canonical order, see
+    /// [`FrameworkComponent::new_in_canonical_order()`].
+    ///
+    /// [`FrameworkComponent::new_in_canonical_order()`]:
+    ///     crate::constraint_framework::FrameworkComponent::new_in_canonical_order
+    pub fn allocation_order(&self) -> Components<'a> {
+        let mut allocated = self
+            .components
+            .iter()
+            .filter_map(|&component| Some((component.allocated_locations()?, component)))
+            .sorted_by_key(|(locations, _)| {
+                locations.iter().map(|span| span.col_start).collect_vec()
+            })
+            .map(|(_, component)| component);
+        Components {
+            components: self
+                .components
+                .iter()
+                .map(|&component| match component.allocated_locations() {
+                    Some(_) => allocated.next().unwrap(),
+                    None => component,
+                })
+                .collect(),
+            n_preprocessed_columns: self.n_preprocessed_columns,
+        }
+    }
+
+    /// Returns a hash of the names and layouts of the components, in order.
+    ///
+    /// A prover and a verifier agree on the order of the components if their fingerprints match.
+    pub fn fingerprint(&self) -> Blake2sHash {
+        let mut hasher = Blake2sHasher::new();
+        hasher.update(&(self.n_preprocessed_columns as u64).to_le_bytes());
+        for &component in &self.components {
+            let (name, layout_hash) = component_key(component);
+            hasher.update(&(name.len() as u64).to_le_bytes());
+            hasher.update(name.as_bytes());
+            hasher.update(&layout_hash);
+        }
+        hasher.finalize()
+    }
+
+    /// Returns the number of constraints of each degree, across all the components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component does not track its constraint degrees, see
+    /// [`Component::constraint_degrees()`].
+    pub fn constraint_degree_histogram(&self) -> BTreeMap<u32, usize> {
+        let mut histogram = BTreeMap::new();
+        for component in &self.components {
+            let degrees = component.constraint_degrees().unwrap_or_else(|| {
+                panic!(
+                    "Component {} does not track its constraint degrees",
+                    component.name()
+                )
+            });
+            for degree in degrees {
+                *histogram.entry(degree).or_default() += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Returns each distinct preprocessed column requested by the components, in order of first
+    /// request, with the indices of the components that request it.
+    pub fn shared_preprocessed_columns(&self) -> Vec<(PreprocessedColumn, Vec<ComponentIndex>)> {
+        let mut columns: Vec<(PreprocessedColumn, Vec<ComponentIndex>)> = vec![];
+        for (component_index, component) in self.components.iter().enumerate() {
+            for column in component.preprocessed_columns() {
+                match columns.iter_mut().find(|(c, _)| *c == column) {
+                    Some((_, component_indices)) => {
+                        if component_indices.last() != Some(&component_index) {
+                            component_indices.push(component_index);
+                        }
+                    }
+                    None => columns.push((column, vec![component_index])),
+                }
+            }
+        }
+        columns
+    }
+
+    /// Returns the claimed logup sum of each component that has one, in order.
+    pub fn claimed_logup_sums(&self) -> Vec<SecureField> {
+        self.components
+            .iter()
+            .filter_map(|component| component.claimed_logup_sum())
+            .collect()
+    }
+
+    /// Returns the sum of the claimed logup sums of the components.
+    pub fn claimed_logup_sum(&self) -> SecureField {
+        self.claimed_logup_sums().into_iter().sum()
+    }
+
+    /// Checks that the claimed logup sums of the components cancel out, returning the remainder
+    /// of their sum if they don't.
+    pub fn assert_balanced(&self) -> Result<(), SecureField> {
+        let remainder = self.claimed_logup_sum();
+        if !remainder.is_zero() {
+            return Err(remainder);
+        }
+        Ok(())
+    }
+
+    /// Checks that the claimed logup sums of the components cancel out, see
+    /// [`Self::assert_balanced()`].
+    pub fn verify_logup_balance(&self) -> Result<(), VerificationError> {
+        self.assert_balanced()
+            .map_err(|_| VerificationError::UnbalancedLogup)
+    }
+
+    /// Returns the log degree bound of the composition polynomial: the largest of the constraint
+    /// log degree bounds of the components.
     pub fn composition_log_degree_bound(&self) -> u32 {
         self.components
             .iter()
@@ -26,26 +222,118 @@ impl<'a> Components<'a> {
             .unwrap()
     }
 
+    /// Returns the log size of the largest column of the trace.
+    pub fn max_trace_log_size(&self) -> u32 {
+        self.column_log_sizes().flatten().into_iter().max().unwrap()
+    }
+
+    /// Returns the minimal log blowup factor of the trace evaluation domain, so that it is large
+    /// enough to evaluate the composition polynomial on.
+    pub fn required_blowup_log(&self) -> u32 {
+        self.composition_log_degree_bound() - self.max_trace_log_size()
+    }
+
+    /// Returns the log sizes of the domains of the FRI layers of a proof of the components, with
+    /// `config`: the circle domain of the first layer, the line domains of the inner layers and the
+    /// line domain of the last layer.
+    ///
+    /// A verifier can reject a proof whose FRI layers don't match before verifying it.
+    pub fn expected_fri_layer_log_sizes(&self, config: &FriConfig) -> Vec<u32> {
+        let max_log_degree_bound = self
+            .components
+            .iter()
+            .flat_map(|component| component.trace_log_degree_bounds().flatten())
+            .chain([self.composition_log_degree_bound()])
+            .max()
+            .unwrap();
+        let first_layer_log_size = max_log_degree_bound + config.log_blowup_factor;
+        let last_layer_log_size = config.log_last_layer_degree_bound + config.log_blowup_factor;
+        let line_layer_log_sizes = (last_layer_log_size
+            ..=first_layer_log_size - CIRCLE_TO_LINE_FOLD_STEP)
+            .rev()
+            .step_by(FOLD_STEP as usize);
+        iter::once(first_layer_log_size)
+            .chain(line_layer_log_sizes)
+            .collect()
+    }
+
+    /// Checks that the OODS point lies outside the trace domains of the columns of the
+    /// components, where the constraint quotients are undefined.
+    pub fn validate_oods_point(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> Result<(), VerificationError> {
+        // Only base field points lie in a trace domain.
+        let [x, y] = [point.x, point.y].map(|coord| coord.to_m31_array());
+        if x[1..].iter().chain(&y[1..]).any(|c| !c.is_zero()) {
+            return Ok(());
+        }
+        // The canonic coset of size `2^log_size` holds exactly the points of order
+        // `2^(log_size + 1)`.
+        let log_order = CirclePoint { x: x[0], y: y[0] }.log_order();
+        let in_domain = self
+            .components
+            .iter()
+            .flat_map(|component| component.trace_log_degree_bounds().flatten())
+            .any(|log_size| log_size + 1 == log_order);
+        if in_domain {
+            return Err(VerificationError::OodsPointInDomain(log_order - 1));
+        }
+        Ok(())
+    }
+
+    /// Returns the points at which the trace columns are sampled, see [`Self::try_mask_points()`].
+    ///
+    /// # Panics
+    ///
+    /// If the components are inconsistent, see [`ComponentError`].
     pub fn mask_points(
         &self,
         point: CirclePoint<SecureField>,
     ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
-        let mut mask_points = TreeVec::concat_cols(
-            self.components
-                .iter()
-                .map(|component| component.mask_points(point)),
-        );
+        self.try_mask_points(point)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns the points at which the trace columns are sampled, given the OODS point.
+    ///
+    /// Fails if a component reports mask points for a different number of trees than its trace
+    /// has, or refers to a preprocessed column that doesn't exist.
+    pub fn try_mask_points(
+        &self,
+        point: CirclePoint<SecureField>,
+    ) -> Result<TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>>, ComponentError> {
+        let mut mask_points = TreeVec::default();
+        for (component_index, component) in self.components.iter().enumerate() {
+            let component_mask_points = component.mask_points(point);
+            let n_trees = component.trace_log_degree_bounds().len();
+            if component_mask_points.len() != n_trees {
+                return Err(ComponentError::TreeCountMismatch {
+                    expected: n_trees,
+                    got: component_mask_points.len(),
+                    component_index,
+                });
+            }
+            mask_points.append_cols(component_mask_points);
+        }
 
         let preprocessed_mask_points = &mut mask_points[PREPROCESSED_TRACE_IDX];
         *preprocessed_mask_points = vec![vec![]; self.n_preprocessed_columns];
 
-        for component in &self.components {
+        for (component_index, component) in self.components.iter().enumerate() {
             for idx in component.preproccessed_column_indices() {
-                preprocessed_mask_points[idx] = vec![point];
+                let column_mask_points = preprocessed_mask_points.get_mut(idx).ok_or(
+                    ComponentError::PreprocessedColumnOutOfRange {
+                        column_index: idx,
+                        n_preprocessed_columns: self.n_preprocessed_columns,
+                        component_index,
+                    },
+                )?;
+                *column_mask_points = vec![point];
             }
         }
 
-        mask_points
+        Ok(mask_points)
     }
 
     pub fn eval_composition_polynomial_at_point(
@@ -65,6 +353,24 @@ impl<'a> Components<'a> {
         evaluation_accumulator.finalize()
     }
 
+    /// Returns the log sizes of the columns of each tree, as in [`Self::column_log_sizes()`].
+    ///
+    /// The interaction tree is empty if no component has interaction columns.
+    pub fn commitment_layout(&self) -> CommitmentLayout {
+        let mut column_log_sizes = self.column_log_sizes();
+        let mut take_tree = |tree_index: usize| {
+            column_log_sizes
+                .get_mut(tree_index)
+                .map(std::mem::take)
+                .unwrap_or_default()
+        };
+        CommitmentLayout {
+            preprocessed: take_tree(PREPROCESSED_TRACE_IDX),
+            original: take_tree(ORIGINAL_TRACE_IDX),
+            interaction: take_tree(INTERACTION_TRACE_IDX),
+        }
+    }
+
     pub fn column_log_sizes(&self) -> TreeVec<ColumnVec<u32>> {
         let mut preprocessed_columns_trace_log_sizes = vec![0; self.n_preprocessed_columns];
         let mut visited_columns = vec![false; self.n_preprocessed_columns];
@@ -103,6 +409,55 @@ impl<'a> Components<'a> {
     }
 }
 
+/// Returns the sort key of a component: its name, then a hash of the parameters that determine
+/// its columns and constraints.
+fn component_key(component: &dyn Component) -> (String, [u8; 32]) {
+    let trace_log_degree_bounds = component.trace_log_degree_bounds();
+    let preprocessed_column_indices = component.preproccessed_column_indices();
+    let layout = [
+        component.n_constraints(),
+        component.max_constraint_log_degree_bound() as usize,
+        trace_log_degree_bounds.len(),
+    ]
+    .into_iter()
+    .chain(trace_log_degree_bounds.iter().flat_map(|tree| {
+        std::iter::once(tree.len()).chain(tree.iter().map(|&log_size| log_size as usize))
+    }))
+    .chain(std::iter::once(preprocessed_column_indices.len()))
+    .chain(preprocessed_column_indices)
+    .flat_map(|value| (value as u64).to_le_bytes())
+    .collect_vec();
+    (component.name(), Blake2sHasher::hash(&layout).into())
+}
+
+type MaskPoints = TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>>;
+
+/// Memoizes [`Components::mask_points()`] by the sampled point.
+///
+/// Points are compared exactly, so a hit returns the mask points computed for the same point.
+pub struct MaskPointsCache<'a> {
+    components: &'a Components<'a>,
+    cache: HashMap<CirclePoint<SecureField>, MaskPoints>,
+}
+
+impl<'a> MaskPointsCache<'a> {
+    pub fn new(components: &'a Components<'a>) -> Self {
+        Self {
+            components,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the mask points of the components at `point`, computing them on the first call for
+    /// each point.
+    pub fn mask_points(&mut self, point: CirclePoint<SecureField>) -> MaskPoints {
+        self.cache
+            .entry(point)
+            .or_insert_with(|| self.components.mask_points(point))
+            .clone()
+    }
+}
+
 pub struct ComponentProvers<'a, B: Backend> {
     pub components: Vec<&'a dyn ComponentProver<B>>,
     pub n_preprocessed_columns: usize,
@@ -130,9 +485,747 @@ impl<'a, B: Backend> ComponentProvers<'a, B> {
             self.components().composition_log_degree_bound(),
             total_constraints,
         );
+        #[cfg(not(feature = "parallel"))]
         for component in &self.components {
             component.evaluate_constraint_quotients_on_domain(trace, &mut accumulator)
         }
+
+        // Each component accumulates into its own accumulator, so that they can be evaluated in
+        // parallel.
+        #[cfg(feature = "parallel")]
+        {
+            let mut component_accumulators = accumulator.split(
+                &self
+                    .components
+                    .iter()
+                    .map(|c| c.n_constraints())
+                    .collect_vec(),
+            );
+            self.components
+                .par_iter()
+                .zip(component_accumulators.par_iter_mut())
+                .for_each(|(component, component_accumulator)| {
+                    component.evaluate_constraint_quotients_on_domain(trace, component_accumulator)
+                });
+            for component_accumulator in component_accumulators {
+                accumulator.merge(component_accumulator);
+            }
+        }
         accumulator.finalize()
     }
+
+    /// Computes the total logup sum of each component from its interaction trace, without
+    /// evaluating any constraints, see [`ComponentProver::trace_logup_sum()`].
+    ///
+    /// Meant for debugging the logup wiring of new components: the returned sums should equal the
+    /// claimed sums used by the full proof, see [`Components::claimed_logup_sum()`].
+    pub fn prove_lookups_only(&self, trace: &Trace<'_, B>) -> Vec<Option<SecureField>> {
+        self.components
+            .iter()
+            .map(|component| component.trace_logup_sum(trace))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::iter::zip;
+
+    use itertools::Itertools;
+    use num_traits::Zero;
+
+    use super::{CommitmentLayout, ComponentError, ComponentProvers, Components, MaskPointsCache};
+    use crate::constraint_framework::preprocessed_columns::PreprocessedColumn;
+    use crate::constraint_framework::{
+        EvalAtRow, FrameworkComponent, FrameworkEval, TraceLocationAllocator, INTERACTION_TRACE_IDX,
+    };
+    #[cfg(feature = "parallel")]
+    use crate::core::air::accumulation::DomainEvaluationAccumulator;
+    use crate::core::air::accumulation::PointEvaluationAccumulator;
+    use crate::core::air::{Component, ComponentProver, Trace};
+    #[cfg(feature = "parallel")]
+    use crate::core::backend::simd::m31::LOG_N_LANES;
+    #[cfg(feature = "parallel")]
+    use crate::core::backend::Column;
+    use crate::core::channel::{Blake2sChannel, ChannelOp};
+    use crate::core::circle::CirclePoint;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::FieldExpOps;
+    use crate::core::pcs::TreeVec;
+    use crate::core::poly::circle::CanonicCoset;
+    use crate::core::prover::VerificationError;
+    use crate::core::ColumnVec;
+    use crate::examples::state_machine::components::{
+        StateMachineElements, StateMachineOp0Component, StateTransitionEval,
+    };
+    use crate::examples::state_machine::gen::{gen_interaction_trace, gen_trace};
+    #[cfg(feature = "parallel")]
+    use crate::examples::wide_fibonacci::{generate_trace, FibInput};
+    use crate::examples::wide_fibonacci::{WideFibonacciComponent, WideFibonacciEval};
+    use crate::qm31;
+
+    struct TestComponent {
+        log_size: u32,
+        claimed_logup_sum: Option<SecureField>,
+    }
+
+    impl TestComponent {
+        const fn new(log_size: u32) -> Self {
+            Self {
+                log_size,
+                claimed_logup_sum: None,
+            }
+        }
+
+        const fn with_logup_sum(claimed_logup_sum: SecureField) -> Self {
+            Self {
+                log_size: 4,
+                claimed_logup_sum: Some(claimed_logup_sum),
+            }
+        }
+    }
+
+    impl Component for TestComponent {
+        fn name(&self) -> String {
+            "TestComponent".to_string()
+        }
+
+        fn n_constraints(&self) -> usize {
+            0
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size + 1
+        }
+
+        fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+            TreeVec::new(vec![vec![], vec![self.log_size]])
+        }
+
+        fn mask_points(
+            &self,
+            _point: CirclePoint<SecureField>,
+        ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+            TreeVec::default()
+        }
+
+        fn preproccessed_column_indices(&self) -> ColumnVec<usize> {
+            vec![]
+        }
+
+        fn evaluate_constraint_quotients_at_point(
+            &self,
+            _point: CirclePoint<SecureField>,
+            _mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+            _evaluation_accumulator: &mut PointEvaluationAccumulator,
+        ) {
+        }
+
+        fn claimed_logup_sum(&self) -> Option<SecureField> {
+            self.claimed_logup_sum
+        }
+    }
+
+    /// Has two constraints of degree 2 and one of degree 3.
+    struct MixedDegreeEval;
+
+    impl FrameworkEval for MixedDegreeEval {
+        fn name(&self) -> String {
+            "MixedDegreeEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            4
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size() + 2
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let a = eval.next_trace_mask();
+            let b = eval.next_trace_mask();
+            let c = eval.next_trace_mask();
+            eval.add_constraint(a.clone() * b.clone() - c.clone());
+            eval.add_constraint(a.clone() * b.clone() * c.clone() - a.clone());
+            eval.add_constraint(c.clone() * c - b);
+            eval
+        }
+    }
+
+    /// Multiplies a trace column by a preprocessed column.
+    struct PreprocessedEval(PreprocessedColumn);
+
+    impl FrameworkEval for PreprocessedEval {
+        fn name(&self) -> String {
+            "PreprocessedEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            10
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size() + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let preprocessed = eval.get_preprocessed_column(self.0);
+            let a = eval.next_trace_mask();
+            eval.add_constraint(preprocessed * a);
+            eval
+        }
+    }
+
+    /// Has two trace columns and an extension interaction column.
+    struct InteractionEval;
+
+    impl FrameworkEval for InteractionEval {
+        fn name(&self) -> String {
+            "InteractionEval".to_string()
+        }
+
+        fn log_size(&self) -> u32 {
+            5
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            self.log_size() + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let [a, b] = eval.next_trace_masks();
+            let [c] = eval.next_extension_interaction_mask(INTERACTION_TRACE_IDX, [0]);
+            eval.add_constraint(c - E::EF::from(a * b));
+            eval
+        }
+    }
+
+    fn components<C: Component>(components: &[C]) -> Components<'_> {
+        Components {
+            components: components.iter().map(|c| c as &dyn Component).collect(),
+            n_preprocessed_columns: 0,
+        }
+    }
+
+    #[test]
+    fn test_logup_balance() {
+        let sum = qm31!(1, 2, 3, 4);
+        let balanced = [
+            TestComponent::with_logup_sum(sum),
+            TestComponent::new(4),
+            TestComponent::with_logup_sum(-sum),
+        ];
+
+        components(&balanced).verify_logup_balance().unwrap();
+    }
+
+    #[test]
+    fn test_channel_mix_sequence_mixes_claimed_sums() {
+        let sum = qm31!(1, 2, 3, 4);
+        let test_components = [
+            TestComponent::with_logup_sum(sum),
+            TestComponent::new(4),
+            TestComponent::with_logup_sum(-sum),
+        ];
+        let components = components(&test_components);
+
+        assert_eq!(components.claimed_logup_sums(), [sum, -sum]);
+        assert_eq!(
+            components.channel_mix_sequence().ops,
+            [
+                ChannelOp::MixFelts(2),
+                ChannelOp::DrawFelts(1),
+                ChannelOp::MixRoot,
+                ChannelOp::DrawPoint,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_logup_balance_rejects_unbalanced() {
+        let unbalanced = [
+            TestComponent::with_logup_sum(qm31!(1, 2, 3, 4)),
+            TestComponent::with_logup_sum(qm31!(4, 3, 2, 1)),
+        ];
+
+        assert!(matches!(
+            components(&unbalanced).verify_logup_balance(),
+            Err(VerificationError::UnbalancedLogup)
+        ));
+    }
+
+    #[test]
+    fn test_assert_balanced_rejects_tampered_multiplicity() {
+        let denoms = [qm31!(1, 2, 3, 4), qm31!(5, 6, 7, 8), qm31!(9, 10, 11, 12)];
+        let logup_sum = |multiplicities: [i32; 3]| -> SecureField {
+            zip(multiplicities, denoms)
+                .map(|(multiplicity, denom)| SecureField::from(multiplicity) / denom)
+                .sum()
+        };
+        let balanced = [
+            TestComponent::with_logup_sum(logup_sum([1, 1, 1])),
+            TestComponent::with_logup_sum(-logup_sum([1, 1, 1])),
+        ];
+        let tampered = [
+            TestComponent::with_logup_sum(logup_sum([1, 1, 1])),
+            TestComponent::with_logup_sum(-logup_sum([1, 2, 1])),
+        ];
+
+        assert_eq!(components(&balanced).assert_balanced(), Ok(()));
+        assert_eq!(
+            components(&tampered).assert_balanced(),
+            Err(-denoms[1].inverse())
+        );
+    }
+
+    #[test]
+    fn test_concat() {
+        const N: usize = 8;
+        let allocator = &mut TraceLocationAllocator::default();
+        let [first, second] = [5, 7].map(|log_n_rows| {
+            WideFibonacciComponent::new(
+                allocator,
+                WideFibonacciEval::<N> { log_n_rows },
+                (SecureField::zero(), None),
+            )
+        });
+        let single = |component| Components {
+            components: vec![component as &dyn Component],
+            n_preprocessed_columns: 0,
+        };
+        let point = CirclePoint::get_point(98765);
+
+        let merged = Components::concat(single(&first), single(&second));
+
+        assert_eq!(merged.components.len(), 2);
+        assert_eq!(
+            merged.mask_points(point).0,
+            TreeVec::concat_cols(
+                [single(&first), single(&second)]
+                    .iter()
+                    .map(|c| c.mask_points(point))
+            )
+            .0
+        );
+        assert_eq!(
+            merged.column_log_sizes().0,
+            TreeVec::concat_cols(
+                [single(&first), single(&second)]
+                    .iter()
+                    .map(|c| c.column_log_sizes())
+            )
+            .0
+        );
+    }
+
+    #[test]
+    fn test_required_blowup_log() {
+        let mixed_degree = FrameworkComponent::new(
+            &mut TraceLocationAllocator::default(),
+            MixedDegreeEval,
+            (SecureField::zero(), None),
+        );
+        let small = TestComponent::new(3);
+        let components = Components {
+            components: vec![&mixed_degree, &small],
+            n_preprocessed_columns: 0,
+        };
+
+        assert_eq!(components.composition_log_degree_bound(), 6);
+        assert_eq!(components.max_trace_log_size(), 4);
+        assert_eq!(components.required_blowup_log(), 2);
+    }
+
+    #[test]
+    fn test_validate_oods_point() {
+        let test_components = [TestComponent::new(4), TestComponent::new(6)];
+        let components = components(&test_components);
+
+        components
+            .validate_oods_point(CirclePoint::get_point(98765))
+            .unwrap();
+        // A base field point outside the trace domains.
+        components
+            .validate_oods_point(CanonicCoset::new(5).at(3).into_ef())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_validate_oods_point_rejects_in_domain_point() {
+        let test_components = [TestComponent::new(4), TestComponent::new(6)];
+        let components = components(&test_components);
+
+        for log_size in [4, 6] {
+            let point = CanonicCoset::new(log_size).at(3).into_ef();
+            assert!(matches!(
+                components.validate_oods_point(point),
+                Err(VerificationError::OodsPointInDomain(l)) if l == log_size
+            ));
+        }
+    }
+
+    #[test]
+    fn test_constraint_degree_histogram() {
+        let allocator = &mut TraceLocationAllocator::default();
+        let components = [0, 1].map(|_| {
+            FrameworkComponent::new(allocator, MixedDegreeEval, (SecureField::zero(), None))
+        });
+        let components = Components {
+            components: components.iter().map(|c| c as &dyn Component).collect(),
+            n_preprocessed_columns: 0,
+        };
+
+        let histogram = components.constraint_degree_histogram();
+
+        assert_eq!(histogram, BTreeMap::from([(2, 4), (3, 2)]));
+    }
+
+    #[test]
+    fn test_shared_preprocessed_columns() {
+        let allocator = &mut TraceLocationAllocator::default();
+        let components = [
+            PreprocessedColumn::IsFirst(10),
+            PreprocessedColumn::Seq(10),
+            PreprocessedColumn::IsFirst(10),
+        ]
+        .map(|column| {
+            FrameworkComponent::new(
+                allocator,
+                PreprocessedEval(column),
+                (SecureField::zero(), None),
+            )
+        });
+        let components = Components {
+            components: components.iter().map(|c| c as &dyn Component).collect(),
+            n_preprocessed_columns: 2,
+        };
+
+        let shared_columns = components.shared_preprocessed_columns();
+
+        assert_eq!(
+            shared_columns,
+            [
+                (PreprocessedColumn::IsFirst(10), vec![0, 2]),
+                (PreprocessedColumn::Seq(10), vec![1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commitment_layout() {
+        let allocator = &mut TraceLocationAllocator::default();
+        let preprocessed = FrameworkComponent::new(
+            allocator,
+            PreprocessedEval(PreprocessedColumn::IsFirst(10)),
+            (SecureField::zero(), None),
+        );
+        let interaction =
+            FrameworkComponent::new(allocator, InteractionEval, (SecureField::zero(), None));
+        let air = Components {
+            components: vec![&preprocessed, &interaction],
+            n_preprocessed_columns: 1,
+        };
+
+        let layout = air.commitment_layout();
+
+        assert_eq!(
+            layout,
+            CommitmentLayout {
+                preprocessed: vec![10],
+                original: vec![10, 5, 5],
+                interaction: vec![5; 4],
+            }
+        );
+        assert_eq!(
+            components(&[TestComponent::new(4)]).commitment_layout(),
+            CommitmentLayout {
+                preprocessed: vec![],
+                original: vec![4],
+                interaction: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_canonical_order() {
+        const N: usize = 4;
+        let canonical_components = |log_sizes: [u32; 3]| {
+            FrameworkComponent::new_in_canonical_order(
+                &mut TraceLocationAllocator::default(),
+                log_sizes.map(|log_n_rows| {
+                    (
+                        WideFibonacciEval::<N> { log_n_rows },
+                        (SecureField::zero(), None),
+                    )
+                }),
+            )
+        };
+        let ordered = canonical_components([4, 5, 6]);
+        let reordered = canonical_components([6, 4, 5]);
+        let allocator = &mut TraceLocationAllocator::default();
+        let unsorted = [6, 4, 5].map(|log_n_rows| {
+            WideFibonacciComponent::new(
+                allocator,
+                WideFibonacciEval::<N> { log_n_rows },
+                (SecureField::zero(), None),
+            )
+        });
+        let ordered = components(&ordered);
+        let reordered = components(&reordered);
+        let unsorted = components(&unsorted);
+
+        assert_eq!(ordered.fingerprint(), reordered.fingerprint());
+        assert_ne!(ordered.fingerprint(), unsorted.fingerprint());
+        for (ordered, reordered) in zip(&ordered.components, &reordered.components) {
+            assert_eq!(
+                ordered.allocated_locations().unwrap().0,
+                reordered.allocated_locations().unwrap().0
+            );
+        }
+    }
+
+    #[test]
+    fn test_allocation_order() {
+        const N: usize = 4;
+        let allocator = &mut TraceLocationAllocator::default();
+        let fib = [4, 5].map(|log_n_rows| {
+            WideFibonacciComponent::new(
+                allocator,
+                WideFibonacciEval::<N> { log_n_rows },
+                (SecureField::zero(), None),
+            )
+        });
+        let test_component = TestComponent::new(4);
+        let allocated = Components {
+            components: vec![&fib[0], &test_component, &fib[1]],
+            n_preprocessed_columns: 0,
+        };
+        let swapped = Components {
+            components: vec![&fib[1], &test_component, &fib[0]],
+            n_preprocessed_columns: 0,
+        };
+
+        assert_eq!(
+            allocated.allocation_order().fingerprint(),
+            allocated.fingerprint()
+        );
+        assert_eq!(
+            swapped.allocation_order().fingerprint(),
+            allocated.fingerprint()
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_composition_polynomial_matches_sequential_accumulation() {
+        const N: usize = 8;
+        let allocator = &mut TraceLocationAllocator::default();
+        let components = [5, 7].map(|log_n_rows| {
+            WideFibonacciComponent::new(
+                allocator,
+                WideFibonacciEval::<N> { log_n_rows },
+                (SecureField::zero(), None),
+            )
+        });
+        let trace_polys = [5, 7]
+            .into_iter()
+            .flat_map(|log_size| {
+                let inputs = (0..1 << (log_size - LOG_N_LANES))
+                    .map(|i| FibInput::from_seeds(BaseField::from(i), BaseField::from(i + 1)))
+                    .collect_vec();
+                generate_trace::<N>(log_size, &inputs)
+            })
+            .map(|eval| eval.interpolate())
+            .collect_vec();
+        let trace_evals = trace_polys
+            .iter()
+            .map(|poly| poly.evaluate(CanonicCoset::new(poly.log_size() + 1).circle_domain()))
+            .collect_vec();
+        let trace = Trace {
+            polys: TreeVec::new(vec![vec![], trace_polys.iter().collect()]),
+            evals: TreeVec::new(vec![vec![], trace_evals.iter().collect()]),
+        };
+        let component_provers = ComponentProvers {
+            components: components
+                .iter()
+                .map(|c| c as &dyn ComponentProver<_>)
+                .collect(),
+            n_preprocessed_columns: 0,
+        };
+        let random_coeff = qm31!(1, 2, 3, 4);
+
+        let composition_poly =
+            component_provers.compute_composition_polynomial(random_coeff, &trace);
+
+        let mut accumulator = DomainEvaluationAccumulator::new(
+            random_coeff,
+            component_provers
+                .components()
+                .composition_log_degree_bound(),
+            components.iter().map(|c| c.n_constraints()).sum(),
+        );
+        for component in &components {
+            component.evaluate_constraint_quotients_on_domain(&trace, &mut accumulator);
+        }
+        let sequential_poly = accumulator.finalize();
+        for (coordinate, sequential_coordinate) in composition_poly.0.iter().zip(&sequential_poly.0)
+        {
+            assert_eq!(
+                coordinate.coeffs.to_cpu(),
+                sequential_coordinate.coeffs.to_cpu()
+            );
+        }
+    }
+
+    #[test]
+    fn test_prove_lookups_only() {
+        const LOG_SIZE: u32 = 5;
+        const CLAIMED_ROW: usize = 10;
+        let lookup_elements = StateMachineElements::draw(&mut Blake2sChannel::default());
+        let trace = gen_trace(LOG_SIZE, [BaseField::from(3), BaseField::from(7)], 0);
+        let (interaction_trace, [total_sum, claimed_sum]) =
+            gen_interaction_trace(CLAIMED_ROW, &trace, 0, &lookup_elements);
+        let component = StateMachineOp0Component::new(
+            &mut TraceLocationAllocator::default(),
+            StateTransitionEval {
+                log_n_rows: LOG_SIZE,
+                lookup_elements,
+                total_sum,
+                claimed_sum: (claimed_sum, CLAIMED_ROW),
+            },
+            (total_sum, Some((claimed_sum, CLAIMED_ROW))),
+        );
+        let trace_polys = trace
+            .into_iter()
+            .map(|eval| eval.interpolate())
+            .collect_vec();
+        let interaction_polys = interaction_trace
+            .into_iter()
+            .map(|eval| eval.interpolate())
+            .collect_vec();
+        let trace = Trace {
+            polys: TreeVec::new(vec![
+                vec![],
+                trace_polys.iter().collect(),
+                interaction_polys.iter().collect(),
+            ]),
+            evals: TreeVec::new(vec![vec![], vec![], vec![]]),
+        };
+        let component_provers = ComponentProvers {
+            components: vec![&component as &dyn ComponentProver<_>],
+            n_preprocessed_columns: 0,
+        };
+
+        let lookup_sums = component_provers.prove_lookups_only(&trace);
+
+        assert_eq!(lookup_sums, vec![Some(total_sum)]);
+        assert_eq!(lookup_sums[0], component.claimed_logup_sum());
+    }
+
+    #[test]
+    fn test_prove_lookups_only_without_logup() {
+        let component = WideFibonacciComponent::new(
+            &mut TraceLocationAllocator::default(),
+            WideFibonacciEval::<8> { log_n_rows: 4 },
+            (SecureField::zero(), None),
+        );
+        let trace = Trace {
+            polys: TreeVec::new(vec![vec![], vec![]]),
+            evals: TreeVec::new(vec![vec![], vec![]]),
+        };
+        let component_provers = ComponentProvers {
+            components: vec![&component as &dyn ComponentProver<_>],
+            n_preprocessed_columns: 0,
+        };
+
+        assert_eq!(component_provers.prove_lookups_only(&trace), vec![None]);
+    }
+
+    #[test]
+    fn test_try_mask_points_tree_count_mismatch() {
+        let fibonacci = WideFibonacciComponent::new(
+            &mut TraceLocationAllocator::default(),
+            WideFibonacciEval::<8> { log_n_rows: 4 },
+            (SecureField::zero(), None),
+        );
+        // Reports mask points for no trees, but has two trees.
+        let inconsistent = TestComponent::new(4);
+        let components = Components {
+            components: vec![&fibonacci, &inconsistent],
+            n_preprocessed_columns: 0,
+        };
+
+        assert_eq!(
+            components
+                .try_mask_points(CirclePoint::get_point(98765))
+                .unwrap_err(),
+            ComponentError::TreeCountMismatch {
+                expected: 2,
+                got: 0,
+                component_index: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_try_mask_points_preprocessed_column_out_of_range() {
+        let component = FrameworkComponent::new(
+            &mut TraceLocationAllocator::default(),
+            PreprocessedEval(PreprocessedColumn::IsFirst(10)),
+            (SecureField::zero(), None),
+        );
+        let components = Components {
+            components: vec![&component],
+            n_preprocessed_columns: 0,
+        };
+
+        assert_eq!(
+            components
+                .try_mask_points(CirclePoint::get_point(98765))
+                .unwrap_err(),
+            ComponentError::PreprocessedColumnOutOfRange {
+                column_index: 0,
+                n_preprocessed_columns: 0,
+                component_index: 0
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Component 0 has mask points for 0 trees, expected 2.")]
+    fn test_mask_points_panics_on_inconsistent_component() {
+        let inconsistent = TestComponent::new(4);
+
+        components(&[inconsistent]).mask_points(CirclePoint::get_point(98765));
+    }
+
+    #[test]
+    fn test_mask_points_cache() {
+        let allocator = &mut TraceLocationAllocator::default();
+        let components = [4, 5].map(|log_n_rows| {
+            WideFibonacciComponent::new(
+                allocator,
+                WideFibonacciEval::<8> { log_n_rows },
+                (SecureField::zero(), None),
+            )
+        });
+        let components = Components {
+            components: components.iter().map(|c| c as &dyn Component).collect(),
+            n_preprocessed_columns: 0,
+        };
+        let mut cache = MaskPointsCache::new(&components);
+        let point = CirclePoint::<SecureField>::get_point(98765);
+        let other_point = CirclePoint::<SecureField>::get_point(4321);
+
+        assert_eq!(cache.mask_points(point).0, components.mask_points(point).0);
+        assert_eq!(cache.mask_points(point).0, components.mask_points(point).0);
+        assert_eq!(
+            cache.mask_points(other_point).0,
+            components.mask_points(other_point).0
+        );
+        assert_eq!(cache.cache.len(), 2);
+    }
 }
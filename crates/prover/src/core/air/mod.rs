@@ -1,14 +1,17 @@
-pub use components::{ComponentProvers, Components};
+pub use components::{
+    CommitmentLayout, ComponentError, ComponentIndex, ComponentProvers, Components, MaskPointsCache,
+};
 
 use self::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
-use super::backend::Backend;
+use super::backend::{Backend, MaybeSync};
 use super::circle::CirclePoint;
 use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
-use super::pcs::TreeVec;
+use super::pcs::{TreeSubspan, TreeVec};
 use super::poly::circle::{CircleEvaluation, CirclePoly};
 use super::poly::BitReversedOrder;
 use super::ColumnVec;
+use crate::constraint_framework::preprocessed_columns::PreprocessedColumn;
 
 pub mod accumulation;
 mod components;
@@ -30,6 +33,16 @@ pub trait AirProver<B: Backend>: Air {
 /// A component is a set of trace columns of various sizes along with a set of
 /// constraints on them.
 pub trait Component {
+    /// Returns the name of the kind of the component. Defaults to its type name.
+    ///
+    /// The name is part of the fingerprint of a list of components, see
+    /// [`Components::fingerprint()`]. Type names are not guaranteed to be the same across builds,
+    /// so components whose fingerprint is compared between builds, e.g. between a prover and a
+    /// verifier binary, should override it.
+    fn name(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+
     fn n_constraints(&self) -> usize;
 
     fn max_constraint_log_degree_bound(&self) -> u32;
@@ -47,6 +60,35 @@ pub trait Component {
 
     fn preproccessed_column_indices(&self) -> ColumnVec<usize>;
 
+    /// Returns the preprocessed columns the component requests, in the order of
+    /// [`Self::preproccessed_column_indices()`]. Defaults to none.
+    fn preprocessed_columns(&self) -> Vec<PreprocessedColumn> {
+        vec![]
+    }
+
+    /// Returns the columns allocated to the component in each tree, if the component tracks
+    /// them, see [`Components::allocation_order()`]. Defaults to `None`.
+    fn allocated_locations(&self) -> Option<TreeVec<TreeSubspan>> {
+        None
+    }
+
+    /// Returns the total sum of the logup fractions of the component, or `None` if the component
+    /// takes no part in a logup argument.
+    ///
+    /// The claimed sums of all the components must cancel out, see
+    /// [`Components::verify_logup_balance()`].
+    fn claimed_logup_sum(&self) -> Option<SecureField> {
+        None
+    }
+
+    /// Returns the algebraic degree of each constraint in the trace values, or `None` if the
+    /// component does not track them.
+    ///
+    /// See [`Components::constraint_degree_histogram()`].
+    fn constraint_degrees(&self) -> Option<Vec<u32>> {
+        None
+    }
+
     /// Evaluates the constraint quotients combination of the component at a point.
     fn evaluate_constraint_quotients_at_point(
         &self,
@@ -56,7 +98,7 @@ pub trait Component {
     );
 }
 
-pub trait ComponentProver<B: Backend>: Component {
+pub trait ComponentProver<B: Backend>: Component + MaybeSync {
     /// Evaluates the constraint quotients of the component on the evaluation domain.
     /// Accumulates quotients in `evaluation_accumulator`.
     fn evaluate_constraint_quotients_on_domain(
@@ -64,6 +106,15 @@ pub trait ComponentProver<B: Backend>: Component {
         trace: &Trace<'_, B>,
         evaluation_accumulator: &mut DomainEvaluationAccumulator<B>,
     );
+
+    /// Returns the total logup sum of the component, read off the last row of its committed
+    /// interaction trace. None if the component has no logup argument.
+    ///
+    /// Unlike [`Component::claimed_logup_sum()`], the sum is computed from the trace, so it can be
+    /// compared against the claimed sum to debug the interaction trace generation.
+    fn trace_logup_sum(&self, _trace: &Trace<'_, B>) -> Option<SecureField> {
+        None
+    }
 }
 
 /// The set of polynomials that make up the trace.
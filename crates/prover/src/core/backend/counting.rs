@@ -0,0 +1,494 @@
+//! A backend that tallies the work requested from it, for estimating the cost of a proof.
+//!
+//! [CountingBackend] does no arithmetic: its operations return zeroed values of the right shapes,
+//! so that a dry-run proof goes through all of the prover steps at the cost of allocating its
+//! columns. The values computed by a dry run are meaningless, e.g. the prover may report that the
+//! constraints are not satisfied.
+
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::mem;
+use std::ops::Add;
+
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+use super::{Backend, BackendForChannel, Col, Column, ColumnOps, MaybeSendSync};
+use crate::core::air::accumulation::AccumulationOps;
+use crate::core::channel::{Channel, MerkleChannel};
+use crate::core::circle::{CirclePoint, Coset};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SecureColumnByCoords;
+use crate::core::fields::{Field, FieldOps};
+use crate::core::fri::FriOps;
+use crate::core::lookups::gkr_prover::{GkrMultivariatePolyOracle, GkrOps, Layer};
+use crate::core::lookups::mle::{Mle, MleOps};
+use crate::core::lookups::utils::UnivariatePoly;
+use crate::core::pcs::quotients::{ColumnSampleBatch, QuotientOps};
+use crate::core::poly::circle::{
+    CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly, PolyOps, SecureEvaluation,
+};
+use crate::core::poly::line::LineEvaluation;
+use crate::core::poly::twiddles::TwiddleTree;
+use crate::core::poly::BitReversedOrder;
+use crate::core::proof_of_work::GrindOps;
+use crate::core::vcs::ops::{MerkleHasher, MerkleOps};
+
+/// The work requested from [CountingBackend] during a dry run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkCounts {
+    /// Number of circle FFTs and inverse FFTs, one per column.
+    pub n_ffts: usize,
+    /// Total size of the domains of the FFTs.
+    pub n_fft_points: usize,
+    /// Estimated number of field multiplications of the requested operations, e.g. `n log(n) / 2`
+    /// for an FFT of size `n`. Multiplications done by components outside of backend operations
+    /// are not counted.
+    pub n_multiplications: usize,
+    /// Number of bytes hashed when committing to Merkle layers.
+    pub n_hashed_bytes: usize,
+    /// Number of field elements batch inverted.
+    pub n_inverted_elements: usize,
+}
+
+impl WorkCounts {
+    const ZERO: Self = Self {
+        n_ffts: 0,
+        n_fft_points: 0,
+        n_multiplications: 0,
+        n_hashed_bytes: 0,
+        n_inverted_elements: 0,
+    };
+}
+
+impl Add for WorkCounts {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            n_ffts: self.n_ffts + rhs.n_ffts,
+            n_fft_points: self.n_fft_points + rhs.n_fft_points,
+            n_multiplications: self.n_multiplications + rhs.n_multiplications,
+            n_hashed_bytes: self.n_hashed_bytes + rhs.n_hashed_bytes,
+            n_inverted_elements: self.n_inverted_elements + rhs.n_inverted_elements,
+        }
+    }
+}
+
+thread_local! {
+    /// The work requested on this thread since the innermost dry run started.
+    static WORK_COUNTS: Cell<WorkCounts> = const { Cell::new(WorkCounts::ZERO) };
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+pub struct CountingBackend;
+
+impl CountingBackend {
+    /// Runs `f`, returning its result and the work it requested from [CountingBackend].
+    ///
+    /// Only the work requested on the calling thread is counted, so dry runs on different threads
+    /// don't interfere. Dry runs may be nested, the work of an inner dry run is counted in the
+    /// outer one too.
+    pub fn dry_run<R>(f: impl FnOnce() -> R) -> (R, WorkCounts) {
+        let outer_counts = WORK_COUNTS.with(Cell::take);
+        let res = f();
+        let counts = WORK_COUNTS.with(|work_counts| work_counts.replace(WorkCounts::ZERO));
+        WORK_COUNTS.with(|work_counts| work_counts.set(outer_counts + counts));
+        (res, counts)
+    }
+}
+
+fn count(update: impl FnOnce(&mut WorkCounts)) {
+    WORK_COUNTS.with(|work_counts| {
+        let mut counts = work_counts.get();
+        update(&mut counts);
+        work_counts.set(counts);
+    });
+}
+
+fn count_multiplications(n: usize) {
+    count(|counts| counts.n_multiplications += n);
+}
+
+fn count_fft(domain: CircleDomain) {
+    count(|counts| {
+        counts.n_ffts += 1;
+        counts.n_fft_points += domain.size();
+        counts.n_multiplications += (domain.size() * domain.log_size() as usize) / 2;
+    });
+}
+
+fn zero_secure_column(len: usize) -> SecureColumnByCoords<CountingBackend> {
+    SecureColumnByCoords::zeros(len)
+}
+
+fn zero_mle<F: Field>(n_variables: usize) -> Mle<CountingBackend, F>
+where
+    CountingBackend: MleOps<F>,
+{
+    Mle::new(Col::<CountingBackend, F>::zeros(1 << n_variables))
+}
+
+impl Backend for CountingBackend {}
+impl<MC: MerkleChannel> BackendForChannel<MC> for CountingBackend {}
+
+impl<T: Debug + Clone + Default + MaybeSendSync> ColumnOps<T> for CountingBackend {
+    type Column = Vec<T>;
+
+    fn bit_reverse_column(_column: &mut Self::Column) {}
+}
+
+impl<F: Field> FieldOps<F> for CountingBackend {
+    fn batch_inverse(column: &Self::Column, _dst: &mut Self::Column) {
+        count(|counts| {
+            counts.n_inverted_elements += column.len();
+            // Montgomery's trick takes 3 multiplications per element.
+            counts.n_multiplications += 3 * column.len();
+        });
+    }
+}
+
+impl PolyOps for CountingBackend {
+    type Twiddles = Vec<BaseField>;
+
+    fn new_canonical_ordered(
+        coset: CanonicCoset,
+        values: Col<Self, BaseField>,
+    ) -> CircleEvaluation<Self, BaseField, BitReversedOrder> {
+        CircleEvaluation::new(coset.circle_domain(), values)
+    }
+
+    fn interpolate(
+        eval: CircleEvaluation<Self, BaseField, BitReversedOrder>,
+        _itwiddles: &TwiddleTree<Self>,
+    ) -> CirclePoly<Self> {
+        count_fft(eval.domain);
+        let mut coeffs = eval.values;
+        coeffs.fill(BaseField::zero());
+        CirclePoly::new(coeffs)
+    }
+
+    fn eval_at_point(poly: &CirclePoly<Self>, _point: CirclePoint<SecureField>) -> SecureField {
+        count_multiplications(poly.coeffs.len());
+        SecureField::zero()
+    }
+
+    fn extend(poly: &CirclePoly<Self>, log_size: u32) -> CirclePoly<Self> {
+        CirclePoly::new(Col::<Self, BaseField>::zeros(
+            1 << log_size.max(poly.log_size()),
+        ))
+    }
+
+    fn evaluate(
+        _poly: &CirclePoly<Self>,
+        domain: CircleDomain,
+        _twiddles: &TwiddleTree<Self>,
+    ) -> CircleEvaluation<Self, BaseField, BitReversedOrder> {
+        count_fft(domain);
+        CircleEvaluation::new(domain, Col::<Self, BaseField>::zeros(domain.size()))
+    }
+
+    fn precompute_twiddles(coset: Coset) -> TwiddleTree<Self> {
+        TwiddleTree {
+            root_coset: coset,
+            twiddles: vec![],
+            itwiddles: vec![],
+        }
+    }
+}
+
+impl QuotientOps for CountingBackend {
+    fn accumulate_quotients(
+        domain: CircleDomain,
+        _columns: &[&CircleEvaluation<Self, BaseField, BitReversedOrder>],
+        _random_coeff: SecureField,
+        sample_batches: &[ColumnSampleBatch],
+        _log_blowup_factor: u32,
+    ) -> SecureEvaluation<Self, BitReversedOrder> {
+        let n_sampled_columns = sample_batches
+            .iter()
+            .map(|batch| batch.columns_and_values.len())
+            .sum::<usize>();
+        count_multiplications(n_sampled_columns * domain.size());
+        SecureEvaluation::new(domain, zero_secure_column(domain.size()))
+    }
+}
+
+impl FriOps for CountingBackend {
+    fn fold_line(
+        eval: &LineEvaluation<Self>,
+        _alpha: SecureField,
+        _twiddles: &TwiddleTree<Self>,
+    ) -> LineEvaluation<Self> {
+        // Two multiplications per folded pair of values.
+        count_multiplications(eval.len());
+        LineEvaluation::new(eval.domain().double(), zero_secure_column(eval.len() / 2))
+    }
+
+    fn fold_circle_into_line(
+        dst: &mut LineEvaluation<Self>,
+        src: &SecureEvaluation<Self, BitReversedOrder>,
+        _alpha: SecureField,
+        _twiddles: &TwiddleTree<Self>,
+    ) {
+        count_multiplications(src.len() + dst.len());
+    }
+
+    fn decompose(
+        eval: &SecureEvaluation<Self, BitReversedOrder>,
+    ) -> (SecureEvaluation<Self, BitReversedOrder>, SecureField) {
+        (
+            SecureEvaluation::new(eval.domain, zero_secure_column(eval.len())),
+            SecureField::zero(),
+        )
+    }
+}
+
+impl AccumulationOps for CountingBackend {
+    fn accumulate(_column: &mut SecureColumnByCoords<Self>, _other: &SecureColumnByCoords<Self>) {}
+}
+
+impl MleOps<BaseField> for CountingBackend {
+    fn fix_first_variable(
+        mle: Mle<Self, BaseField>,
+        _assignment: SecureField,
+    ) -> Mle<Self, SecureField> {
+        count_multiplications(mle.len() / 2);
+        zero_mle(mle.n_variables() - 1)
+    }
+}
+
+impl MleOps<SecureField> for CountingBackend {
+    fn fix_first_variable(
+        mle: Mle<Self, SecureField>,
+        _assignment: SecureField,
+    ) -> Mle<Self, SecureField> {
+        count_multiplications(mle.len() / 2);
+        zero_mle(mle.n_variables() - 1)
+    }
+}
+
+impl GkrOps for CountingBackend {
+    fn gen_eq_evals(y: &[SecureField], _v: SecureField) -> Mle<Self, SecureField> {
+        count_multiplications(1 << y.len());
+        zero_mle(y.len())
+    }
+
+    fn next_layer(layer: &Layer<Self>) -> Layer<Self> {
+        let n_variables = layer.n_variables() - 1;
+        count_multiplications(3 << n_variables);
+        match layer {
+            Layer::GrandProduct(_) => Layer::GrandProduct(zero_mle(n_variables)),
+            _ => Layer::LogUpGeneric {
+                numerators: zero_mle(n_variables),
+                denominators: zero_mle(n_variables),
+            },
+        }
+    }
+
+    fn sum_as_poly_in_first_variable(
+        h: &GkrMultivariatePolyOracle<'_, Self>,
+        _claim: SecureField,
+    ) -> UnivariatePoly<SecureField> {
+        count_multiplications(3 << h.input_layer.n_variables());
+        UnivariatePoly::zero()
+    }
+}
+
+impl<H: MerkleHasher> MerkleOps<H> for CountingBackend {
+    fn commit_on_layer(
+        log_size: u32,
+        prev_layer: Option<&Vec<H::Hash>>,
+        columns: &[&Vec<BaseField>],
+    ) -> Vec<H::Hash> {
+        let children_bytes = if prev_layer.is_some() {
+            2 * mem::size_of::<H::Hash>()
+        } else {
+            0
+        };
+        let node_bytes = children_bytes + columns.len() * mem::size_of::<BaseField>();
+        count(|counts| counts.n_hashed_bytes += node_bytes << log_size);
+        vec![H::Hash::default(); 1 << log_size]
+    }
+}
+
+impl<C: Channel> GrindOps<C> for CountingBackend {
+    fn grind(_channel: &C, _pow_bits: u32) -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{One, Zero};
+
+    use super::{CountingBackend, WorkCounts};
+    use crate::core::air::accumulation::{DomainEvaluationAccumulator, PointEvaluationAccumulator};
+    use crate::core::air::{Component, ComponentProver, Trace};
+    use crate::core::channel::MerkleChannel;
+    use crate::core::circle::CirclePoint;
+    use crate::core::constraints::coset_vanishing;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::{FieldExpOps, FieldOps};
+    use crate::core::pcs::{CommitmentSchemeProver, PcsConfig, TreeVec};
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, PolyOps};
+    use crate::core::prover::prove;
+    use crate::core::utils::bit_reverse_index;
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleChannel;
+    #[cfg(not(target_arch = "wasm32"))]
+    use crate::core::vcs::poseidon252_merkle::Poseidon252MerkleChannel;
+    use crate::core::ColumnVec;
+
+    const LOG_SIZE: u32 = 4;
+
+    /// A component with columns `a` and `b`, constrained by `a^2 - b = 0`.
+    struct SquareComponent;
+
+    impl Component for SquareComponent {
+        fn name(&self) -> String {
+            "SquareComponent".to_string()
+        }
+
+        fn n_constraints(&self) -> usize {
+            1
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            LOG_SIZE + 1
+        }
+
+        fn trace_log_degree_bounds(&self) -> TreeVec<ColumnVec<u32>> {
+            TreeVec::new(vec![vec![], vec![LOG_SIZE; 2]])
+        }
+
+        fn mask_points(
+            &self,
+            point: CirclePoint<SecureField>,
+        ) -> TreeVec<ColumnVec<Vec<CirclePoint<SecureField>>>> {
+            TreeVec::new(vec![vec![], vec![vec![point]; 2]])
+        }
+
+        fn preproccessed_column_indices(&self) -> ColumnVec<usize> {
+            vec![]
+        }
+
+        fn evaluate_constraint_quotients_at_point(
+            &self,
+            point: CirclePoint<SecureField>,
+            mask: &TreeVec<ColumnVec<Vec<SecureField>>>,
+            evaluation_accumulator: &mut PointEvaluationAccumulator,
+        ) {
+            let [a, b] = [mask[1][0][0], mask[1][1][0]];
+            let denom_inv = coset_vanishing(CanonicCoset::new(LOG_SIZE).coset, point).inverse();
+            evaluation_accumulator.accumulate((a.square() - b) * denom_inv);
+        }
+    }
+
+    impl ComponentProver<CountingBackend> for SquareComponent {
+        fn evaluate_constraint_quotients_on_domain(
+            &self,
+            trace: &Trace<'_, CountingBackend>,
+            evaluation_accumulator: &mut DomainEvaluationAccumulator<CountingBackend>,
+        ) {
+            let eval_log_size = self.max_constraint_log_degree_bound();
+            let eval_domain = CanonicCoset::new(eval_log_size).circle_domain();
+            let [acc] = evaluation_accumulator.columns([(eval_log_size, 1)]);
+            let [a, b] = [trace.evals[1][0], trace.evals[1][1]];
+            for row in 0..1 << eval_log_size {
+                let point = eval_domain.at(bit_reverse_index(row, eval_log_size));
+                let denom_inv = coset_vanishing(CanonicCoset::new(LOG_SIZE).coset, point).inverse();
+                let constraint = a.values[row].square() - b.values[row];
+                let quotient = acc.random_coeff_powers[0] * constraint * denom_inv;
+                acc.col.set(row, acc.col.at(row) + quotient);
+            }
+        }
+    }
+
+    /// Dry runs a proof of [SquareComponent] over `MC`.
+    fn dry_run_square_proof<MC: MerkleChannel>() -> WorkCounts {
+        let config = PcsConfig::default();
+        let twiddles = CountingBackend::precompute_twiddles(
+            CanonicCoset::new(LOG_SIZE + 1 + config.fri_config.log_blowup_factor)
+                .circle_domain()
+                .half_coset,
+        );
+        let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+        let a = (0..1 << LOG_SIZE).map(BaseField::from).collect::<Vec<_>>();
+        let b = a.iter().map(|a| a.square()).collect();
+
+        let ((), counts) = CountingBackend::dry_run(|| {
+            let channel = &mut MC::C::default();
+            let mut commitment_scheme =
+                CommitmentSchemeProver::<CountingBackend, MC>::new(config, &twiddles);
+            let mut tree_builder = commitment_scheme.tree_builder();
+            tree_builder.extend_evals([]);
+            tree_builder.commit(channel);
+            let mut tree_builder = commitment_scheme.tree_builder();
+            tree_builder.extend_evals([
+                CircleEvaluation::new(domain, a.clone()),
+                CircleEvaluation::new(domain, b),
+            ]);
+            tree_builder.commit(channel);
+
+            prove::<CountingBackend, MC>(&[&SquareComponent], channel, commitment_scheme).unwrap();
+        });
+        counts
+    }
+
+    #[test]
+    fn test_dry_run_counts_prover_work() {
+        let counts = dry_run_square_proof::<Blake2sMerkleChannel>();
+
+        // Interpolating and extending the two trace columns takes an FFT each way.
+        assert!(counts.n_ffts >= 4);
+        assert!(counts.n_fft_points >= 4 << LOG_SIZE);
+        assert!(counts.n_multiplications > 0);
+        assert!(counts.n_hashed_bytes > 0);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_dry_run_poseidon252() {
+        let blake_counts = dry_run_square_proof::<Blake2sMerkleChannel>();
+
+        let poseidon_counts = dry_run_square_proof::<Poseidon252MerkleChannel>();
+
+        // The channel changes the queries, but not the FFTs.
+        assert_eq!(poseidon_counts.n_ffts, blake_counts.n_ffts);
+        assert!(poseidon_counts.n_hashed_bytes > 0);
+    }
+
+    #[test]
+    fn test_nested_dry_runs() {
+        let standalone_counts = dry_run_square_proof::<Blake2sMerkleChannel>();
+
+        let (inner_counts, outer_counts) = CountingBackend::dry_run(|| {
+            let inner_counts = dry_run_square_proof::<Blake2sMerkleChannel>();
+            CountingBackend::batch_inverse(
+                &vec![BaseField::one(); 4],
+                &mut vec![BaseField::zero(); 4],
+            );
+            inner_counts
+        });
+
+        assert_eq!(inner_counts, standalone_counts);
+        assert_eq!(
+            outer_counts.n_inverted_elements,
+            inner_counts.n_inverted_elements + 4
+        );
+        assert_eq!(outer_counts.n_ffts, inner_counts.n_ffts);
+    }
+
+    #[test]
+    fn test_dry_run_ignores_other_threads() {
+        let ((), counts) = CountingBackend::dry_run(|| {
+            std::thread::spawn(dry_run_square_proof::<Blake2sMerkleChannel>)
+                .join()
+                .unwrap();
+        });
+
+        assert_eq!(counts, WorkCounts::default());
+    }
+}
@@ -1,3 +1,4 @@
+use itertools::Itertools;
 use num_traits::Zero;
 
 use super::CpuBackend;
@@ -8,7 +9,7 @@ use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
 use crate::core::fields::{ExtensionOf, FieldExpOps};
 use crate::core::poly::circle::{
-    CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly, PolyOps,
+    CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly, PolyOps, SecureCirclePoly,
 };
 use crate::core::poly::twiddles::TwiddleTree;
 use crate::core::poly::utils::{domain_line_twiddles_from_tree, fold};
@@ -89,15 +90,26 @@ impl PolyOps for CpuBackend {
             return poly.coeffs[0].into();
         }
 
-        let mut mappings = vec![point.y];
-        let mut x = point.x;
-        for _ in 1..poly.log_size() {
-            mappings.push(x);
-            x = CirclePoint::double_x(x);
+        fold(&poly.coeffs, &eval_mappings(point, poly.log_size()))
+    }
+
+    fn eval_secure_at_points(
+        poly: &SecureCirclePoly<Self>,
+        points: &[CirclePoint<SecureField>],
+    ) -> Vec<SecureField> {
+        // Packs the coordinate polynomials into a single polynomial with secure coefficients, so
+        // each point takes a single fold.
+        let coeffs = (0..1 << poly.log_size())
+            .map(|i| SecureField::from_m31_array(poly.each_ref().map(|c| c.coeffs[i])))
+            .collect_vec();
+        if poly.log_size() == 0 {
+            return vec![coeffs[0]; points.len()];
         }
-        mappings.reverse();
 
-        fold(&poly.coeffs, &mappings)
+        points
+            .iter()
+            .map(|&point| fold(&coeffs, &eval_mappings(point, poly.log_size())))
+            .collect()
     }
 
     fn extend(poly: &CirclePoly<Self>, log_size: u32) -> CirclePoly<Self> {
@@ -182,6 +194,19 @@ impl PolyOps for CpuBackend {
     }
 }
 
+/// Returns the folding factors that evaluate a polynomial of size `2^log_size` at `point` with
+/// [fold]: the iterated doublings of the x-coordinate, from the last, then the y-coordinate.
+fn eval_mappings(point: CirclePoint<SecureField>, log_size: u32) -> Vec<SecureField> {
+    let mut mappings = vec![point.y];
+    let mut x = point.x;
+    for _ in 1..log_size {
+        mappings.push(x);
+        x = CirclePoint::double_x(x);
+    }
+    mappings.reverse();
+    mappings
+}
+
 pub fn slow_precompute_twiddles(mut coset: Coset) -> Vec<BaseField> {
     let mut twiddles = Vec::with_capacity(coset.size());
     for _ in 0..coset.log_size() {
@@ -1,9 +1,11 @@
 mod accumulation;
 mod blake2s;
+mod blake3;
 pub mod circle;
 mod fri;
 mod grind;
 pub mod lookups;
+mod poseidon2;
 #[cfg(not(target_arch = "wasm32"))]
 mod poseidon252;
 pub mod quotients;
@@ -12,12 +14,13 @@ use std::fmt::Debug;
 
 use serde::{Deserialize, Serialize};
 
-use super::{Backend, BackendForChannel, Column, ColumnOps, FieldOps};
+use super::{Backend, BackendForChannel, Column, ColumnOps, FieldOps, MaybeSendSync};
 use crate::core::fields::Field;
 use crate::core::lookups::mle::Mle;
 use crate::core::poly::circle::{CircleEvaluation, CirclePoly};
 use crate::core::utils::bit_reverse;
 use crate::core::vcs::blake2_merkle::Blake2sMerkleChannel;
+use crate::core::vcs::blake3_merkle::Blake3MerkleChannel;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::core::vcs::poseidon252_merkle::Poseidon252MerkleChannel;
 
@@ -26,10 +29,11 @@ pub struct CpuBackend;
 
 impl Backend for CpuBackend {}
 impl BackendForChannel<Blake2sMerkleChannel> for CpuBackend {}
+impl BackendForChannel<Blake3MerkleChannel> for CpuBackend {}
 #[cfg(not(target_arch = "wasm32"))]
 impl BackendForChannel<Poseidon252MerkleChannel> for CpuBackend {}
 
-impl<T: Debug + Clone + Default> ColumnOps<T> for CpuBackend {
+impl<T: Debug + Clone + Default + MaybeSendSync> ColumnOps<T> for CpuBackend {
     type Column = Vec<T>;
 
     fn bit_reverse_column(column: &mut Self::Column) {
@@ -45,7 +49,7 @@ impl<F: Field> FieldOps<F> for CpuBackend {
     }
 }
 
-impl<T: Debug + Clone + Default> Column<T> for Vec<T> {
+impl<T: Debug + Clone + Default + MaybeSendSync> Column<T> for Vec<T> {
     fn zeros(len: usize) -> Self {
         vec![T::default(); len]
     }
@@ -0,0 +1,25 @@
+use itertools::Itertools;
+
+use super::CpuBackend;
+use crate::core::fields::m31::BaseField;
+use crate::core::vcs::ops::{MerkleHasher, MerkleOps};
+use crate::core::vcs::poseidon2_hash::{Poseidon2Hash, Poseidon2MerkleHasher, Poseidon2Params};
+
+impl<const WIDTH: usize, P: Poseidon2Params<WIDTH>> MerkleOps<Poseidon2MerkleHasher<WIDTH, P>>
+    for CpuBackend
+{
+    fn commit_on_layer(
+        log_size: u32,
+        prev_layer: Option<&Vec<Poseidon2Hash>>,
+        columns: &[&Vec<BaseField>],
+    ) -> Vec<Poseidon2Hash> {
+        (0..(1 << log_size))
+            .map(|i| {
+                Poseidon2MerkleHasher::<WIDTH, P>::hash_node(
+                    prev_layer.map(|prev_layer| (prev_layer[2 * i], prev_layer[2 * i + 1])),
+                    &columns.iter().map(|column| column[i]).collect_vec(),
+                )
+            })
+            .collect()
+    }
+}
@@ -14,6 +14,7 @@ use super::poly::circle::PolyOps;
 use super::proof_of_work::GrindOps;
 use super::vcs::ops::MerkleOps;
 
+pub mod counting;
 pub mod cpu;
 pub mod simd;
 
@@ -43,8 +44,30 @@ pub trait ColumnOps<T> {
 
 pub type Col<B, T> = <B as ColumnOps<T>>::Column;
 
+/// `Send + Sync` with the `parallel` feature, where columns are shared between threads, and no
+/// bound otherwise.
+#[cfg(feature = "parallel")]
+pub trait MaybeSendSync: Send + Sync {}
+#[cfg(feature = "parallel")]
+impl<T: Send + Sync + ?Sized> MaybeSendSync for T {}
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSendSync {}
+#[cfg(not(feature = "parallel"))]
+impl<T: ?Sized> MaybeSendSync for T {}
+
+/// `Sync` with the `parallel` feature, where components are evaluated from several threads, and
+/// no bound otherwise.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Sync {}
+#[cfg(feature = "parallel")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "parallel"))]
+impl<T: ?Sized> MaybeSync for T {}
+
 // TODO(alont): Consider removing the generic parameter and only support BaseField.
-pub trait Column<T>: Clone + Debug + FromIterator<T> {
+pub trait Column<T>: Clone + Debug + FromIterator<T> + MaybeSendSync {
     /// Creates a new column of zeros with the given length.
     fn zeros(len: usize) -> Self;
     /// Creates a new column of uninitialized values with the given length.
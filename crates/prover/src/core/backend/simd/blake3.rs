@@ -0,0 +1,34 @@
+use itertools::Itertools;
+
+use super::SimdBackend;
+use crate::core::backend::{Col, Column, ColumnOps};
+use crate::core::fields::m31::BaseField;
+use crate::core::vcs::blake3_hash::Blake3Hash;
+use crate::core::vcs::blake3_merkle::Blake3MerkleHasher;
+use crate::core::vcs::ops::{MerkleHasher, MerkleOps};
+
+impl ColumnOps<Blake3Hash> for SimdBackend {
+    type Column = Vec<Blake3Hash>;
+
+    fn bit_reverse_column(_column: &mut Self::Column) {
+        unimplemented!()
+    }
+}
+
+impl MerkleOps<Blake3MerkleHasher> for SimdBackend {
+    // TODO: replace with SIMD implementation.
+    fn commit_on_layer(
+        log_size: u32,
+        prev_layer: Option<&Vec<Blake3Hash>>,
+        columns: &[&Col<Self, BaseField>],
+    ) -> Vec<Blake3Hash> {
+        (0..(1 << log_size))
+            .map(|i| {
+                Blake3MerkleHasher::hash_node(
+                    prev_layer.map(|prev_layer| (prev_layer[2 * i], prev_layer[2 * i + 1])),
+                    &columns.iter().map(|column| column.at(i)).collect_vec(),
+                )
+            })
+            .collect()
+    }
+}
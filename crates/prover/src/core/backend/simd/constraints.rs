@@ -0,0 +1,67 @@
+use num_traits::One;
+
+use super::domain::PackedCirclePoint;
+use super::m31::PackedBaseField;
+use crate::core::circle::{CirclePoint, Coset};
+use crate::core::fields::m31::BaseField;
+
+/// Packed version of [`coset_vanishing`], evaluating on each lane of `p`.
+///
+/// [`coset_vanishing`]: crate::core::constraints::coset_vanishing
+pub fn coset_vanishing_simd(coset: Coset, p: PackedCirclePoint) -> PackedBaseField {
+    let shift = coset.step_size.half().to_point() - coset.initial;
+    let p = p + shift.into_ef();
+    let mut x = p.x;
+    for _ in 1..coset.log_size {
+        x = CirclePoint::double_x(x);
+    }
+    x
+}
+
+/// Packed version of [`point_excluder`], evaluating on each lane of `p`.
+///
+/// [`point_excluder`]: crate::core::constraints::point_excluder
+pub fn point_excluder_simd(
+    excluded: CirclePoint<BaseField>,
+    p: PackedCirclePoint,
+) -> PackedBaseField {
+    (p - excluded.into_ef()).x - PackedBaseField::one()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{coset_vanishing_simd, point_excluder_simd};
+    use crate::core::backend::simd::domain::PackedCirclePoint;
+    use crate::core::backend::simd::m31::N_LANES;
+    use crate::core::circle::{CirclePoint, CirclePointIndex, Coset};
+    use crate::core::constraints::{coset_vanishing, point_excluder};
+    use crate::core::fields::m31::BaseField;
+
+    #[test]
+    fn test_vanishing_simd_matches_scalar() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let cosets = [
+            Coset::half_odds(5),
+            Coset::odds(8),
+            Coset::new(CirclePointIndex::zero(), 1),
+            Coset::half_odds(10).conjugate(),
+        ];
+        for _ in 0..10 {
+            let points: [CirclePoint<BaseField>; N_LANES] = std::array::from_fn(|_| {
+                (CirclePointIndex::generator() * rng.gen::<usize>()).to_point()
+            });
+            let packed = PackedCirclePoint::from_array(points);
+            let excluded = (CirclePointIndex::generator() * rng.gen::<usize>()).to_point();
+
+            for coset in cosets {
+                let expected = points.map(|p| coset_vanishing(coset, p));
+                assert_eq!(coset_vanishing_simd(coset, packed).to_array(), expected);
+            }
+            let expected = points.map(|p| point_excluder(excluded, p));
+            assert_eq!(point_excluder_simd(excluded, packed).to_array(), expected);
+        }
+    }
+}
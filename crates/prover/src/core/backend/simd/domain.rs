@@ -1,11 +1,80 @@
 use std::simd::{simd_swizzle, u32x2, Simd};
 
-use super::m31::{PackedM31, LOG_N_LANES};
+use super::m31::{PackedM31, LOG_N_LANES, N_LANES};
 use crate::core::circle::{CirclePoint, M31_CIRCLE_LOG_ORDER};
 use crate::core::fields::m31::M31;
 use crate::core::poly::circle::CircleDomain;
 use crate::core::utils::bit_reverse_index;
 
+/// A circle point per SIMD lane.
+///
+/// Doubling, addition and scalar multiplication act on each lane, as on [`CirclePoint<M31>`].
+pub type PackedCirclePoint = CirclePoint<PackedM31>;
+
+impl PackedCirclePoint {
+    pub fn broadcast(point: CirclePoint<M31>) -> Self {
+        Self {
+            x: PackedM31::broadcast(point.x),
+            y: PackedM31::broadcast(point.y),
+        }
+    }
+
+    pub fn from_array(points: [CirclePoint<M31>; N_LANES]) -> Self {
+        Self {
+            x: PackedM31::from_array(points.map(|p| p.x)),
+            y: PackedM31::from_array(points.map(|p| p.y)),
+        }
+    }
+
+    pub fn to_array(self) -> [CirclePoint<M31>; N_LANES] {
+        let (xs, ys) = (self.x.to_array(), self.y.to_array());
+        std::array::from_fn(|i| CirclePoint { x: xs[i], y: ys[i] })
+    }
+}
+
+/// Iterates over the points of a [CircleDomain] in natural order, [N_LANES] points at a time.
+pub struct CircleDomainPackedIterator {
+    /// The number of packed points of the half coset, after which the conjugates are yielded.
+    n_half_coset_chunks: usize,
+    i: usize,
+    current: PackedCirclePoint,
+    step: PackedCirclePoint,
+    initial: PackedCirclePoint,
+}
+impl CircleDomainPackedIterator {
+    pub fn new(domain: CircleDomain) -> Self {
+        assert!(domain.half_coset.log_size() >= LOG_N_LANES);
+        let initial = PackedCirclePoint::from_array(std::array::from_fn(|i| domain.at(i)));
+        Self {
+            n_half_coset_chunks: domain.half_coset.size() >> LOG_N_LANES,
+            i: 0,
+            current: initial,
+            step: PackedCirclePoint::broadcast(domain.half_coset.step.mul(N_LANES as u128)),
+            initial,
+        }
+    }
+}
+impl Iterator for CircleDomainPackedIterator {
+    type Item = PackedCirclePoint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i == 2 * self.n_half_coset_chunks {
+            return None;
+        }
+        if self.i == self.n_half_coset_chunks {
+            // The second half of the domain is the conjugate of the half coset.
+            self.current = self.initial;
+        }
+        let res = match self.i < self.n_half_coset_chunks {
+            true => self.current,
+            false => self.current.conjugate(),
+        };
+        self.current = self.current + self.step;
+        self.i += 1;
+        Some(res)
+    }
+}
+
 pub struct CircleDomainBitRevIterator {
     domain: CircleDomain,
     i: usize,
@@ -84,3 +153,42 @@ fn test_circle_domain_bit_rev_iterator() {
         .collect::<Vec<_>>();
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn test_circle_domain_packed_iterator() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let log_size = rng.gen_range(LOG_N_LANES + 1..=10);
+        let initial_index = crate::core::circle::CirclePointIndex(rng.gen::<usize>()).reduce();
+        let domain =
+            CircleDomain::new(crate::core::circle::Coset::new(initial_index, log_size - 1));
+
+        let expected = domain
+            .iter_indices()
+            .map(|index| index.to_point())
+            .collect::<Vec<_>>();
+        let actual = CircleDomainPackedIterator::new(domain)
+            .flat_map(PackedCirclePoint::to_array)
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_packed_circle_point_ops() {
+    let points: [_; N_LANES] =
+        std::array::from_fn(|i| crate::core::circle::CirclePointIndex(i * 12345 + 7).to_point());
+    let other = crate::core::circle::CirclePointIndex(98765).to_point();
+    let packed = PackedCirclePoint::from_array(points);
+
+    assert_eq!(packed.double().to_array(), points.map(|p| p.double()));
+    assert_eq!(
+        (packed + PackedCirclePoint::broadcast(other)).to_array(),
+        points.map(|p| p + other)
+    );
+    assert_eq!(packed.mul(123456).to_array(), points.map(|p| p.mul(123456)));
+}
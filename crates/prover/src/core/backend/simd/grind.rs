@@ -9,9 +9,9 @@ use rayon::prelude::*;
 use super::blake2s::compress16;
 use super::SimdBackend;
 use crate::core::backend::simd::m31::N_LANES;
-use crate::core::channel::Blake2sChannel;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::core::channel::{Channel, Poseidon252Channel};
+use crate::core::channel::Poseidon252Channel;
+use crate::core::channel::{Blake2sChannel, Blake3Channel, Channel};
 use crate::core::proof_of_work::GrindOps;
 
 // Note: GRIND_LOW_BITS is a cap on how much extra time we need to wait for all threads to finish.
@@ -77,3 +77,19 @@ impl GrindOps<Poseidon252Channel> for SimdBackend {
         }
     }
 }
+
+// TODO: This is a naive implementation, a packed BLAKE3 compression would grind all the lanes at
+// once.
+impl GrindOps<Blake3Channel> for SimdBackend {
+    fn grind(channel: &Blake3Channel, pow_bits: u32) -> u64 {
+        let mut nonce = 0;
+        loop {
+            let mut channel = channel.clone();
+            channel.mix_u64(nonce);
+            if channel.trailing_zeros() >= pow_bits {
+                return nonce;
+            }
+            nonce += 1;
+        }
+    }
+}
@@ -2,10 +2,11 @@ use std::iter::Sum;
 use std::mem::transmute;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use std::ptr;
-use std::simd::cmp::SimdOrd;
-use std::simd::{u32x16, Simd, Swizzle};
+use std::simd::cmp::{SimdOrd, SimdPartialEq};
+use std::simd::{u32x16, Mask, Simd, Swizzle};
 
 use bytemuck::{Pod, Zeroable};
+use itertools::{zip_eq, Itertools};
 use num_traits::{One, Zero};
 use rand::distributions::{Distribution, Standard};
 
@@ -78,6 +79,27 @@ impl PackedM31 {
         self + self
     }
 
+    /// Returns a mask of the lanes that are zero, represented either as `0` or as `P`.
+    pub fn is_zero_mask(self) -> Mask<i32, N_LANES> {
+        self.reduce().0.simd_eq(Simd::splat(0))
+    }
+
+    /// Inverts all the non-zero lanes of `values` with a single inversion, using Montgomery's
+    /// trick (see [FieldExpOps::batch_inverse]). Zero lanes are left as zero.
+    pub fn batch_inverse_allow_zeros(values: &[Self]) -> Vec<Self> {
+        let zero_masks = values.iter().map(|v| v.is_zero_mask()).collect_vec();
+        let non_zero_values = zip_eq(values, &zero_masks)
+            .map(|(v, is_zero)| Self(is_zero.select(Simd::splat(1), v.0)))
+            .collect_vec();
+
+        let mut inverses = vec![Self::zero(); values.len()];
+        Self::batch_inverse(&non_zero_values, &mut inverses);
+        for (inverse, is_zero) in zip_eq(&mut inverses, zero_masks) {
+            *inverse = Self(is_zero.select(Simd::splat(0), inverse.0));
+        }
+        inverses
+    }
+
     pub const fn into_simd(self) -> Simd<u32, N_LANES> {
         self.0
     }
@@ -237,7 +259,7 @@ impl Zero for PackedM31 {
     }
 
     fn is_zero(&self) -> bool {
-        self.to_array().iter().all(M31::is_zero)
+        self.is_zero_mask().all()
     }
 }
 
@@ -575,13 +597,16 @@ pub(crate) fn _mul_doubled_simd(a: PackedM31, b_double: u32x16) -> PackedM31 {
 #[cfg(test)]
 mod tests {
     use std::array;
+    use std::simd::Simd;
 
     use aligned::{Aligned, A64};
+    use num_traits::Zero;
     use rand::rngs::SmallRng;
+    use rand::seq::SliceRandom;
     use rand::{Rng, SeedableRng};
 
     use super::PackedM31;
-    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::m31::{BaseField, P};
     use crate::core::fields::FieldExpOps;
 
     #[test]
@@ -663,4 +688,43 @@ mod tests {
 
         assert_eq!(res.to_array(), array::from_fn(|i| values[i].inverse()));
     }
+
+    #[test]
+    fn is_zero_mask_works() {
+        let lanes = array::from_fn(|i| [0, 1, P - 1, P][i % 4]);
+        let packed = unsafe { PackedM31::from_simd_unchecked(Simd::from_array(lanes)) };
+
+        let mask = packed.is_zero_mask();
+
+        assert_eq!(mask.to_array(), lanes.map(|v| v == 0 || v == P));
+    }
+
+    #[test]
+    fn batch_inverse_allow_zeros_matches_scalar_inverse() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let n_packed = rng.gen_range(0..10);
+            let values = (0..n_packed)
+                .map(|_| {
+                    let lanes = array::from_fn(|_| match rng.gen_bool(0.2) {
+                        // Zero may also be represented by `P`.
+                        true => *[0, P].choose(&mut rng).unwrap(),
+                        false => rng.gen_range(1..P),
+                    });
+                    unsafe { PackedM31::from_simd_unchecked(Simd::from_array(lanes)) }
+                })
+                .collect::<Vec<_>>();
+
+            let inverses = PackedM31::batch_inverse_allow_zeros(&values);
+
+            for (value, inverse) in values.iter().zip(inverses) {
+                for (v, inv) in value.to_array().into_iter().zip(inverse.to_array()) {
+                    match v.is_zero() {
+                        true => assert!(inv.is_zero()),
+                        false => assert_eq!(inv, v.inverse()),
+                    }
+                }
+            }
+        }
+    }
 }
@@ -2,15 +2,18 @@ use serde::{Deserialize, Serialize};
 
 use super::{Backend, BackendForChannel};
 use crate::core::vcs::blake2_merkle::Blake2sMerkleChannel;
+use crate::core::vcs::blake3_merkle::Blake3MerkleChannel;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::core::vcs::poseidon252_merkle::Poseidon252MerkleChannel;
 
 pub mod accumulation;
 pub mod bit_reverse;
 pub mod blake2s;
+pub mod blake3;
 pub mod circle;
 pub mod cm31;
 pub mod column;
+pub mod constraints;
 pub mod conversion;
 pub mod domain;
 pub mod fft;
@@ -18,6 +21,7 @@ pub mod fri;
 mod grind;
 pub mod lookups;
 pub mod m31;
+pub mod poseidon2;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod poseidon252;
 pub mod prefix_sum;
@@ -31,5 +35,6 @@ pub struct SimdBackend;
 
 impl Backend for SimdBackend {}
 impl BackendForChannel<Blake2sMerkleChannel> for SimdBackend {}
+impl BackendForChannel<Blake3MerkleChannel> for SimdBackend {}
 #[cfg(not(target_arch = "wasm32"))]
 impl BackendForChannel<Poseidon252MerkleChannel> for SimdBackend {}
@@ -170,6 +170,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_draw_secure_felts_matches_draw_felt() {
+        let mut channel = Blake2sChannel::default();
+        channel.mix_u64(7);
+        let mut expected_channel = channel.clone();
+
+        let felts = channel.draw_secure_felts(5);
+
+        let expected_felts: Vec<_> = (0..5).map(|_| expected_channel.draw_felt()).collect();
+        assert_eq!(felts, expected_felts);
+        assert_eq!(channel.digest(), expected_channel.digest());
+    }
+
+    #[test]
+    fn test_mix_u64s_matches_sequential_mix_u64() {
+        let mut channel = Blake2sChannel::default();
+        let mut expected_channel = Blake2sChannel::default();
+
+        channel.mix_u64s(&[3, u64::MAX, 0]);
+        expected_channel.mix_u64(3);
+        expected_channel.mix_u64(u64::MAX);
+        expected_channel.mix_u64(0);
+
+        assert_eq!(channel.digest(), expected_channel.digest());
+    }
+
+    #[test]
+    fn test_mix_felts_binds_order() {
+        let [a, b] = [m31!(1), m31!(2)].map(SecureField::from);
+        let mut channel = Blake2sChannel::default();
+        let mut swapped_channel = Blake2sChannel::default();
+        let mut sequential_channel = Blake2sChannel::default();
+
+        channel.mix_felts(&[a, b]);
+        swapped_channel.mix_felts(&[b, a]);
+        sequential_channel.mix_felts(&[a]);
+        sequential_channel.mix_felts(&[b]);
+
+        assert_ne!(channel.digest(), swapped_channel.digest());
+        assert_ne!(channel.digest(), sequential_channel.digest());
+        assert_eq!(channel.channel_time.n_challenges, 1);
+    }
+
     #[test]
     pub fn test_mix_felts() {
         let mut channel = Blake2sChannel::default();
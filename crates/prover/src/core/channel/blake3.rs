@@ -0,0 +1,175 @@
+use std::iter;
+
+use super::blake2s::{BLAKE_BYTES_PER_HASH, FELTS_PER_HASH};
+use super::{Channel, ChannelTime};
+use crate::core::fields::m31::{BaseField, N_BYTES_FELT, P};
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::fields::IntoSlice;
+use crate::core::vcs::blake3_hash::{Blake3Hash, Blake3Hasher};
+
+/// A channel that can be used to draw random elements from a [Blake3Hash] digest.
+///
+/// Follows the transcript of [`Blake2sChannel`](super::Blake2sChannel), with BLAKE3 as the hash.
+/// Random bytes are squeezed from the extendable output of the digest.
+#[derive(Default, Clone)]
+pub struct Blake3Channel {
+    digest: Blake3Hash,
+    pub channel_time: ChannelTime,
+}
+
+impl Blake3Channel {
+    pub const fn digest(&self) -> Blake3Hash {
+        self.digest
+    }
+    pub fn update_digest(&mut self, new_digest: Blake3Hash) {
+        self.digest = new_digest;
+        self.channel_time.inc_challenges();
+    }
+    /// Generates a uniform random vector of BaseField elements.
+    fn draw_base_felts(&mut self) -> [BaseField; FELTS_PER_HASH] {
+        // Repeats drawing until getting a good result.
+        // Retry probability for each round is ~ 2^(-28).
+        loop {
+            let u32s: [u32; FELTS_PER_HASH] = self
+                .draw_random_bytes()
+                .chunks_exact(N_BYTES_FELT) // 4 bytes per u32.
+                .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap();
+
+            // Retry if not all the u32 are in the range [0, 2P).
+            if u32s.iter().all(|x| *x < 2 * P) {
+                return u32s.map(|x| BaseField::reduce(x as u64));
+            }
+        }
+    }
+}
+
+impl Channel for Blake3Channel {
+    const BYTES_PER_HASH: usize = BLAKE_BYTES_PER_HASH;
+
+    fn trailing_zeros(&self) -> u32 {
+        u128::from_le_bytes(std::array::from_fn(|i| self.digest.as_ref()[i])).trailing_zeros()
+    }
+
+    fn mix_felts(&mut self, felts: &[SecureField]) {
+        let mut hasher = Blake3Hasher::new();
+        hasher.update(self.digest.as_ref());
+        hasher.update(IntoSlice::<u8>::into_slice(felts));
+
+        self.update_digest(hasher.finalize());
+    }
+
+    fn mix_u64(&mut self, value: u64) {
+        let mut hasher = Blake3Hasher::new();
+        hasher.update(self.digest.as_ref());
+        hasher.update(&value.to_le_bytes());
+
+        self.update_digest(hasher.finalize());
+    }
+
+    fn draw_felt(&mut self) -> SecureField {
+        let felts: [BaseField; FELTS_PER_HASH] = self.draw_base_felts();
+        SecureField::from_m31_array(felts[..SECURE_EXTENSION_DEGREE].try_into().unwrap())
+    }
+
+    fn draw_felts(&mut self, n_felts: usize) -> Vec<SecureField> {
+        let mut felts = iter::from_fn(|| Some(self.draw_base_felts())).flatten();
+        let secure_felts = iter::from_fn(|| {
+            Some(SecureField::from_m31_array([
+                felts.next()?,
+                felts.next()?,
+                felts.next()?,
+                felts.next()?,
+            ]))
+        });
+        secure_felts.take(n_felts).collect()
+    }
+
+    fn draw_random_bytes(&mut self) -> Vec<u8> {
+        // The `n_sent`-th block of the extendable output of the digest.
+        let mut output = blake3::Hasher::new()
+            .update(self.digest.as_ref())
+            .finalize_xof();
+        output.set_position((self.channel_time.n_sent * BLAKE_BYTES_PER_HASH) as u64);
+        let mut bytes = vec![0; BLAKE_BYTES_PER_HASH];
+        output.fill(&mut bytes);
+
+        self.channel_time.inc_sent();
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::core::channel::blake3::Blake3Channel;
+    use crate::core::channel::{Blake2sChannel, Channel};
+    use crate::core::fields::qm31::SecureField;
+    use crate::m31;
+
+    /// Runs the same transcript on a channel, returning the drawn values.
+    fn transcript<C: Channel>(channel: &mut C) -> (Vec<u8>, Vec<SecureField>) {
+        channel.mix_u64(7);
+        channel.mix_felts(&[SecureField::from(m31!(1923782)), SecureField::from(5)]);
+        let bytes = channel.draw_random_bytes();
+        let mut felts = vec![channel.draw_felt()];
+        felts.extend(channel.draw_felts(5));
+        (bytes, felts)
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let mut channel = Blake3Channel::default();
+        let mut other_channel = Blake3Channel::default();
+
+        assert_eq!(transcript(&mut channel), transcript(&mut other_channel));
+        assert_eq!(channel.digest(), other_channel.digest());
+    }
+
+    #[test]
+    fn test_transcript_shape_matches_blake2s() {
+        let mut channel = Blake3Channel::default();
+        let mut blake2s_channel = Blake2sChannel::default();
+
+        let (bytes, felts) = transcript(&mut channel);
+        let (blake2s_bytes, blake2s_felts) = transcript(&mut blake2s_channel);
+
+        assert_eq!(bytes.len(), blake2s_bytes.len());
+        assert_eq!(felts.len(), blake2s_felts.len());
+        assert_ne!(felts, blake2s_felts);
+        assert_eq!(
+            channel.channel_time.n_challenges,
+            blake2s_channel.channel_time.n_challenges
+        );
+    }
+
+    #[test]
+    fn test_channel_time() {
+        let mut channel = Blake3Channel::default();
+
+        channel.draw_random_bytes();
+        assert_eq!(channel.channel_time.n_challenges, 0);
+
+        channel.mix_u64(1);
+        assert_eq!(channel.channel_time.n_challenges, 1);
+    }
+
+    #[test]
+    fn test_draw_felts() {
+        let mut channel = Blake3Channel::default();
+
+        let first_random_bytes = channel.draw_random_bytes();
+        assert_ne!(first_random_bytes, channel.draw_random_bytes());
+
+        let mut random_felts = channel.draw_felts(5);
+        random_felts.extend(channel.draw_felts(4));
+        assert_eq!(
+            random_felts.len(),
+            random_felts.iter().collect::<BTreeSet<_>>().len()
+        );
+    }
+}
@@ -9,6 +9,17 @@ pub use poseidon252::Poseidon252Channel;
 mod blake2s;
 pub use blake2s::Blake2sChannel;
 
+mod blake3;
+pub use self::blake3::Blake3Channel;
+
+mod poseidon2;
+pub use poseidon2::Poseidon2Channel;
+
+mod script;
+pub use script::{
+    ChannelDraw, ChannelInput, ChannelOp, ChannelScript, ChannelScriptError, ChannelScriptRunner,
+};
+
 pub const EXTENSION_FELTS_PER_HASH: usize = 2;
 
 #[derive(Clone, Default)]
@@ -34,13 +45,42 @@ pub trait Channel: Default + Clone {
     fn trailing_zeros(&self) -> u32;
 
     // Mix functions.
+    /// Mixes `felts`, in order, into the channel with a single digest update.
+    ///
+    /// This binds the whole slice at once, so it differs from mixing the elements one at a time.
     fn mix_felts(&mut self, felts: &[SecureField]);
     fn mix_u64(&mut self, value: u64);
+    /// Mixes `values` by calling [Self::mix_u64] on each of them, in order.
+    fn mix_u64s(&mut self, values: &[u64]) {
+        for &value in values {
+            self.mix_u64(value);
+        }
+    }
+    /// Mixes the domain separator `tag` into the channel, so that the values drawn afterwards
+    /// differ from those drawn after the same operations under any other tag.
+    ///
+    /// The tag is mixed with its length, as little-endian `u64` words, so different tags never
+    /// mix the same words.
+    fn with_domain(&mut self, tag: &[u8]) {
+        self.mix_u64(tag.len() as u64);
+        for word in tag.chunks(8) {
+            let mut bytes = [0; 8];
+            bytes[..word.len()].copy_from_slice(word);
+            self.mix_u64(u64::from_le_bytes(bytes));
+        }
+    }
 
     // Draw functions.
     fn draw_felt(&mut self) -> SecureField;
     /// Generates a uniform random vector of SecureField elements.
     fn draw_felts(&mut self, n_felts: usize) -> Vec<SecureField>;
+    /// Draws `n_felts` elements by calling [Self::draw_felt] `n_felts` times.
+    ///
+    /// Unlike [Self::draw_felts], the drawn sequence is the same as drawing each element
+    /// separately.
+    fn draw_secure_felts(&mut self, n_felts: usize) -> Vec<SecureField> {
+        (0..n_felts).map(|_| self.draw_felt()).collect()
+    }
     /// Returns a vector of random bytes of length `BYTES_PER_HASH`.
     fn draw_random_bytes(&mut self) -> Vec<u8>;
 }
@@ -50,3 +90,42 @@ pub trait MerkleChannel: Default {
     type H: MerkleHasher;
     fn mix_root(channel: &mut Self::C, root: <Self::H as MerkleHasher>::Hash);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Blake2sChannel, Blake3Channel, Channel, Poseidon2Channel};
+    use crate::core::fields::qm31::SecureField;
+
+    fn draws_under_domain<C: Channel>(tag: &[u8]) -> Vec<SecureField> {
+        let mut channel = C::default();
+        channel.mix_u64(7);
+        channel.with_domain(tag);
+        channel.mix_u64(42);
+        channel.draw_felts(3)
+    }
+
+    fn assert_domains_separate<C: Channel>() {
+        assert_eq!(
+            draws_under_domain::<C>(b"fri"),
+            draws_under_domain::<C>(b"fri")
+        );
+        assert_ne!(
+            draws_under_domain::<C>(b"fri"),
+            draws_under_domain::<C>(b"lookup")
+        );
+        // Tags that only differ in trailing zeros are still separated by their lengths.
+        assert_ne!(
+            draws_under_domain::<C>(b"gkr"),
+            draws_under_domain::<C>(b"gkr\0")
+        );
+    }
+
+    #[test]
+    fn test_with_domain() {
+        assert_domains_separate::<Blake2sChannel>();
+        assert_domains_separate::<Blake3Channel>();
+        assert_domains_separate::<Poseidon2Channel>();
+        #[cfg(not(target_arch = "wasm32"))]
+        assert_domains_separate::<super::Poseidon252Channel>();
+    }
+}
@@ -0,0 +1,193 @@
+use std::iter;
+
+use super::{Channel, ChannelTime};
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
+use crate::core::vcs::ops::MerkleHasher;
+use crate::core::vcs::poseidon2_hash::{
+    Poseidon2Hash, Poseidon2M31MerkleHasher, POSEIDON2_DIGEST_SIZE,
+};
+
+/// Number of bytes taken from the low bits of each drawn [BaseField] element, which are close to
+/// uniform.
+const BYTES_PER_BASE_FELT: usize = 3;
+pub const POSEIDON2_BYTES_PER_HASH: usize = BYTES_PER_BASE_FELT * POSEIDON2_DIGEST_SIZE;
+/// Number of bits of each [BaseField] element used by [Poseidon2Channel::trailing_zeros].
+const BITS_PER_BASE_FELT: u32 = 31;
+
+/// A channel that can be used to draw random elements from a [Poseidon2Hash] digest.
+///
+/// Values are absorbed and challenges are squeezed as [BaseField] elements, which makes the
+/// transcript cheap to replay inside an M31 circuit, e.g. for recursive verification.
+#[derive(Default, Clone)]
+pub struct Poseidon2Channel {
+    digest: Poseidon2Hash,
+    pub channel_time: ChannelTime,
+}
+
+impl Poseidon2Channel {
+    pub const fn digest(&self) -> Poseidon2Hash {
+        self.digest
+    }
+    pub fn update_digest(&mut self, new_digest: Poseidon2Hash) {
+        self.digest = new_digest;
+        self.channel_time.inc_challenges();
+    }
+
+    /// Hashes the digest followed by `values`.
+    fn hash_with_digest(&self, values: impl IntoIterator<Item = BaseField>) -> Poseidon2Hash {
+        let input = self.digest.0.into_iter().chain(values).collect::<Vec<_>>();
+        Poseidon2M31MerkleHasher::hash_node(None, &input)
+    }
+
+    /// Generates a uniform random vector of BaseField elements.
+    fn draw_base_felts(&mut self) -> [BaseField; POSEIDON2_DIGEST_SIZE] {
+        let counter = self.channel_time.n_sent as u64;
+        self.channel_time.inc_sent();
+        self.hash_with_digest(u64_limbs(counter)).0
+    }
+}
+
+/// Splits `value` into 16-bit limbs, which are all valid [BaseField] elements.
+fn u64_limbs(value: u64) -> [BaseField; 4] {
+    std::array::from_fn(|i| BaseField::from_u32_unchecked((value >> (16 * i)) as u32 & 0xffff))
+}
+
+impl Channel for Poseidon2Channel {
+    const BYTES_PER_HASH: usize = POSEIDON2_BYTES_PER_HASH;
+
+    fn trailing_zeros(&self) -> u32 {
+        self.digest.0[..4]
+            .iter()
+            .rev()
+            .fold(0u128, |acc, felt| {
+                (acc << BITS_PER_BASE_FELT) | felt.0 as u128
+            })
+            .trailing_zeros()
+    }
+
+    fn mix_felts(&mut self, felts: &[SecureField]) {
+        let digest = self.hash_with_digest(felts.iter().flat_map(|felt| felt.to_m31_array()));
+        self.update_digest(digest);
+    }
+
+    fn mix_u64(&mut self, value: u64) {
+        let digest = self.hash_with_digest(u64_limbs(value));
+        self.update_digest(digest);
+    }
+
+    fn draw_felt(&mut self) -> SecureField {
+        let felts = self.draw_base_felts();
+        SecureField::from_m31_array(felts[..SECURE_EXTENSION_DEGREE].try_into().unwrap())
+    }
+
+    fn draw_felts(&mut self, n_felts: usize) -> Vec<SecureField> {
+        let mut felts = iter::from_fn(|| Some(self.draw_base_felts())).flatten();
+        let secure_felts = iter::from_fn(|| {
+            Some(SecureField::from_m31_array([
+                felts.next()?,
+                felts.next()?,
+                felts.next()?,
+                felts.next()?,
+            ]))
+        });
+        secure_felts.take(n_felts).collect()
+    }
+
+    fn draw_random_bytes(&mut self) -> Vec<u8> {
+        self.draw_base_felts()
+            .into_iter()
+            .flat_map(|felt| felt.0.to_le_bytes().into_iter().take(BYTES_PER_BASE_FELT))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::constraint_framework::relation;
+    use crate::core::channel::poseidon2::Poseidon2Channel;
+    use crate::core::channel::Channel;
+    use crate::core::fields::qm31::SecureField;
+    use crate::m31;
+
+    relation!(TestRelation, 2);
+
+    #[test]
+    fn test_channel_time() {
+        let mut channel = Poseidon2Channel::default();
+
+        channel.draw_random_bytes();
+        assert_eq!(channel.channel_time.n_challenges, 0);
+        assert_eq!(channel.channel_time.n_sent, 1);
+
+        channel.draw_felts(9);
+        assert_eq!(channel.channel_time.n_challenges, 0);
+        assert_eq!(channel.channel_time.n_sent, 6);
+
+        channel.mix_u64(1);
+        assert_eq!(channel.channel_time.n_challenges, 1);
+        assert_eq!(channel.channel_time.n_sent, 0);
+    }
+
+    #[test]
+    fn test_draw_random_bytes() {
+        let mut channel = Poseidon2Channel::default();
+
+        let first_random_bytes = channel.draw_random_bytes();
+
+        assert_eq!(first_random_bytes.len(), Poseidon2Channel::BYTES_PER_HASH);
+        assert_ne!(first_random_bytes, channel.draw_random_bytes());
+    }
+
+    #[test]
+    pub fn test_draw_felts() {
+        let mut channel = Poseidon2Channel::default();
+
+        let mut random_felts = channel.draw_felts(5);
+        random_felts.extend(channel.draw_felts(4));
+
+        // Assert that all the random felts are unique.
+        assert_eq!(
+            random_felts.len(),
+            random_felts.iter().collect::<BTreeSet<_>>().len()
+        );
+    }
+
+    #[test]
+    pub fn test_mix_felts() {
+        let mut channel = Poseidon2Channel::default();
+        let initial_digest = channel.digest;
+        let felts: Vec<SecureField> = (0..2)
+            .map(|i| SecureField::from(m31!(i + 1923782)))
+            .collect();
+
+        channel.mix_felts(felts.as_slice());
+
+        assert_ne!(initial_digest, channel.digest);
+    }
+
+    #[test]
+    fn test_same_transcript_draws_same_elements() {
+        let transcript = |channel: &mut Poseidon2Channel| {
+            channel.mix_felts(&[SecureField::from(m31!(5)), SecureField::from(m31!(7))]);
+            channel.mix_u64(1234);
+            channel.draw_felts(3)
+        };
+        let mut first_channel = Poseidon2Channel::default();
+        let mut second_channel = Poseidon2Channel::default();
+        let mut other_channel = Poseidon2Channel::default();
+        other_channel.mix_u64(1);
+
+        let draws = transcript(&mut first_channel);
+
+        assert_eq!(draws, transcript(&mut second_channel));
+        assert_ne!(draws, transcript(&mut other_channel));
+        assert_eq!(
+            TestRelation::draw(&mut first_channel),
+            TestRelation::draw(&mut second_channel)
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use std::marker::PhantomData;
+
+use thiserror::Error;
+
+use super::{Channel, MerkleChannel};
+use crate::core::circle::CirclePoint;
+use crate::core::fields::qm31::SecureField;
+use crate::core::vcs::ops::MerkleHasher;
+
+/// A single Fiat-Shamir operation on a channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelOp {
+    /// Mixes a Merkle root, see [MerkleChannel::mix_root].
+    MixRoot,
+    /// Mixes the given number of [SecureField] elements.
+    MixFelts(usize),
+    MixU64,
+    /// Draws the given number of [SecureField] challenges.
+    DrawFelts(usize),
+    /// Draws a random [CirclePoint] over the [SecureField], e.g. an OODS point.
+    DrawPoint,
+}
+
+/// A value mixed into the channel by a [ChannelOp].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChannelInput<H: MerkleHasher> {
+    Root(H::Hash),
+    Felts(Vec<SecureField>),
+    U64(u64),
+}
+
+/// A value drawn from the channel by a [ChannelOp].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChannelDraw {
+    Felts(Vec<SecureField>),
+    Point(CirclePoint<SecureField>),
+}
+
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum ChannelScriptError {
+    #[error("Operation {index} ({op:?}) got a mismatching input.")]
+    InputMismatch { index: usize, op: ChannelOp },
+    #[error("Operation {index} ({op:?}) is missing an input.")]
+    MissingInput { index: usize, op: ChannelOp },
+    #[error("Operation {index} ({op:?}) draws a different value.")]
+    DrawMismatch { index: usize, op: ChannelOp },
+    #[error("Operation {index} ({op:?}) was not run.")]
+    Unfinished { index: usize, op: ChannelOp },
+    #[error("Too many inputs were given to the script.")]
+    TooManyInputs,
+    #[error("The script has no operations left.")]
+    Finished,
+}
+
+/// A declarative Fiat-Shamir transcript: the ordered sequence of mix and draw operations an AIR
+/// requires.
+///
+/// Both the prover and the verifier run the same script on their channels, each supplying the
+/// values it mixes, which guarantees that they draw identical challenges.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChannelScript {
+    pub ops: Vec<ChannelOp>,
+}
+
+impl ChannelScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mix_root(mut self) -> Self {
+        self.ops.push(ChannelOp::MixRoot);
+        self
+    }
+
+    pub fn mix_felts(mut self, n_felts: usize) -> Self {
+        self.ops.push(ChannelOp::MixFelts(n_felts));
+        self
+    }
+
+    pub fn mix_u64(mut self) -> Self {
+        self.ops.push(ChannelOp::MixU64);
+        self
+    }
+
+    pub fn draw_felts(mut self, n_felts: usize) -> Self {
+        self.ops.push(ChannelOp::DrawFelts(n_felts));
+        self
+    }
+
+    pub fn draw_point(mut self) -> Self {
+        self.ops.push(ChannelOp::DrawPoint);
+        self
+    }
+
+    /// Returns a runner that runs the script one operation at a time, for protocols where the
+    /// mixed values depend on earlier draws.
+    pub fn runner<MC: MerkleChannel>(&self) -> ChannelScriptRunner<'_, MC> {
+        ChannelScriptRunner {
+            ops: &self.ops,
+            index: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Runs the script on `channel`.
+    ///
+    /// `inputs` are consumed by the mix operations, in order. Returns the values drawn by the draw
+    /// operations, in order.
+    pub fn run<MC: MerkleChannel>(
+        &self,
+        channel: &mut MC::C,
+        inputs: impl IntoIterator<Item = ChannelInput<MC::H>>,
+    ) -> Result<Vec<ChannelDraw>, ChannelScriptError> {
+        let mut inputs = inputs.into_iter();
+        let mut runner = self.runner::<MC>();
+        let mut draws = vec![];
+        while let Some((index, op)) = runner.peek() {
+            match op {
+                ChannelOp::MixRoot | ChannelOp::MixFelts(_) | ChannelOp::MixU64 => {
+                    let input = inputs
+                        .next()
+                        .ok_or(ChannelScriptError::MissingInput { index, op })?;
+                    runner.mix(channel, input)?;
+                }
+                ChannelOp::DrawFelts(_) | ChannelOp::DrawPoint => draws.push(runner.draw(channel)?),
+            }
+        }
+        if inputs.next().is_some() {
+            return Err(ChannelScriptError::TooManyInputs);
+        }
+        Ok(draws)
+    }
+}
+
+/// Runs the operations of a [ChannelScript] in order, see [`ChannelScript::runner()`].
+///
+/// Each call runs the next operation of the script, and fails if the operation is not of the
+/// called kind. [`Self::finish()`] checks that the whole script was run.
+pub struct ChannelScriptRunner<'a, MC: MerkleChannel> {
+    ops: &'a [ChannelOp],
+    index: usize,
+    phantom: PhantomData<MC>,
+}
+
+impl<'a, MC: MerkleChannel> ChannelScriptRunner<'a, MC> {
+    /// Returns the next operation and its index in the script, if any.
+    fn peek(&self) -> Option<(usize, ChannelOp)> {
+        self.ops.get(self.index).map(|&op| (self.index, op))
+    }
+
+    /// Runs the next operation, which must mix `input`.
+    pub fn mix(
+        &mut self,
+        channel: &mut MC::C,
+        input: ChannelInput<MC::H>,
+    ) -> Result<(), ChannelScriptError> {
+        let (index, op) = self.peek().ok_or(ChannelScriptError::TooManyInputs)?;
+        match (op, input) {
+            (ChannelOp::MixRoot, ChannelInput::Root(root)) => MC::mix_root(channel, root),
+            (ChannelOp::MixFelts(n_felts), ChannelInput::Felts(felts))
+                if felts.len() == n_felts =>
+            {
+                channel.mix_felts(&felts)
+            }
+            (ChannelOp::MixU64, ChannelInput::U64(value)) => channel.mix_u64(value),
+            _ => return Err(ChannelScriptError::InputMismatch { index, op }),
+        }
+        self.index += 1;
+        Ok(())
+    }
+
+    /// Runs the next operation, which must be a draw, and returns the drawn value.
+    pub fn draw(&mut self, channel: &mut MC::C) -> Result<ChannelDraw, ChannelScriptError> {
+        let (index, op) = self.peek().ok_or(ChannelScriptError::Finished)?;
+        let draw = match op {
+            ChannelOp::DrawFelts(n_felts) => ChannelDraw::Felts(channel.draw_felts(n_felts)),
+            ChannelOp::DrawPoint => ChannelDraw::Point(CirclePoint::get_random_point(channel)),
+            _ => return Err(ChannelScriptError::MissingInput { index, op }),
+        };
+        self.index += 1;
+        Ok(draw)
+    }
+
+    /// Runs the next operation, which must draw [SecureField] challenges, and returns them.
+    pub fn draw_felts(
+        &mut self,
+        channel: &mut MC::C,
+    ) -> Result<Vec<SecureField>, ChannelScriptError> {
+        if let Some((index, op @ ChannelOp::DrawPoint)) = self.peek() {
+            return Err(ChannelScriptError::DrawMismatch { index, op });
+        }
+        match self.draw(channel)? {
+            ChannelDraw::Felts(felts) => Ok(felts),
+            ChannelDraw::Point(_) => unreachable!(),
+        }
+    }
+
+    /// Runs the next operation, which must draw a [CirclePoint], and returns it.
+    pub fn draw_point(
+        &mut self,
+        channel: &mut MC::C,
+    ) -> Result<CirclePoint<SecureField>, ChannelScriptError> {
+        if let Some((index, op @ ChannelOp::DrawFelts(_))) = self.peek() {
+            return Err(ChannelScriptError::DrawMismatch { index, op });
+        }
+        match self.draw(channel)? {
+            ChannelDraw::Point(point) => Ok(point),
+            ChannelDraw::Felts(_) => unreachable!(),
+        }
+    }
+
+    /// Checks that every operation of the script was run.
+    pub fn finish(self) -> Result<(), ChannelScriptError> {
+        match self.peek() {
+            Some((index, op)) => Err(ChannelScriptError::Unfinished { index, op }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelDraw, ChannelInput, ChannelOp, ChannelScript, ChannelScriptError};
+    use crate::core::channel::Blake2sChannel;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::vcs::blake2_hash::Blake2sHash;
+    use crate::core::vcs::blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher};
+    use crate::qm31;
+
+    fn script() -> ChannelScript {
+        ChannelScript::new()
+            .mix_root()
+            .draw_felts(2)
+            .mix_felts(1)
+            .mix_u64()
+            .draw_point()
+    }
+
+    fn inputs() -> Vec<ChannelInput<Blake2sMerkleHasher>> {
+        vec![
+            ChannelInput::Root(Blake2sHash([7; 32])),
+            ChannelInput::Felts(vec![qm31!(1, 2, 3, 4)]),
+            ChannelInput::U64(5),
+        ]
+    }
+
+    #[test]
+    fn test_prover_and_verifier_draws_match() {
+        let script = script();
+        let mut prover_channel = Blake2sChannel::default();
+        let mut verifier_channel = Blake2sChannel::default();
+
+        let prover_draws = script
+            .run::<Blake2sMerkleChannel>(&mut prover_channel, inputs())
+            .unwrap();
+        let verifier_draws = script
+            .run::<Blake2sMerkleChannel>(&mut verifier_channel, inputs())
+            .unwrap();
+
+        assert_eq!(prover_draws.len(), 2);
+        assert_eq!(prover_draws, verifier_draws);
+        assert_eq!(prover_channel.digest(), verifier_channel.digest());
+    }
+
+    #[test]
+    fn test_script_input_mismatch() {
+        let mut channel = Blake2sChannel::default();
+        let mut inputs = inputs();
+        inputs[1] = ChannelInput::Felts(vec![SecureField::default(); 2]);
+
+        assert_eq!(
+            script()
+                .run::<Blake2sMerkleChannel>(&mut channel, inputs)
+                .unwrap_err(),
+            ChannelScriptError::InputMismatch {
+                index: 2,
+                op: ChannelOp::MixFelts(1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_runner_matches_run() {
+        let script = script();
+        let mut run_channel = Blake2sChannel::default();
+        let mut runner_channel = Blake2sChannel::default();
+        let [root, felts, value]: [_; 3] = inputs().try_into().unwrap();
+
+        let draws = script
+            .run::<Blake2sMerkleChannel>(&mut run_channel, inputs())
+            .unwrap();
+        let mut runner = script.runner::<Blake2sMerkleChannel>();
+        runner.mix(&mut runner_channel, root).unwrap();
+        let alphas = runner.draw_felts(&mut runner_channel).unwrap();
+        runner.mix(&mut runner_channel, felts).unwrap();
+        runner.mix(&mut runner_channel, value).unwrap();
+        let point = runner.draw_point(&mut runner_channel).unwrap();
+        runner.finish().unwrap();
+
+        assert_eq!(
+            draws,
+            [ChannelDraw::Felts(alphas), ChannelDraw::Point(point)]
+        );
+        assert_eq!(run_channel.digest(), runner_channel.digest());
+    }
+
+    #[test]
+    fn test_runner_out_of_order() {
+        let channel = &mut Blake2sChannel::default();
+        let root = inputs().remove(0);
+        let script = script();
+        let mut runner = script.runner::<Blake2sMerkleChannel>();
+
+        assert_eq!(
+            runner.draw(channel).unwrap_err(),
+            ChannelScriptError::MissingInput {
+                index: 0,
+                op: ChannelOp::MixRoot
+            }
+        );
+        runner.mix(channel, root).unwrap();
+        assert_eq!(
+            runner.draw_point(channel).unwrap_err(),
+            ChannelScriptError::DrawMismatch {
+                index: 1,
+                op: ChannelOp::DrawFelts(2)
+            }
+        );
+        runner.draw_felts(channel).unwrap();
+        assert_eq!(
+            runner.finish().unwrap_err(),
+            ChannelScriptError::Unfinished {
+                index: 2,
+                op: ChannelOp::MixFelts(1)
+            }
+        );
+    }
+}
@@ -244,6 +244,27 @@ impl CirclePointIndex {
         M31_CIRCLE_GEN.mul(self.0 as u128)
     }
 
+    /// Returns the index of `point`, the inverse of [Self::to_point].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `point` is not on the circle.
+    pub fn from_point(point: CirclePoint<M31>) -> Self {
+        // Recovers the bits of the index from the least significant one. Once the bits below `bit`
+        // are removed, the rest is a multiple of `2^bit`, and `bit` is set if and only if the rest
+        // doesn't vanish after `M31_CIRCLE_LOG_ORDER - 1 - bit` doublings.
+        let mut index = Self::zero();
+        let mut rest = point;
+        for bit in 0..M31_CIRCLE_LOG_ORDER {
+            if rest.repeated_double(M31_CIRCLE_LOG_ORDER - 1 - bit) != CirclePoint::zero() {
+                index = index + Self(1 << bit);
+                rest = rest - Self(1 << bit).to_point();
+            }
+        }
+        assert_eq!(rest, CirclePoint::zero(), "Point is not on the circle");
+        index
+    }
+
     pub fn half(self) -> Self {
         assert!(self.0 & 1 == 0);
         Self(self.0 >> 1)
@@ -301,7 +322,7 @@ impl Neg for CirclePointIndex {
 }
 
 /// Represents the coset initial + \<step\>.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug)]
 pub struct Coset {
     pub initial_index: CirclePointIndex,
     pub initial: CirclePoint<M31>,
@@ -323,6 +344,30 @@ impl Coset {
         }
     }
 
+    /// Creates the coset `initial + <step>` of size `2^log_size`, whose point at `i` is
+    /// `initial + i * step`.
+    ///
+    /// The caller is responsible for the points forming a valid coset, i.e. `step` must generate
+    /// the subgroup of size `2^log_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial` or `step` is not on the circle.
+    pub fn from_initial_step(
+        initial: CirclePoint<M31>,
+        step: CirclePoint<M31>,
+        log_size: u32,
+    ) -> Self {
+        assert!(log_size <= M31_CIRCLE_LOG_ORDER);
+        Self {
+            initial_index: CirclePointIndex::from_point(initial),
+            initial,
+            step_size: CirclePointIndex::from_point(step),
+            step,
+            log_size,
+        }
+    }
+
     /// Creates a coset of the form <G_n>.
     /// For example, for n=8, we get the point indices \[0,1,2,3,4,5,6,7\].
     pub fn subgroup(log_size: u32) -> Self {
@@ -397,10 +442,17 @@ impl Coset {
         self.initial_index + self.step_size.mul(index)
     }
 
+    /// Returns the point at `index` in the coset.
+    /// The index wraps modulo the coset size, i.e. `at(size())` is `at(0)`.
     pub fn at(&self, index: usize) -> CirclePoint<M31> {
         self.index_at(index).to_point()
     }
 
+    /// Returns the point at `index` in the coset, or `None` if `index` is out of range.
+    pub fn at_checked(&self, index: usize) -> Option<CirclePoint<M31>> {
+        (index < self.size()).then(|| self.at(index))
+    }
+
     pub fn shift(&self, shift_size: CirclePointIndex) -> Self {
         let initial_index = self.initial_index + shift_size;
         Self {
@@ -429,6 +481,20 @@ impl Coset {
     }
 }
 
+/// Two cosets are equal iff they yield the same points in the same order.
+///
+/// The point indices are not compared, as different indices may represent the same point, and the
+/// step is ignored for cosets of a single point.
+impl PartialEq for Coset {
+    fn eq(&self, other: &Self) -> bool {
+        self.log_size == other.log_size
+            && self.initial == other.initial
+            && (self.log_size == 0 || self.step == other.step)
+    }
+}
+
+impl Eq for Coset {}
+
 impl IntoIterator for Coset {
     type Item = CirclePoint<BaseField>;
     type IntoIter = CosetIterator<CirclePoint<BaseField>>;
@@ -468,11 +534,29 @@ mod tests {
 
     use super::{CirclePointIndex, Coset};
     use crate::core::channel::Blake2sChannel;
-    use crate::core::circle::{CirclePoint, SECURE_FIELD_CIRCLE_GEN};
-    use crate::core::fields::qm31::{SecureField, P4};
-    use crate::core::fields::FieldExpOps;
+    use crate::core::circle::{
+        CirclePoint, M31_CIRCLE_GEN, M31_CIRCLE_LOG_ORDER, SECURE_FIELD_CIRCLE_GEN,
+        SECURE_FIELD_CIRCLE_ORDER,
+    };
+    use crate::core::fields::m31::{BaseField, M31, P};
+    use crate::core::fields::qm31::{SecureField, P4, QM31};
+    use crate::core::fields::{ExtensionOf, FieldExpOps};
     use crate::core::poly::circle::CanonicCoset;
 
+    /// Returns the order of the circle group `x^2 + y^2 = 1` over `F`.
+    ///
+    /// Over a field of size `q` the circle has `q + 1` points if `-1` is not a square and `q - 1`
+    /// points otherwise. As `P = 3 mod 4`, `-1` is a square exactly in the even degree extensions
+    /// of [M31].
+    const fn circle_group_order<F: ExtensionOf<BaseField>>() -> u128 {
+        let degree = F::EXTENSION_DEGREE as u32;
+        let field_size = (P as u128).pow(degree);
+        match degree % 2 {
+            0 => field_size - 1,
+            _ => field_size + 1,
+        }
+    }
+
     #[test]
     fn test_iterator() {
         let coset = Coset::new(CirclePointIndex(1), 3);
@@ -494,6 +578,72 @@ mod tests {
         assert_eq!(actual_points, expected_points);
     }
 
+    #[test]
+    fn test_circle_point_index_from_point() {
+        for index in [0, 1, 2, 3, 12345, 1 << 30, (1 << 31) - 1] {
+            let index = CirclePointIndex(index);
+
+            assert_eq!(CirclePointIndex::from_point(index.to_point()), index);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Point is not on the circle")]
+    fn test_circle_point_index_from_point_not_on_circle() {
+        CirclePointIndex::from_point(CirclePoint {
+            x: M31::from(2),
+            y: M31::from(3),
+        });
+    }
+
+    #[test]
+    fn test_coset_from_initial_step() {
+        let initial = CirclePointIndex(123).to_point();
+        let step = CirclePointIndex::subgroup_gen(4).to_point();
+
+        let coset = Coset::from_initial_step(initial, step, 4);
+
+        let mut expected = initial;
+        for i in 0..coset.size() {
+            assert_eq!(coset.at(i), expected);
+            expected = expected + step;
+        }
+        assert_eq!(
+            coset.iter().collect::<Vec<_>>(),
+            coset
+                .iter_indices()
+                .map(|i| i.to_point())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(coset, Coset::new(CirclePointIndex(123), 4));
+    }
+
+    #[test]
+    fn test_coset_eq_compares_points() {
+        let initial_index = CirclePointIndex(5);
+        let coset = Coset::new(initial_index, 3);
+        // Same points, constructed from an unreduced index.
+        let unreduced = Coset::new(CirclePointIndex(5 + (1 << M31_CIRCLE_LOG_ORDER)), 3);
+        let shifted_subgroup = Coset::subgroup(3).shift(initial_index);
+        // Same point set, in a different order.
+        let rotated = coset.shift(CirclePointIndex::subgroup_gen(3));
+
+        assert_eq!(coset, unreduced);
+        assert_eq!(coset, shifted_subgroup);
+        assert_ne!(coset, rotated);
+    }
+
+    #[test]
+    fn test_coset_at_wraps_and_at_checked() {
+        let coset = Coset::new(CirclePointIndex(1), 3);
+        let size = coset.size();
+
+        assert_eq!(coset.at(size), coset.at(0));
+        assert_eq!(coset.at(size + 3), coset.at(3));
+        assert_eq!(coset.at_checked(size - 1), Some(coset.at(size - 1)));
+        assert_eq!(coset.at_checked(size), None);
+    }
+
     #[test]
     fn test_coset_is_half_coset_with_conjugate() {
         let canonic_coset = CanonicCoset::new(8);
@@ -558,4 +708,26 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_circle_group_order() {
+        assert_eq!(circle_group_order::<M31>(), 1 << M31_CIRCLE_LOG_ORDER);
+        assert_eq!(
+            M31_CIRCLE_GEN.mul(circle_group_order::<M31>()),
+            CirclePoint::zero()
+        );
+
+        let order = circle_group_order::<QM31>();
+        assert_eq!(order, SECURE_FIELD_CIRCLE_ORDER);
+        assert_eq!(SECURE_FIELD_CIRCLE_GEN.mul(order), CirclePoint::zero());
+        assert_eq!(
+            SECURE_FIELD_CIRCLE_GEN.mul(order + 1),
+            SECURE_FIELD_CIRCLE_GEN
+        );
+        for index in [1, 2, 12345, order - 1] {
+            let point = CirclePoint::<SecureField>::get_point(index);
+            assert_eq!(point.x.square() + point.y.square(), SecureField::one());
+            assert_eq!(point.mul(order), CirclePoint::zero());
+        }
+    }
 }
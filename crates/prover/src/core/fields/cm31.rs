@@ -27,6 +27,11 @@ impl CM31 {
     pub const fn from_m31(a: M31, b: M31) -> CM31 {
         Self(a, b)
     }
+
+    /// Returns whether both coordinates are in canonical representation.
+    pub const fn is_canonical(&self) -> bool {
+        self.0.is_canonical() && self.1.is_canonical()
+    }
 }
 
 impl Display for CM31 {
@@ -2,10 +2,13 @@ use std::fmt::Display;
 use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
+use std::simd::cmp::SimdOrd;
+use std::simd::Simd;
 
 use bytemuck::{Pod, Zeroable};
 use rand::distributions::{Distribution, Standard};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use super::{ComplexConjugate, FieldExpOps};
 use crate::impl_field;
@@ -15,19 +18,7 @@ pub const P: u32 = 2147483647; // 2 ** 31 - 1
 
 #[repr(transparent)]
 #[derive(
-    Copy,
-    Clone,
-    Debug,
-    Default,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    Hash,
-    Pod,
-    Zeroable,
-    Serialize,
-    Deserialize,
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Pod, Zeroable, Serialize, Deserialize,
 )]
 pub struct M31(pub u32);
 pub type BaseField = M31;
@@ -59,9 +50,96 @@ impl M31 {
         Self((((((val >> MODULUS_BITS) + val + 1) >> MODULUS_BITS) + val) & (P as u64)) as u32)
     }
 
+    /// Returns `val % P` for any `u64`.
+    ///
+    /// Slower than [`Self::reduce()`], which only handles products of two elements. Since `2^64`
+    /// isn't a multiple of `P`, reducing a uniformly random `u64` is biased towards the smallest
+    /// residues, by a relative factor of about `2^-33`.
+    ///
+    /// ```
+    /// use stwo_prover::core::fields::m31::{M31, P};
+    ///
+    /// assert_eq!(
+    ///     M31::reduce_u64(u64::MAX),
+    ///     M31::from((u64::MAX % P as u64) as u32)
+    /// );
+    /// ```
+    pub const fn reduce_u64(val: u64) -> Self {
+        // `2^31 = 1 (mod P)`, so the high and low 31-bit halves can be added up.
+        let folded = (val & P as u64) + (val >> MODULUS_BITS);
+        let folded = ((folded & P as u64) + (folded >> MODULUS_BITS)) as u32;
+        // `folded < 2^31 + 8`, so a single subtraction suffices.
+        if folded >= P {
+            Self(folded - P)
+        } else {
+            Self(folded)
+        }
+    }
+
     pub const fn from_u32_unchecked(arg: u32) -> Self {
         Self(arg)
     }
+
+    /// Creates an element from a value already known to be in the range `[0, P)`.
+    pub const fn from_u64_unchecked(arg: u64) -> Self {
+        debug_assert!(arg < P as u64);
+        Self(arg as u32)
+    }
+
+    /// Returns whether the value is in the canonical range `[0, P)`.
+    pub const fn is_canonical(&self) -> bool {
+        self.0 < P
+    }
+
+    /// Reduces all the elements of `values` to their canonical representatives in place.
+    ///
+    /// Accepts any `u32` representation, e.g. of elements read from untrusted bytes.
+    pub fn canonicalize_slice(values: &mut [Self]) {
+        const N_LANES: usize = 16;
+        let modulus = Simd::<u32, N_LANES>::splat(P);
+        let (prefix, middle, suffix) = bytemuck::cast_slice_mut::<_, u32>(values).as_simd_mut();
+        for v in middle {
+            // Any u32 is smaller than `3P`, so two conditional subtractions suffice.
+            *v = v.simd_min(*v - modulus);
+            *v = v.simd_min(*v - modulus);
+        }
+        for v in prefix.iter_mut().chain(suffix) {
+            *v %= P;
+        }
+    }
+
+    /// Checks that all the elements of `values` are canonical.
+    ///
+    /// Returns the first non-canonical element otherwise.
+    pub fn validate_slice(values: &[Self]) -> Result<(), NonCanonicalAt> {
+        match values.iter().position(|v| !v.is_canonical()) {
+            Some(index) => Err(NonCanonicalAt {
+                index,
+                value: values[index].0,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns all ones if the element is zero, and zero otherwise, without branching.
+    pub const fn is_zero_mask(&self) -> u32 {
+        ((self.0 == 0) as u32).wrapping_neg()
+    }
+
+    /// Returns the canonical representative of the element as a float.
+    ///
+    /// A debugging aid, e.g. for histograms of field values. Not meant for arithmetic.
+    pub const fn to_f64(&self) -> f64 {
+        (self.0 % P) as f64
+    }
+}
+
+/// A non-canonical element found by [M31::validate_slice].
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+#[error("Non-canonical value {value} at index {index}.")]
+pub struct NonCanonicalAt {
+    pub index: usize,
+    pub value: u32,
 }
 
 impl Display for M31 {
@@ -74,7 +152,10 @@ impl Add for M31 {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::partial_reduce(self.0 + rhs.0)
+        debug_assert_canonical!(self, rhs);
+        let res = Self::partial_reduce(self.0 + rhs.0);
+        debug_assert_canonical!(res);
+        res
     }
 }
 
@@ -90,7 +171,10 @@ impl Sub for M31 {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Self::partial_reduce(self.0 + P - rhs.0)
+        debug_assert_canonical!(self, rhs);
+        let res = Self::partial_reduce(self.0 + P - rhs.0);
+        debug_assert_canonical!(res);
+        res
     }
 }
 
@@ -98,7 +182,10 @@ impl Mul for M31 {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        Self::reduce((self.0 as u64) * (rhs.0 as u64))
+        debug_assert_canonical!(self, rhs);
+        let res = Self::reduce((self.0 as u64) * (rhs.0 as u64));
+        debug_assert_canonical!(res);
+        res
     }
 }
 
@@ -113,7 +200,10 @@ impl FieldExpOps for M31 {
     /// ```
     fn inverse(&self) -> Self {
         assert!(!self.is_zero(), "0 has no inverse");
-        pow2147483645(*self)
+        debug_assert_canonical!(self);
+        let res = pow2147483645(*self);
+        debug_assert_canonical!(res);
+        res
     }
 }
 
@@ -139,6 +229,14 @@ impl Zero for M31 {
     }
 }
 
+/// The default value is zero, the additive identity, so e.g. `vec![M31::default(); n]` is a zero
+/// column.
+impl Default for M31 {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
 impl From<usize> for M31 {
     fn from(value: usize) -> Self {
         M31::reduce(value.try_into().unwrap())
@@ -205,12 +303,19 @@ fn sqn<const N: usize, T: FieldExpOps>(mut v: T) -> T {
 
 #[cfg(test)]
 mod tests {
+    use num_traits::Zero;
     use rand::rngs::SmallRng;
     use rand::{Rng, SeedableRng};
 
-    use super::{M31, P};
+    use super::{NonCanonicalAt, M31, P};
     use crate::core::fields::IntoSlice;
 
+    #[test]
+    fn test_default_is_zero() {
+        assert_eq!(M31::default(), M31::zero());
+        assert_eq!(vec![M31::default(); 4], vec![M31::zero(); 4]);
+    }
+
     const fn mul_p(a: u32, b: u32) -> u32 {
         ((a as u64 * b as u64) % P as u64) as u32
     }
@@ -227,6 +332,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reduce_u64() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let p = P as u64;
+        let edge_cases = [
+            0,
+            1,
+            p - 1,
+            p,
+            p + 1,
+            2 * p,
+            p * p,
+            1 << 62,
+            u64::MAX - p,
+            u64::MAX,
+        ];
+        let values = edge_cases
+            .into_iter()
+            .chain((0..64).map(|bits| (1 << bits) - 1))
+            .chain((0..10000).map(|_| rng.gen::<u64>()));
+
+        for val in values {
+            assert_eq!(M31::reduce_u64(val), M31((val % p) as u32), "{val}");
+        }
+    }
+
+    #[test]
+    fn test_from_u64_unchecked() {
+        assert_eq!(M31::from_u64_unchecked(P as u64 - 1), M31(P - 1));
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(M31::from(5).to_f64(), 5.0);
+        assert_eq!(M31::from(P - 1).to_f64(), (P - 1) as f64);
+        assert_eq!(M31::from_u32_unchecked(P).to_f64(), 0.0);
+        assert_eq!(M31::from_u32_unchecked(P + 7).to_f64(), 7.0);
+    }
+
     #[test]
     fn test_basic_ops() {
         let mut rng = SmallRng::seed_from_u64(0);
@@ -239,6 +383,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_zero_mask() {
+        assert_eq!(m31!(0).is_zero_mask(), u32::MAX);
+        assert_eq!(m31!(1).is_zero_mask(), 0);
+        assert_eq!(m31!(P - 1).is_zero_mask(), 0);
+    }
+
+    #[test]
+    fn test_canonicalize_and_validate_slice() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut values = (0..37)
+            .map(|_| M31::from_u32_unchecked(rng.gen::<u32>() % P))
+            .collect::<Vec<_>>();
+        values[3] = M31::from_u32_unchecked(P);
+        values[20] = M31::from_u32_unchecked(2 * P + 1);
+        values[36] = M31::from_u32_unchecked(P + 5);
+        let expected = values.iter().map(|v| m31!(v.0 % P)).collect::<Vec<_>>();
+
+        assert_eq!(
+            M31::validate_slice(&values),
+            Err(NonCanonicalAt { index: 3, value: P })
+        );
+        M31::canonicalize_slice(&mut values);
+
+        assert_eq!(values, expected);
+        assert_eq!(M31::validate_slice(&values), Ok(()));
+    }
+
     #[test]
     fn test_into_slice() {
         let mut rng = SmallRng::seed_from_u64(0);
@@ -255,4 +427,13 @@ mod tests {
             );
         }
     }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "Non-canonical field element")]
+    fn test_debug_checks_non_canonical() {
+        let corrupted = M31(P);
+
+        let _ = corrupted + m31!(1);
+    }
 }
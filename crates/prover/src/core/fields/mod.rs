@@ -6,6 +6,21 @@ use num_traits::{NumAssign, NumAssignOps, NumOps, One};
 
 use super::backend::ColumnOps;
 
+/// Asserts that the given field elements are in canonical representation.
+///
+/// Only checked in debug builds with the `debug-checks` feature enabled.
+macro_rules! debug_assert_canonical {
+    ($($value:expr),+ $(,)?) => {
+        if cfg!(feature = "debug-checks") {
+            $(debug_assert!(
+                $value.is_canonical(),
+                "Non-canonical field element: {:?}",
+                $value
+            );)+
+        }
+    };
+}
+
 pub mod cm31;
 pub mod m31;
 pub mod qm31;
@@ -292,7 +307,10 @@ macro_rules! impl_extension_field {
             type Output = Self;
 
             fn add(self, rhs: Self) -> Self::Output {
-                Self(self.0 + rhs.0, self.1 + rhs.1)
+                debug_assert_canonical!(self, rhs);
+                let res = Self(self.0 + rhs.0, self.1 + rhs.1);
+                debug_assert_canonical!(res);
+                res
             }
         }
 
@@ -308,7 +326,10 @@ macro_rules! impl_extension_field {
             type Output = Self;
 
             fn sub(self, rhs: Self) -> Self::Output {
-                Self(self.0 - rhs.0, self.1 - rhs.1)
+                debug_assert_canonical!(self, rhs);
+                let res = Self(self.0 - rhs.0, self.1 - rhs.1);
+                debug_assert_canonical!(res);
+                res
             }
         }
 
@@ -1,23 +1,27 @@
 use std::fmt::{Debug, Display};
+use std::iter::zip;
 use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use super::secure_column::SECURE_EXTENSION_DEGREE;
 use super::{ComplexConjugate, FieldExpOps};
 use crate::core::fields::cm31::CM31;
-use crate::core::fields::m31::M31;
+use crate::core::fields::m31::{NonCanonicalAt, M31};
 use crate::{impl_extension_field, impl_field};
 
 pub const P4: u128 = 21267647892944572736998860269687930881; // (2 ** 31 - 1) ** 4
 pub const R: CM31 = CM31::from_u32_unchecked(2, 1);
+/// `R^((p - 1) / 2)`, so that `u^p = FROBENIUS_U_COEFF * u`.
+const FROBENIUS_U_COEFF: CM31 = CM31::from_u32_unchecked(21189756, 42379512);
 
 /// Extension field of CM31.
 /// Equivalent to CM31\[x\] over (x^2 - 2 - i) as the irreducible polynomial.
 /// Represented as ((a, b), (c, d)) of (a + bi) + (c + di)u.
-#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct QM31(pub CM31, pub CM31);
 pub type SecureField = QM31;
 
@@ -44,6 +48,30 @@ impl QM31 {
         [self.0 .0, self.0 .1, self.1 .0, self.1 .1]
     }
 
+    /// Returns the little-endian encoding of the four [M31] coordinates, in order.
+    ///
+    /// This is the encoding the channels absorb field elements with.
+    pub fn to_le_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        for (chunk, coord) in zip(bytes.chunks_exact_mut(4), self.to_m31_array()) {
+            chunk.copy_from_slice(&coord.0.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes an element encoded by [`Self::to_le_bytes()`].
+    ///
+    /// Returns the first non-canonical coordinate otherwise.
+    pub fn from_le_bytes(bytes: [u8; 16]) -> Result<Self, NonCanonicalAt> {
+        let coords: [M31; SECURE_EXTENSION_DEGREE] = std::array::from_fn(|i| {
+            M31(u32::from_le_bytes(
+                bytes[i * 4..(i + 1) * 4].try_into().unwrap(),
+            ))
+        });
+        M31::validate_slice(&coords)?;
+        Ok(Self::from_m31_array(coords))
+    }
+
     /// Returns the combined value, given the values of its composing base field polynomials at that
     /// point.
     pub fn from_partial_evals(evals: [Self; SECURE_EXTENSION_DEGREE]) -> Self {
@@ -54,11 +82,76 @@ impl QM31 {
         res
     }
 
+    /// Returns whether all coordinates are in canonical representation.
+    pub const fn is_canonical(&self) -> bool {
+        self.0.is_canonical() && self.1.is_canonical()
+    }
+
+    /// Returns the canonical representatives of the coordinates as floats, see [`M31::to_f64()`].
+    ///
+    /// A debugging aid, not meant for arithmetic.
+    pub fn coords_f64(&self) -> [f64; SECURE_EXTENSION_DEGREE] {
+        self.to_m31_array().map(|coord| coord.to_f64())
+    }
+
     // Note: Adding this as a Mul impl drives rust insane, and it tries to infer Qm31*Qm31 as
     // QM31*CM31.
     pub fn mul_cm31(self, rhs: CM31) -> Self {
         Self(self.0 * rhs, self.1 * rhs)
     }
+
+    /// Multiplies by a base field element, coordinate-wise.
+    ///
+    /// Equivalent to multiplying by `QM31::from(rhs)`, with four [M31] multiplications instead of a
+    /// full extension multiplication. Same as the [`Mul<M31>`] implementation, which is what
+    /// [`Relation::combine`](crate::constraint_framework::Relation::combine) uses for base field
+    /// values.
+    pub fn mul_base(self, rhs: M31) -> Self {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+
+    /// Returns the Frobenius map of the element, `x^p`.
+    ///
+    /// Applying it repeatedly gives the Galois conjugates of the element over [M31], and twice
+    /// gives the [ComplexConjugate] over [CM31], `x^(p^2)`.
+    pub fn frobenius(&self) -> Self {
+        let Self(a, b) = *self;
+        Self(
+            a.complex_conjugate(),
+            b.complex_conjugate() * FROBENIUS_U_COEFF,
+        )
+    }
+
+    /// Returns the norm of the element over [M31], the product of its Galois conjugates
+    /// `x * x^p * x^(p^2) * x^(p^3)`.
+    pub fn norm(&self) -> M31 {
+        // The norm over CM31 is `(a + bu)(a - bu) = a^2 - R * b^2`, whose norm over M31 is in turn
+        // `(c + di)(c - di) = c^2 + d^2`.
+        let Self(a, b) = *self;
+        let CM31(c, d) = a.square() - R * b.square();
+        c.square() + d.square()
+    }
+
+    /// Inverts all the non-zero `values` with a single inversion, using Montgomery's trick (see
+    /// [FieldExpOps::batch_inverse]). Zero values are left as zero.
+    pub fn batch_inverse_allow_zeros(values: &[Self]) -> Vec<Self> {
+        let non_zero_values = values
+            .iter()
+            .copied()
+            .filter(|v| !v.is_zero())
+            .collect_vec();
+        let mut non_zero_inverses = vec![Self::zero(); non_zero_values.len()];
+        Self::batch_inverse(&non_zero_values, &mut non_zero_inverses);
+
+        let mut non_zero_inverses = non_zero_inverses.into_iter();
+        values
+            .iter()
+            .map(|v| match v.is_zero() {
+                true => *v,
+                false => non_zero_inverses.next().unwrap(),
+            })
+            .collect()
+    }
 }
 
 impl Display for QM31 {
@@ -77,11 +170,21 @@ impl Mul for QM31 {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
+        debug_assert_canonical!(self, rhs);
         // (a + bu) * (c + du) = (ac + rbd) + (ad + bc)u.
-        Self(
+        let res = Self(
             self.0 * rhs.0 + R * self.1 * rhs.1,
             self.0 * rhs.1 + self.1 * rhs.0,
-        )
+        );
+        debug_assert_canonical!(res);
+        res
+    }
+}
+
+/// The default value is zero, the additive identity, like for [M31].
+impl Default for QM31 {
+    fn default() -> Self {
+        Self::zero()
     }
 }
 
@@ -121,8 +224,11 @@ impl FieldExpOps for QM31 {
         let b2 = self.1.square();
         let ib2 = CM31(-b2.1, b2.0);
         let denom = self.0.square() - (b2 + b2 + ib2);
+        debug_assert_canonical!(self);
         let denom_inverse = denom.inverse();
-        Self(self.0 * denom_inverse, -self.1 * denom_inverse)
+        let res = Self(self.0 * denom_inverse, -self.1 * denom_inverse);
+        debug_assert_canonical!(res);
+        res
     }
 }
 
@@ -137,15 +243,89 @@ macro_rules! qm31 {
 
 #[cfg(test)]
 mod tests {
-    use num_traits::One;
+    use std::iter::zip;
+
+    use num_traits::{One, Zero};
     use rand::rngs::SmallRng;
     use rand::{Rng, SeedableRng};
 
     use super::QM31;
-    use crate::core::fields::m31::P;
-    use crate::core::fields::{FieldExpOps, IntoSlice};
+    use crate::core::fields::m31::{NonCanonicalAt, M31, P};
+    use crate::core::fields::{ComplexConjugate, FieldExpOps, IntoSlice};
     use crate::m31;
 
+    #[test]
+    fn test_coords_f64() {
+        let value = QM31::from_u32_unchecked(1, P, 3, P + 4);
+
+        assert_eq!(value.coords_f64(), [1.0, 0.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_default_is_zero() {
+        assert_eq!(QM31::default(), QM31::zero());
+    }
+
+    #[test]
+    fn test_mul_base() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let a: QM31 = rng.gen();
+            let b: M31 = rng.gen();
+
+            assert_eq!(a.mul_base(b), a * QM31::from(b));
+            assert_eq!(a.mul_base(b), a * b);
+        }
+    }
+
+    #[test]
+    fn test_batch_inverse_allow_zeros() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut values = (0..16).map(|_| rng.gen()).collect::<Vec<QM31>>();
+        values[0] = QM31::zero();
+        values[5] = QM31::zero();
+        values[15] = QM31::zero();
+
+        let inverses = QM31::batch_inverse_allow_zeros(&values);
+
+        for (value, inverse) in values.iter().zip(inverses) {
+            match value.is_zero() {
+                true => assert!(inverse.is_zero()),
+                false => assert_eq!(inverse, value.inverse()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_frobenius() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let x = rng.gen::<QM31>();
+
+            assert_eq!(x.frobenius(), x.pow(P as u128));
+            assert_eq!(x.frobenius().frobenius(), x.complex_conjugate());
+            assert_eq!(x.frobenius().frobenius().frobenius().frobenius(), x);
+        }
+    }
+
+    #[test]
+    fn test_norm() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let [x, y] = rng.gen::<[QM31; 2]>();
+            let x_conjugates = [
+                x,
+                x.frobenius(),
+                x.complex_conjugate(),
+                x.complex_conjugate().frobenius(),
+            ];
+
+            assert_eq!(x_conjugates.into_iter().product::<QM31>(), x.norm().into());
+            assert_eq!((x * y).norm(), x.norm() * y.norm());
+        }
+        assert_eq!(QM31::from(m31!(3)).norm(), m31!(81));
+    }
+
     #[test]
     fn test_inverse() {
         let qm = qm31!(1, 2, 3, 4);
@@ -192,4 +372,38 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn test_le_bytes_roundtrip() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let values = (0..100).map(|_| rng.gen()).collect::<Vec<QM31>>();
+
+        for (value, encoded) in zip(&values, QM31::into_slice(&values).chunks_exact(16)) {
+            let bytes = value.to_le_bytes();
+
+            assert_eq!(bytes, encoded);
+            assert_eq!(QM31::from_le_bytes(bytes), Ok(*value));
+        }
+    }
+
+    #[test]
+    fn test_from_le_bytes_rejects_non_canonical() {
+        let mut bytes = qm31!(1, 2, 3, 4).to_le_bytes();
+        bytes[8..12].copy_from_slice(&P.to_le_bytes());
+
+        assert_eq!(
+            QM31::from_le_bytes(bytes),
+            Err(NonCanonicalAt { index: 2, value: P })
+        );
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "Non-canonical field element")]
+    fn test_debug_checks_non_canonical() {
+        let mut corrupted = qm31!(1, 2, 3, 4);
+        corrupted.1 .0 .0 = P + 5;
+
+        let _ = corrupted * qm31!(5, 6, 7, 8);
+    }
 }
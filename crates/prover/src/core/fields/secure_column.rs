@@ -11,15 +11,25 @@ pub const SECURE_EXTENSION_DEGREE: usize =
 
 /// A column major array of `SECURE_EXTENSION_DEGREE` base field columns, that represents a column
 /// of secure field element coordinates.
+///
+/// The `i`th coordinate of the element at `index` is `columns[i][index]`, in the order of
+/// [`SecureField::to_m31_array()`] and of [`EvalAtRow::combine_ef()`].
+///
+/// [`EvalAtRow::combine_ef()`]: crate::constraint_framework::EvalAtRow::combine_ef
 #[derive(Clone, Debug)]
 pub struct SecureColumnByCoords<B: FieldOps<BaseField>> {
     pub columns: [Col<B, BaseField>; SECURE_EXTENSION_DEGREE],
 }
 impl SecureColumnByCoords<CpuBackend> {
-    // TODO(first): Remove.
+    /// Interleaves the coordinate columns into secure field elements.
     pub fn to_vec(&self) -> Vec<SecureField> {
         (0..self.len()).map(|i| self.at(i)).collect()
     }
+
+    /// Splits secure field elements into coordinate columns.
+    pub fn from_vec(values: Vec<SecureField>) -> Self {
+        values.into_iter().collect()
+    }
 }
 impl<B: FieldOps<BaseField>> SecureColumnByCoords<B> {
     pub fn at(&self, index: usize) -> SecureField {
@@ -109,3 +119,40 @@ impl From<SecureColumnByCoords<CpuBackend>> for Vec<SecureField> {
         column.into_iter().collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{SecureColumnByCoords, SECURE_EXTENSION_DEGREE};
+    use crate::core::backend::CpuBackend;
+    use crate::core::fields::qm31::SecureField;
+
+    #[test]
+    fn test_secure_column_vec_round_trip() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let values = (0..37).map(|_| rng.gen()).collect::<Vec<SecureField>>();
+
+        let column = SecureColumnByCoords::<CpuBackend>::from_vec(values.clone());
+
+        assert_eq!(column.len(), values.len());
+        assert_eq!(column.to_vec(), values);
+    }
+
+    #[test]
+    fn test_secure_column_layout_matches_to_m31_array() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let values = (0..16).map(|_| rng.gen()).collect::<Vec<SecureField>>();
+
+        let column = SecureColumnByCoords::<CpuBackend>::from_vec(values.clone());
+
+        for (index, value) in values.iter().enumerate() {
+            let coords = (0..SECURE_EXTENSION_DEGREE)
+                .map(|i| column.columns[i][index])
+                .collect_vec();
+            assert_eq!(coords, value.to_m31_array());
+        }
+    }
+}
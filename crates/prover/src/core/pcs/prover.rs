@@ -38,14 +38,23 @@ impl<'a, B: BackendForChannel<MC>, MC: MerkleChannel> CommitmentSchemeProver<'a,
     }
 
     fn commit(&mut self, polynomials: ColumnVec<CirclePoly<B>>, channel: &mut MC::C) {
+        let root = self.commit_without_mixing(polynomials);
+        MC::mix_root(channel, root);
+    }
+
+    fn commit_without_mixing(
+        &mut self,
+        polynomials: ColumnVec<CirclePoly<B>>,
+    ) -> <MC::H as MerkleHasher>::Hash {
         let _span = span!(Level::INFO, "Commitment").entered();
-        let tree = CommitmentTreeProver::new(
+        let tree = CommitmentTreeProver::build(
             polynomials,
             self.config.fri_config.log_blowup_factor,
-            channel,
             self.twiddles,
         );
+        let root = tree.commitment.root();
         self.trees.push(tree);
+        root
     }
 
     pub fn tree_builder(&mut self) -> TreeBuilder<'_, 'a, B, MC> {
@@ -192,6 +201,15 @@ impl<'a, 'b, B: BackendForChannel<MC>, MC: MerkleChannel> TreeBuilder<'a, 'b, B,
         let _span = span!(Level::INFO, "Commitment").entered();
         self.commitment_scheme.commit(self.polys, channel);
     }
+
+    /// Commits like [Self::commit], but returns the root instead of mixing it into a channel, for
+    /// callers that mix it themselves, e.g. through a [ChannelScriptRunner].
+    ///
+    /// [ChannelScriptRunner]: crate::core::channel::ChannelScriptRunner
+    pub fn commit_without_mixing(self) -> <MC::H as MerkleHasher>::Hash {
+        let _span = span!(Level::INFO, "Commitment").entered();
+        self.commitment_scheme.commit_without_mixing(self.polys)
+    }
 }
 
 /// Prover data for a single commitment tree in a commitment scheme. The commitment scheme allows to
@@ -208,6 +226,16 @@ impl<B: BackendForChannel<MC>, MC: MerkleChannel> CommitmentTreeProver<B, MC> {
         log_blowup_factor: u32,
         channel: &mut MC::C,
         twiddles: &TwiddleTree<B>,
+    ) -> Self {
+        let tree = Self::build(polynomials, log_blowup_factor, twiddles);
+        MC::mix_root(channel, tree.commitment.root());
+        tree
+    }
+
+    fn build(
+        polynomials: ColumnVec<CirclePoly<B>>,
+        log_blowup_factor: u32,
+        twiddles: &TwiddleTree<B>,
     ) -> Self {
         let span = span!(Level::INFO, "Extension").entered();
         let evaluations = B::evaluate_polynomials(&polynomials, log_blowup_factor, twiddles);
@@ -215,7 +243,6 @@ impl<B: BackendForChannel<MC>, MC: MerkleChannel> CommitmentTreeProver<B, MC> {
 
         let _span = span!(Level::INFO, "Merkle").entered();
         let tree = MerkleProver::commit(evaluations.iter().map(|eval| &eval.values).collect());
-        MC::mix_root(channel, tree.root());
 
         CommitmentTreeProver {
             polynomials,
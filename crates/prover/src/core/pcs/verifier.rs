@@ -44,6 +44,18 @@ impl<MC: MerkleChannel> CommitmentSchemeVerifier<MC> {
         channel: &mut MC::C,
     ) {
         MC::mix_root(channel, commitment);
+        self.commit_without_mixing(commitment, log_sizes);
+    }
+
+    /// Reads a commitment like [Self::commit], for callers that mix it into the channel
+    /// themselves, e.g. through a [ChannelScriptRunner].
+    ///
+    /// [ChannelScriptRunner]: crate::core::channel::ChannelScriptRunner
+    pub fn commit_without_mixing(
+        &mut self,
+        commitment: <MC::H as MerkleHasher>::Hash,
+        log_sizes: &[u32],
+    ) {
         let extended_log_sizes = log_sizes
             .iter()
             .map(|&log_size| log_size + self.config.fri_config.log_blowup_factor)
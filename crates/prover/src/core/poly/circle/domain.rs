@@ -6,17 +6,29 @@ use crate::core::circle::{
     CirclePoint, CirclePointIndex, Coset, CosetIterator, M31_CIRCLE_LOG_ORDER,
 };
 use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
+use crate::core::fields::FieldExpOps;
 
 pub const MAX_CIRCLE_DOMAIN_LOG_SIZE: u32 = M31_CIRCLE_LOG_ORDER - 1;
 
 /// A valid domain for circle polynomial interpolation and evaluation.
 /// Valid domains are a disjoint union of two conjugate cosets: +-C + <G_n>.
 /// The ordering defined on this domain is C + iG_n, and then -C - iG_n.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug)]
 pub struct CircleDomain {
     pub half_coset: Coset,
 }
 
+/// Two domains are equal iff they yield the same points in the same order, which is the case iff
+/// their half cosets are equal (see [Coset]'s [PartialEq] impl).
+impl PartialEq for CircleDomain {
+    fn eq(&self, other: &Self) -> bool {
+        self.half_coset == other.half_coset
+    }
+}
+
+impl Eq for CircleDomain {}
+
 impl CircleDomain {
     /// Given a coset C + <G_n>, constructs the circle domain +-C + <G_n> (i.e.,
     /// this coset and its conjugate).
@@ -96,6 +108,70 @@ impl CircleDomain {
     pub fn shift(&self, shift: CirclePointIndex) -> CircleDomain {
         CircleDomain::new(self.half_coset.shift(shift))
     }
+
+    /// Returns the point-independent barycentric weights of the domain, in domain order.
+    ///
+    /// A polynomial interpolated on the domain is `f0(x) + y * f1(x)`, where `f0` and `f1` are
+    /// univariate polynomials interpolated on the `x` coordinates of the half coset. The weights
+    /// only depend on the domain, so they can be computed once and reused for every evaluation at
+    /// every point. See [`CpuCircleEvaluation::eval_at_point()`].
+    ///
+    /// [`CpuCircleEvaluation::eval_at_point()`]: crate::core::backend::cpu::CpuCircleEvaluation::eval_at_point
+    pub fn barycentric_weights(&self) -> Vec<BaseField> {
+        // The `x` coordinates of the half coset are the roots of `pi^m(x) - c`, where `pi` is the
+        // `x` coordinate doubling map, `m` is the log size of the half coset and `c` is a constant.
+        // The weight of `(x_i, y_i)` is `1 / (2 * y_i * (pi^m)'(x_i))`, where
+        // `(pi^m)'(x_i) = 4^m * prod_{t < m} pi^t(x_i)`.
+        let log_half_size = self.half_coset.log_size;
+        let scale = BaseField::from(2).pow(2 * log_half_size as u128 + 1);
+        let denominators = self
+            .iter()
+            .map(|point| {
+                let mut denominator = scale * point.y;
+                let mut x = point.x;
+                for _ in 0..log_half_size {
+                    denominator *= x;
+                    x = CirclePoint::double_x(x);
+                }
+                denominator
+            })
+            .collect_vec();
+        let mut weights = vec![BaseField::default(); denominators.len()];
+        BaseField::batch_inverse(&denominators, &mut weights);
+        weights
+    }
+
+    /// Returns the barycentric coefficients of `point`, in domain order, given the
+    /// [`Self::barycentric_weights()`] of the domain.
+    ///
+    /// The value at `point` of the polynomial interpolated on an evaluation on the domain is the
+    /// inner product of the coefficients with its values. The coefficients fold the inverses of
+    /// `point.x - x_i` into the weights, so they are computed once per point and reused for every
+    /// evaluation on the domain. See
+    /// [`CpuCircleEvaluation::eval_with_barycentric_coefficients()`].
+    ///
+    /// `point` must not share an `x` coordinate with a point of the domain.
+    ///
+    /// [`CpuCircleEvaluation::eval_with_barycentric_coefficients()`]: crate::core::backend::cpu::CpuCircleEvaluation::eval_with_barycentric_coefficients
+    pub fn barycentric_coefficients(
+        &self,
+        point: CirclePoint<SecureField>,
+        weights: &[BaseField],
+    ) -> Vec<SecureField> {
+        assert_eq!(weights.len(), self.size());
+        let log_half_size = self.half_coset.log_size;
+        // Vanishes on the `x` coordinates of the domain.
+        let vanishing =
+            point.repeated_double(log_half_size).x - self.at(0).repeated_double(log_half_size).x;
+
+        let denominators = self.iter().map(|p| point.x - p.x).collect_vec();
+        let mut coefficients = vec![SecureField::default(); denominators.len()];
+        SecureField::batch_inverse(&denominators, &mut coefficients);
+        for ((coefficient, p), &weight) in coefficients.iter_mut().zip(self.iter()).zip(weights) {
+            *coefficient *= vanishing * (point.y + p.y) * weight;
+        }
+        coefficients
+    }
 }
 
 impl IntoIterator for CircleDomain {
@@ -165,6 +241,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_circle_domain_eq_compares_points() {
+        let domain = CanonicCoset::new(4).circle_domain();
+        let equivalent_domain = CircleDomain::new(Coset::half_odds(3));
+        let rotated_domain = domain.shift(CirclePointIndex::subgroup_gen(3));
+
+        assert_eq!(domain, equivalent_domain);
+        assert!(domain.iter().eq(equivalent_domain.iter()));
+        assert_ne!(domain, rotated_domain);
+    }
+
     #[test]
     fn test_domain_split() {
         let domain = CanonicCoset::new(5).circle_domain();
@@ -1,18 +1,22 @@
 use std::marker::PhantomData;
-use std::ops::{Deref, Index};
+use std::ops::{Add, Deref, Index, Mul, Sub};
 
 use educe::Educe;
+use itertools::zip_eq;
+use thiserror::Error;
 
 use super::{CanonicCoset, CircleDomain, CirclePoly, PolyOps};
 use crate::core::backend::cpu::CpuCircleEvaluation;
 use crate::core::backend::simd::SimdBackend;
 use crate::core::backend::{Col, Column, CpuBackend};
-use crate::core::circle::{CirclePointIndex, Coset};
+use crate::core::circle::{CirclePoint, CirclePointIndex, Coset};
 use crate::core::fields::m31::BaseField;
+use crate::core::fields::qm31::SecureField;
 use crate::core::fields::{ExtensionOf, FieldOps};
 use crate::core::poly::twiddles::TwiddleTree;
 use crate::core::poly::{BitReversedOrder, NaturalOrder};
-use crate::core::utils::bit_reverse_index;
+use crate::core::utils::{bit_reverse_index, coset_index_to_circle_domain_index};
+use crate::core::ColumnVec;
 
 /// An evaluation defined on a [CircleDomain].
 /// The values are ordered according to the [CircleDomain] ordering.
@@ -72,6 +76,33 @@ impl<F: ExtensionOf<BaseField>> CpuCircleEvaluation<F, NaturalOrder> {
     }
 }
 
+impl CpuCircleEvaluation<BaseField, NaturalOrder> {
+    /// Evaluates the polynomial interpolated on this evaluation at `point`, given the
+    /// [`CircleDomain::barycentric_weights()`] of its domain.
+    ///
+    /// `point` must not share an `x` coordinate with a point of the domain. To evaluate several
+    /// evaluations on the same domain at the same point, compute the
+    /// [`CircleDomain::barycentric_coefficients()`] of the point once and use
+    /// [`Self::eval_with_barycentric_coefficients()`] instead.
+    pub fn eval_at_point(
+        &self,
+        point: CirclePoint<SecureField>,
+        weights: &[BaseField],
+    ) -> SecureField {
+        self.eval_with_barycentric_coefficients(
+            &self.domain.barycentric_coefficients(point, weights),
+        )
+    }
+
+    /// Evaluates the polynomial interpolated on this evaluation at a point, given the
+    /// [`CircleDomain::barycentric_coefficients()`] of the point on its domain.
+    pub fn eval_with_barycentric_coefficients(&self, coefficients: &[SecureField]) -> SecureField {
+        zip_eq(coefficients, &self.values)
+            .map(|(&coefficient, &value)| coefficient * value)
+            .sum()
+    }
+}
+
 impl<B: PolyOps> CircleEvaluation<B, BaseField, BitReversedOrder> {
     /// Creates a [CircleEvaluation] from values ordered according to
     /// [CanonicCoset]. For example, the canonic coset might look like this:
@@ -93,9 +124,69 @@ impl<B: PolyOps> CircleEvaluation<B, BaseField, BitReversedOrder> {
     pub fn interpolate_with_twiddles(self, twiddles: &TwiddleTree<B>) -> CirclePoly<B> {
         B::interpolate(self, twiddles)
     }
+
+    /// Extends the evaluation onto `target`, a domain containing [`Self::domain`], by
+    /// interpolating it and evaluating the resulting polynomial on `target`.
+    ///
+    /// Returns a copy of the evaluation when `target` is its domain.
+    pub fn extend(&self, target: CircleDomain) -> Self {
+        let half_coset = self.domain.half_coset;
+        // The target is closed under conjugation, so it contains the domain iff it contains the
+        // half coset.
+        let contains_domain = target.find(half_coset.initial_index).is_some()
+            && (half_coset.log_size == 0
+                || half_coset
+                    .step_size
+                    .try_div(target.half_coset.step_size)
+                    .is_some());
+        assert!(
+            contains_domain,
+            "Target domain does not contain the evaluation domain"
+        );
+
+        if target == self.domain {
+            return self.clone();
+        }
+        self.clone().interpolate().evaluate(target)
+    }
+
+    /// Returns the evaluation of the same polynomial on the domain shifted by `shift`, see
+    /// [`CircleDomain::shift()`].
+    ///
+    /// When the shifted domain has the same points, e.g. for shifts by multiples of the domain's
+    /// step, the values are only reordered. Otherwise the evaluation is interpolated and evaluated
+    /// on the shifted domain.
+    pub fn shift(&self, shift: CirclePointIndex) -> Self {
+        let domain = self.domain.shift(shift);
+        let is_permutation = self.domain.find(domain.index_at(0)).is_some()
+            && domain.half_coset.step_size == self.domain.half_coset.step_size;
+        if !is_permutation {
+            return self.clone().interpolate().evaluate(domain);
+        }
+
+        let log_size = domain.log_size();
+        let values = (0..domain.size())
+            .map(|i| self.get_at(domain.index_at(bit_reverse_index(i, log_size))))
+            .collect();
+        Self::new(domain, values)
+    }
 }
 
 impl<B: FieldOps<F>, F: ExtensionOf<BaseField>> CircleEvaluation<B, F, BitReversedOrder> {
+    /// Creates an evaluation on the circle domain of `coset` from `values` in [NaturalOrder], i.e.
+    /// in the order of [`CircleDomain::iter()`], bit reversing them internally.
+    ///
+    /// Values ordered like the rows of a trace, along the canonic coset, should be passed to
+    /// [`Self::new_canonical_ordered()`] instead.
+    pub fn from_natural_order(coset: CanonicCoset, values: Col<B, F>) -> Self {
+        CircleEvaluation::<B, F, NaturalOrder>::new(coset.circle_domain(), values).bit_reverse()
+    }
+
+    /// Returns the values in [NaturalOrder], see [`Self::from_natural_order()`].
+    pub fn to_natural_order(&self) -> Vec<F> {
+        self.clone().bit_reverse().values.to_cpu()
+    }
+
     pub fn bit_reverse(mut self) -> CircleEvaluation<B, F, NaturalOrder> {
         B::bit_reverse_column(&mut self.values);
         CircleEvaluation::new(self.domain, self.values)
@@ -128,6 +219,101 @@ impl<B: FieldOps<F>, F: ExtensionOf<BaseField>, EvalOrder> Deref
     }
 }
 
+/// Implements a pointwise operation on evaluations, for references and for owned evaluations.
+///
+/// # Panics
+///
+/// The operation panics if the evaluations are over different domains.
+macro_rules! impl_pointwise_op {
+    ($op_trait:ident, $op_fn:ident) => {
+        impl<B: FieldOps<F>, F: ExtensionOf<BaseField>, EvalOrder> $op_trait
+            for &CircleEvaluation<B, F, EvalOrder>
+        {
+            type Output = CircleEvaluation<B, F, EvalOrder>;
+
+            fn $op_fn(self, rhs: Self) -> Self::Output {
+                assert_eq!(
+                    self.domain, rhs.domain,
+                    "Evaluations are over different domains"
+                );
+                let values = (0..self.values.len())
+                    .map(|i| self.values.at(i).$op_fn(rhs.values.at(i)))
+                    .collect();
+                CircleEvaluation::new(self.domain, values)
+            }
+        }
+
+        impl<B: FieldOps<F>, F: ExtensionOf<BaseField>, EvalOrder> $op_trait
+            for CircleEvaluation<B, F, EvalOrder>
+        {
+            type Output = Self;
+
+            fn $op_fn(self, rhs: Self) -> Self {
+                (&self).$op_fn(&rhs)
+            }
+        }
+    };
+}
+
+impl_pointwise_op!(Add, add);
+impl_pointwise_op!(Sub, sub);
+impl_pointwise_op!(Mul, mul);
+
+/// A set of base field evaluations over a single shared domain, ready to be committed as one tree.
+pub type TreeColumns<B, EvalOrder = BitReversedOrder> =
+    ColumnVec<CircleEvaluation<B, BaseField, EvalOrder>>;
+
+/// Error when stacking evaluations that are not all over the same domain.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+#[error(
+    "Column {index} is over a domain of log size {actual_log_size}, expected {expected_log_size}."
+)]
+pub struct DomainMismatch {
+    /// Index of the first offending column.
+    pub index: usize,
+    pub expected_log_size: u32,
+    pub actual_log_size: u32,
+}
+
+/// Stacks independently generated evaluations into a single column set.
+/// All evaluations must be over the domain of the first one.
+pub fn stack_columns<B: FieldOps<BaseField>, EvalOrder>(
+    evals: Vec<CircleEvaluation<B, BaseField, EvalOrder>>,
+) -> Result<TreeColumns<B, EvalOrder>, DomainMismatch> {
+    let Some(expected) = evals.first().map(|eval| eval.domain) else {
+        return Ok(vec![]);
+    };
+    if let Some((index, eval)) = evals.iter().enumerate().find(|(_, e)| e.domain != expected) {
+        return Err(DomainMismatch {
+            index,
+            expected_log_size: expected.log_size(),
+            actual_log_size: eval.domain.log_size(),
+        });
+    }
+    Ok(evals)
+}
+
+/// Returns the values of all the `columns` at trace row `row`, e.g. to inspect a committed tree
+/// while debugging.
+///
+/// Rows are indexed in trace order, i.e. row `i` is at the `i`-th point of the half coset order
+/// used by the trace generators, see [`coset_index_to_circle_domain_index()`].
+pub fn trace_row<B: FieldOps<BaseField>>(
+    columns: &[CircleEvaluation<B, BaseField, BitReversedOrder>],
+    row: usize,
+) -> Vec<BaseField> {
+    columns
+        .iter()
+        .map(|column| {
+            let log_size = column.domain.log_size();
+            let circle_domain_index = coset_index_to_circle_domain_index(row, log_size);
+            column
+                .values
+                .at(bit_reverse_index(circle_domain_index, log_size))
+        })
+        .collect()
+}
+
 /// A part of a [CircleEvaluation], for a specific coset that is a subset of the circle domain.
 pub struct CosetSubEvaluation<'a, F: ExtensionOf<BaseField>> {
     evaluation: &'a [F],
@@ -166,11 +352,16 @@ impl<'a, F: ExtensionOf<BaseField>> Index<usize> for CosetSubEvaluation<'a, F> {
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
+    use super::{stack_columns, trace_row, CircleEvaluation, DomainMismatch};
     use crate::core::backend::cpu::CpuCircleEvaluation;
-    use crate::core::circle::Coset;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::circle::{CirclePoint, CirclePointIndex, Coset};
     use crate::core::fields::m31::BaseField;
-    use crate::core::poly::circle::CanonicCoset;
-    use crate::core::poly::NaturalOrder;
+    use crate::core::poly::circle::{CanonicCoset, CircleDomain};
+    use crate::core::poly::{BitReversedOrder, NaturalOrder};
+    use crate::core::utils::{bit_reverse_index, coset_index_to_circle_domain_index};
     use crate::m31;
 
     #[test]
@@ -201,6 +392,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_barycentric_eval_at_point() {
+        let point = CirclePoint::get_point(98989892);
+        for log_size in 1..=6 {
+            let domain = CanonicCoset::new(log_size).circle_domain();
+            let weights = domain.barycentric_weights();
+            for seed in 0..3 {
+                let values = (0..domain.size())
+                    .map(|i| m31!((seed * 1000 + i * i) as u32))
+                    .collect_vec();
+                let evaluation = CpuCircleEvaluation::<_, NaturalOrder>::new(domain, values);
+                let expected = evaluation
+                    .clone()
+                    .bit_reverse()
+                    .interpolate()
+                    .eval_at_point(point);
+
+                assert_eq!(evaluation.eval_at_point(point, &weights), expected);
+                assert_eq!(
+                    evaluation.eval_with_barycentric_coefficients(
+                        &domain.barycentric_coefficients(point, &weights)
+                    ),
+                    expected
+                );
+                assert_eq!(
+                    evaluation.eval_at_point(point, &domain.barycentric_weights()),
+                    expected
+                );
+            }
+        }
+    }
+
     #[test]
     pub fn test_get_at_circle_evaluation() {
         let domain = CanonicCoset::new(7).circle_domain();
@@ -226,4 +449,154 @@ mod tests {
             assert_eq!(sub_eval[i], circle_evaluation.get_at(coset.index_at(i)));
         }
     }
+
+    #[test]
+    fn test_natural_order_roundtrip() {
+        let coset = CanonicCoset::new(5);
+        let values = (0..coset.size()).map(|i| m31!(i as u32)).collect_vec();
+
+        let cpu_evaluation = CpuCircleEvaluation::from_natural_order(coset, values.clone());
+        let simd_evaluation =
+            CircleEvaluation::<SimdBackend, BaseField, BitReversedOrder>::from_natural_order(
+                coset,
+                values.iter().copied().collect(),
+            );
+
+        assert_eq!(cpu_evaluation.to_natural_order(), values);
+        assert_eq!(simd_evaluation.to_natural_order(), values);
+        assert_eq!(
+            cpu_evaluation.values,
+            CpuCircleEvaluation::<_, NaturalOrder>::new(coset.circle_domain(), values)
+                .bit_reverse()
+                .values
+        );
+    }
+
+    #[test]
+    fn test_pointwise_ops() {
+        let domain = CanonicCoset::new(4).circle_domain();
+        let lhs = CpuCircleEvaluation::<_, BitReversedOrder>::new(
+            domain,
+            (0..domain.size()).map(|i| m31!((i * i) as u32)).collect(),
+        );
+        let rhs = CpuCircleEvaluation::<_, BitReversedOrder>::new(
+            domain,
+            (0..domain.size()).map(|i| m31!(i as u32 + 7)).collect(),
+        );
+
+        let sum = &lhs + &rhs;
+        let difference = &lhs - &rhs;
+        let product = lhs.clone() * rhs.clone();
+
+        for result in [&sum, &difference, &product] {
+            assert_eq!(result.domain, domain);
+        }
+        for i in 0..domain.size() {
+            assert_eq!(sum.values[i], lhs.values[i] + rhs.values[i]);
+            assert_eq!(difference.values[i], lhs.values[i] - rhs.values[i]);
+            assert_eq!(product.values[i], lhs.values[i] * rhs.values[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Evaluations are over different domains")]
+    fn test_pointwise_ops_domain_mismatch() {
+        let eval = |domain: CircleDomain| {
+            CpuCircleEvaluation::<_, BitReversedOrder>::new(
+                domain,
+                (0..domain.size()).map(|i| m31!(i as u32)).collect(),
+            )
+        };
+        let domain = CanonicCoset::new(4).circle_domain();
+
+        let _ = eval(domain) + eval(domain.shift(CirclePointIndex::generator()));
+    }
+
+    #[test]
+    fn test_stack_columns() {
+        let eval = |log_size: u32| {
+            let domain = CanonicCoset::new(log_size).circle_domain();
+            let values = (0..domain.size()).map(|i| m31!(i as u32)).collect();
+            CpuCircleEvaluation::<_, BitReversedOrder>::new(domain, values)
+        };
+
+        let columns = stack_columns(vec![eval(4), eval(4), eval(4)]).unwrap();
+        assert_eq!(columns.len(), 3);
+
+        assert_eq!(
+            stack_columns(vec![eval(4), eval(4), eval(5)]).unwrap_err(),
+            DomainMismatch {
+                index: 2,
+                expected_log_size: 4,
+                actual_log_size: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_trace_row() {
+        const LOG_SIZE: u32 = 3;
+        let domain = CanonicCoset::new(LOG_SIZE).circle_domain();
+        // Column `j` holds `10 * j + i` at row `i`.
+        let columns = (0..4)
+            .map(|j| {
+                let mut values = vec![m31!(0); domain.size()];
+                for i in 0..domain.size() {
+                    let circle_domain_index = coset_index_to_circle_domain_index(i, LOG_SIZE);
+                    values[bit_reverse_index(circle_domain_index, LOG_SIZE)] =
+                        m31!((10 * j + i) as u32);
+                }
+                CpuCircleEvaluation::<_, BitReversedOrder>::new(domain, values)
+            })
+            .collect_vec();
+
+        let row = trace_row(&columns, 3);
+
+        assert_eq!(row, [m31!(3), m31!(13), m31!(23), m31!(33)]);
+    }
+
+    #[test]
+    fn test_extend_then_restrict() {
+        let target = CanonicCoset::new(6).circle_domain();
+        let (domain, _) = target.split(1);
+        let values = (0..domain.size()).map(|i| m31!((i * i) as u32)).collect();
+        let evaluation = CpuCircleEvaluation::<_, BitReversedOrder>::new(domain, values);
+
+        let extended = evaluation.extend(target);
+
+        assert_eq!(extended.domain, target);
+        for index in domain.iter_indices() {
+            assert_eq!(extended.get_at(index), evaluation.get_at(index));
+        }
+        assert_eq!(evaluation.extend(domain).values, evaluation.values);
+    }
+
+    #[test]
+    fn test_shift() {
+        let domain = CanonicCoset::new(5).circle_domain();
+        let values = (0..domain.size()).map(|i| m31!((i * i) as u32)).collect();
+        let evaluation = CpuCircleEvaluation::<_, BitReversedOrder>::new(domain, values);
+        let poly = evaluation.clone().interpolate();
+        let step = domain.half_coset.step_size;
+
+        let full_period_shift = evaluation.shift(step * domain.half_coset.size());
+        let permuting_shift = evaluation.shift(step * 3);
+        let general_shift = evaluation.shift(CirclePointIndex::generator() * 5);
+
+        assert_eq!(full_period_shift.domain, domain);
+        assert_eq!(full_period_shift.values, evaluation.values);
+        for shifted in [permuting_shift, general_shift] {
+            assert_eq!(shifted.values, poly.evaluate(shifted.domain).values);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Target domain does not contain the evaluation domain")]
+    fn test_extend_onto_disjoint_domain_fails() {
+        let domain = CanonicCoset::new(4).circle_domain();
+        let values = (0..domain.size()).map(|i| m31!(i as u32)).collect();
+        let evaluation = CpuCircleEvaluation::<_, BitReversedOrder>::new(domain, values);
+
+        evaluation.extend(CanonicCoset::new(5).circle_domain());
+    }
 }
@@ -7,7 +7,9 @@ mod secure_poly;
 
 pub use canonic::CanonicCoset;
 pub use domain::{CircleDomain, MAX_CIRCLE_DOMAIN_LOG_SIZE};
-pub use evaluation::{CircleEvaluation, CosetSubEvaluation};
+pub use evaluation::{
+    stack_columns, trace_row, CircleEvaluation, CosetSubEvaluation, DomainMismatch, TreeColumns,
+};
 pub use ops::PolyOps;
 pub use poly::CirclePoly;
 pub use secure_poly::{SecureCirclePoly, SecureEvaluation};
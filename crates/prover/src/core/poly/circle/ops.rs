@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-use super::{CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly};
+use super::{CanonicCoset, CircleDomain, CircleEvaluation, CirclePoly, SecureCirclePoly};
 use crate::core::backend::Col;
 use crate::core::circle::{CirclePoint, Coset};
 use crate::core::fields::m31::BaseField;
@@ -44,6 +44,21 @@ pub trait PolyOps: FieldOps<BaseField> + Sized {
     /// Used by the [`CirclePoly::eval_at_point()`] function.
     fn eval_at_point(poly: &CirclePoly<Self>, point: CirclePoint<SecureField>) -> SecureField;
 
+    /// Evaluates the secure polynomial at each of `points`.
+    /// Used by the [`SecureCirclePoly::eval_at_points()`] function.
+    ///
+    /// Backends may share work across the points and coordinate polynomials, rather than
+    /// evaluating each coordinate polynomial at each point.
+    fn eval_secure_at_points(
+        poly: &SecureCirclePoly<Self>,
+        points: &[CirclePoint<SecureField>],
+    ) -> Vec<SecureField> {
+        points
+            .iter()
+            .map(|&point| poly.eval_at_point(point))
+            .collect()
+    }
+
     /// Extends the polynomial to a larger degree bound.
     /// Used by the [`CirclePoly::extend()`] function.
     fn extend(poly: &CirclePoly<Self>, log_size: u32) -> CirclePoly<Self>;
@@ -1,13 +1,16 @@
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
+use itertools::Itertools;
+
 use super::{CircleDomain, CircleEvaluation, CirclePoly, PolyOps};
-use crate::core::backend::CpuBackend;
+use crate::core::backend::{Column, CpuBackend};
 use crate::core::circle::CirclePoint;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
 use crate::core::fields::secure_column::{SecureColumnByCoords, SECURE_EXTENSION_DEGREE};
 use crate::core::fields::FieldOps;
+use crate::core::poly::line::LinePoly;
 use crate::core::poly::twiddles::TwiddleTree;
 use crate::core::poly::BitReversedOrder;
 
@@ -30,6 +33,12 @@ impl<B: PolyOps> SecureCirclePoly<B> {
         ]
     }
 
+    /// Evaluates the polynomial at each of `points`, sharing work across the points where the
+    /// backend allows it. Equivalent to [`Self::eval_at_point()`] on each point.
+    pub fn eval_at_points(&self, points: &[CirclePoint<SecureField>]) -> Vec<SecureField> {
+        B::eval_secure_at_points(self, points)
+    }
+
     pub fn log_size(&self) -> u32 {
         self[0].log_size()
     }
@@ -47,6 +56,59 @@ impl<B: PolyOps> SecureCirclePoly<B> {
     pub fn into_coordinate_polys(self) -> [CirclePoly<B>; SECURE_EXTENSION_DEGREE] {
         self.0
     }
+
+    /// Folds the polynomial with the folding challenge `alpha`, following the circle to line
+    /// folding step of circle FRI.
+    ///
+    /// Writing the polynomial as `2f(x, y) = f0(x) + y * f1(x)`, the fold is the random linear
+    /// combination `f0(x) + alpha * f1(x)` of its even and odd parts, a polynomial in `x` of half
+    /// the size. It takes the same value on each point and its conjugate, so the fold maps an
+    /// evaluation on a [CircleDomain] to an evaluation on the [LineDomain] of its `x` coordinates.
+    /// This matches the evaluations folded by [`FriOps::fold_circle_into_line()`] into a zero line
+    /// evaluation. Folding undoes [`Self::unfold()`], up to the factor of two above.
+    ///
+    /// [LineDomain]: crate::core::poly::line::LineDomain
+    /// [`FriOps::fold_circle_into_line()`]: crate::core::fri::FriOps::fold_circle_into_line
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is constant, i.e. has a log size of zero.
+    pub fn fold_circle(&self, alpha: SecureField) -> LinePoly {
+        assert!(self.log_size() > 0, "Cannot fold a constant polynomial");
+        let coords = self.0.each_ref().map(|poly| poly.coeffs.to_cpu());
+        let coeff = |i: usize| SecureField::from_m31_array(coords.each_ref().map(|c| c[i]));
+
+        // The `y` term is the least significant bit of the FFT basis index, the other bits index
+        // the line polynomial basis in `x`.
+        let folded_coeffs = (0..1 << (self.log_size() - 1))
+            .map(|i| {
+                let folded = coeff(2 * i) + alpha * coeff(2 * i + 1);
+                folded + folded
+            })
+            .collect();
+        LinePoly::from_ordered_coefficients(folded_coeffs)
+    }
+
+    /// Returns the polynomial `even(x) + y * odd(x)`.
+    ///
+    /// Folding the result with `alpha` gives `2 * (even + alpha * odd)`, see
+    /// [`Self::fold_circle()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `even` and `odd` have different sizes.
+    pub fn unfold(even: &LinePoly, odd: &LinePoly) -> Self {
+        assert_eq!(even.len(), odd.len());
+        let coeffs = even
+            .clone()
+            .into_ordered_coefficients()
+            .into_iter()
+            .interleave(odd.clone().into_ordered_coefficients())
+            .collect_vec();
+        SecureCirclePoly(std::array::from_fn(|coord| {
+            CirclePoly::new(coeffs.iter().map(|c| c.to_m31_array()[coord]).collect())
+        }))
+    }
 }
 
 impl<B: FieldOps<BaseField>> Deref for SecureCirclePoly<B> {
@@ -128,3 +190,114 @@ impl<EvalOrder> From<CircleEvaluation<CpuBackend, SecureField, EvalOrder>>
         Self::new(evaluation.domain, evaluation.values.into_iter().collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::SecureCirclePoly;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{Col, CpuBackend};
+    use crate::core::circle::{CirclePoint, SECURE_FIELD_CIRCLE_GEN};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::FieldExpOps;
+    use crate::core::poly::circle::{CirclePoly, PolyOps};
+    use crate::core::poly::line::LinePoly;
+
+    fn random_poly<B: PolyOps>(rng: &mut SmallRng, log_size: u32) -> SecureCirclePoly<B> {
+        SecureCirclePoly(std::array::from_fn(|_| {
+            CirclePoly::new(
+                (0..1 << log_size)
+                    .map(|_| rng.gen::<BaseField>())
+                    .collect::<Col<B, BaseField>>(),
+            )
+        }))
+    }
+
+    fn assert_eval_at_points_matches_eval_at_point<B: PolyOps>(log_size: u32) {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let poly = random_poly::<B>(&mut rng, log_size);
+        let points = [0; 3].map(|_| SECURE_FIELD_CIRCLE_GEN.mul(rng.gen::<u128>()));
+
+        let evals = poly.eval_at_points(&points);
+
+        let expected = points.map(|point| poly.eval_at_point(point));
+        assert_eq!(evals, expected);
+    }
+
+    #[test]
+    fn test_eval_at_points() {
+        for log_size in [0, 1, 5] {
+            assert_eval_at_points_matches_eval_at_point::<CpuBackend>(log_size);
+        }
+        for log_size in [5, 10] {
+            assert_eval_at_points_matches_eval_at_point::<SimdBackend>(log_size);
+        }
+    }
+
+    #[test]
+    fn test_eval_at_points_empty() {
+        let poly = random_poly::<CpuBackend>(&mut SmallRng::seed_from_u64(0), 3);
+
+        assert!(poly.eval_at_points(&[] as &[CirclePoint<_>]).is_empty());
+    }
+
+    fn random_line_poly(rng: &mut SmallRng, log_size: u32) -> LinePoly {
+        LinePoly::new((0..1 << log_size).map(|_| rng.gen()).collect())
+    }
+
+    #[test]
+    fn test_fold_circle() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let poly = random_poly::<CpuBackend>(&mut rng, 5);
+        let alpha = rng.gen();
+
+        let folded = poly.fold_circle(alpha);
+
+        assert_eq!(folded.len(), 1 << 4);
+        for _ in 0..3 {
+            let point = SECURE_FIELD_CIRCLE_GEN.mul(rng.gen::<u128>());
+            let [f_p, f_neg_p] = [point, point.conjugate()].map(|p| poly.eval_at_point(p));
+            let f0_px = f_p + f_neg_p;
+            let f1_px = (f_p - f_neg_p) * point.y.inverse();
+            assert_eq!(folded.eval_at_point(point.x), f0_px + alpha * f1_px);
+        }
+    }
+
+    #[test]
+    fn test_unfold() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let even = random_line_poly(&mut rng, 4);
+        let odd = random_line_poly(&mut rng, 4);
+
+        let unfolded = SecureCirclePoly::<CpuBackend>::unfold(&even, &odd);
+
+        assert_eq!(unfolded.log_size(), 5);
+        for _ in 0..3 {
+            let point = SECURE_FIELD_CIRCLE_GEN.mul(rng.gen::<u128>());
+            assert_eq!(
+                unfolded.eval_at_point(point),
+                even.eval_at_point(point.x) + point.y * odd.eval_at_point(point.x)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fold_circle_of_unfold() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let even = random_line_poly(&mut rng, 4);
+        let odd = random_line_poly(&mut rng, 4);
+        let alpha: SecureField = rng.gen();
+
+        let folded = SecureCirclePoly::<CpuBackend>::unfold(&even, &odd).fold_circle(alpha);
+
+        assert_eq!(folded.len(), 1 << 4);
+        for _ in 0..3 {
+            let x = SECURE_FIELD_CIRCLE_GEN.mul(rng.gen::<u128>()).x;
+            let expected = even.eval_at_point(x) + alpha * odd.eval_at_point(x);
+            assert_eq!(folded.eval_at_point(x), expected + expected);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::iter::zip;
 use std::ops::Deref;
 use std::{array, mem};
 
@@ -12,9 +13,8 @@ use super::fields::secure_column::SECURE_EXTENSION_DEGREE;
 use super::fri::FriVerificationError;
 use super::pcs::CommitmentSchemeProof;
 use super::vcs::ops::MerkleHasher;
-use crate::constraint_framework::PREPROCESSED_TRACE_IDX;
-use crate::core::channel::Channel;
-use crate::core::circle::CirclePoint;
+use crate::constraint_framework::{ORIGINAL_TRACE_IDX, PREPROCESSED_TRACE_IDX};
+use crate::core::channel::{ChannelInput, ChannelScriptError};
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
 use crate::core::fri::{FriLayerProof, FriProof};
@@ -36,10 +36,41 @@ pub fn prove<B: BackendForChannel<MC>, MC: MerkleChannel>(
         components: components.to_vec(),
         n_preprocessed_columns,
     };
+    // The verifier reads the mask values of the components in the order of the committed columns,
+    // so reordering the components after committing to their trace breaks the proof.
+    debug_assert!(
+        zip(
+            commitment_scheme.trees.iter(),
+            component_provers.components().column_log_sizes().iter()
+        )
+        .skip(ORIGINAL_TRACE_IDX)
+        .all(|(tree, log_sizes)| tree
+            .polynomials
+            .iter()
+            .map(|poly| poly.log_size())
+            .eq(log_sizes.iter().copied())),
+        "The components are not in the order of their committed trace columns."
+    );
+    debug_assert_eq!(
+        component_provers.components().fingerprint(),
+        component_provers
+            .components()
+            .allocation_order()
+            .fingerprint(),
+        "The components are not in the order their trace columns were allocated."
+    );
     let trace = commitment_scheme.trace();
+    let channel_script = component_provers.components().channel_mix_sequence();
+    let mut channel_script_runner = channel_script.runner::<MC>();
+    channel_script_runner.mix(
+        channel,
+        ChannelInput::Felts(component_provers.components().claimed_logup_sums()),
+    )?;
 
     // Evaluate and commit on composition polynomial.
-    let random_coeff = channel.draw_felt();
+    let [random_coeff] = channel_script_runner.draw_felts(channel)?[..] else {
+        unreachable!()
+    };
 
     let span = span!(Level::INFO, "Composition").entered();
     let span1 = span!(Level::INFO, "Generation").entered();
@@ -48,11 +79,13 @@ pub fn prove<B: BackendForChannel<MC>, MC: MerkleChannel>(
 
     let mut tree_builder = commitment_scheme.tree_builder();
     tree_builder.extend_polys(composition_poly.into_coordinate_polys());
-    tree_builder.commit(channel);
+    let composition_root = tree_builder.commit_without_mixing();
+    channel_script_runner.mix(channel, ChannelInput::Root(composition_root))?;
     span.exit();
 
     // Draw OODS point.
-    let oods_point = CirclePoint::<SecureField>::get_random_point(channel);
+    let oods_point = channel_script_runner.draw_point(channel)?;
+    channel_script_runner.finish()?;
 
     // Get mask sample points relative to oods point.
     let mut sample_points = component_provers.components().mask_points(oods_point);
@@ -92,17 +125,28 @@ pub fn verify<MC: MerkleChannel>(
         components: components.to_vec(),
         n_preprocessed_columns,
     };
-    let random_coeff = channel.draw_felt();
+    let channel_script = components.channel_mix_sequence();
+    let mut channel_script_runner = channel_script.runner::<MC>();
+    channel_script_runner.mix(
+        channel,
+        ChannelInput::Felts(components.claimed_logup_sums()),
+    )?;
+    let [random_coeff] = channel_script_runner.draw_felts(channel)?[..] else {
+        unreachable!()
+    };
 
     // Read composition polynomial commitment.
-    commitment_scheme.commit(
-        *proof.commitments.last().unwrap(),
+    let composition_root = *proof.commitments.last().unwrap();
+    commitment_scheme.commit_without_mixing(
+        composition_root,
         &[components.composition_log_degree_bound(); SECURE_EXTENSION_DEGREE],
-        channel,
     );
+    channel_script_runner.mix(channel, ChannelInput::Root(composition_root))?;
 
     // Draw OODS point.
-    let oods_point = CirclePoint::<SecureField>::get_random_point(channel);
+    let oods_point = channel_script_runner.draw_point(channel)?;
+    channel_script_runner.finish()?;
+    components.validate_oods_point(oods_point)?;
 
     // Get mask sample points relative to oods point.
     let mut sample_points = components.mask_points(oods_point);
@@ -126,6 +170,26 @@ pub fn verify<MC: MerkleChannel>(
     commitment_scheme.verify_values(sample_points, proof.0, channel)
 }
 
+/// Like [verify], but first rejects a proof whose claimed logup sums don't cancel out, see
+/// [`Components::verify_logup_balance()`].
+///
+/// Only for AIRs whose lookups balance among `components`, i.e. not against public inputs.
+pub fn verify_with_logup_balance<MC: MerkleChannel>(
+    components: &[&dyn Component],
+    channel: &mut MC::C,
+    commitment_scheme: &mut CommitmentSchemeVerifier<MC>,
+    proof: StarkProof<MC::H>,
+) -> Result<(), VerificationError> {
+    Components {
+        components: components.to_vec(),
+        n_preprocessed_columns: commitment_scheme.trees[PREPROCESSED_TRACE_IDX]
+            .column_log_sizes
+            .len(),
+    }
+    .verify_logup_balance()?;
+    verify(components, channel, commitment_scheme, proof)
+}
+
 /// Error when the sampled values have an invalid structure.
 #[derive(Clone, Copy, Debug)]
 pub struct InvalidOodsSampleStructure;
@@ -134,6 +198,8 @@ pub struct InvalidOodsSampleStructure;
 pub enum ProvingError {
     #[error("Constraints not satisfied.")]
     ConstraintsNotSatisfied,
+    #[error(transparent)]
+    ChannelScript(#[from] ChannelScriptError),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -142,6 +208,8 @@ pub enum VerificationError {
     InvalidStructure(String),
     #[error("{0} lookup values do not match.")]
     InvalidLookup(String),
+    #[error("The claimed logup sums of the components do not cancel out.")]
+    UnbalancedLogup,
     #[error(transparent)]
     Merkle(#[from] MerkleVerificationError),
     #[error(
@@ -149,10 +217,14 @@ pub enum VerificationError {
     (DEEP-ALI failure)."
     )]
     OodsNotMatching,
+    #[error("The OODS point lies in the trace domain of log size {0}.")]
+    OodsPointInDomain(u32),
     #[error(transparent)]
     Fri(#[from] FriVerificationError),
     #[error("Proof of work verification failed.")]
     ProofOfWork,
+    #[error(transparent)]
+    ChannelScript(#[from] ChannelScriptError),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -345,12 +417,151 @@ impl<H: MerkleHasher> SizeEstimate for StarkProof<H> {
 
 #[cfg(test)]
 mod tests {
-    use num_traits::One;
+    use num_traits::{One, Zero};
 
+    use crate::constraint_framework::{FrameworkComponent, TraceLocationAllocator};
+    use crate::core::air::Components;
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::{BackendForChannel, Col};
+    use crate::core::channel::{Blake2sChannel, Channel, ChannelInput, ChannelOp, MerkleChannel};
     use crate::core::fields::m31::BaseField;
     use crate::core::fields::qm31::SecureField;
     use crate::core::fields::secure_column::SECURE_EXTENSION_DEGREE;
-    use crate::core::prover::SizeEstimate;
+    use crate::core::pcs::{CommitmentSchemeProver, PcsConfig, TreeVec};
+    use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, PolyOps};
+    use crate::core::poly::BitReversedOrder;
+    use crate::core::proof_of_work::GrindOps;
+    use crate::core::prover::{prove, SizeEstimate};
+    use crate::core::vcs::blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher};
+    use crate::core::vcs::ops::MerkleHasher;
+    use crate::framework_eval;
+
+    /// A [Blake2sChannel] that records the operations run on it.
+    ///
+    /// Drawing a random point records the draw of its parameter, i.e. `ChannelOp::DrawFelts(1)`.
+    #[derive(Clone, Default)]
+    struct RecordingChannel {
+        inner: Blake2sChannel,
+        ops: Vec<ChannelOp>,
+    }
+
+    impl Channel for RecordingChannel {
+        const BYTES_PER_HASH: usize = Blake2sChannel::BYTES_PER_HASH;
+
+        fn trailing_zeros(&self) -> u32 {
+            self.inner.trailing_zeros()
+        }
+
+        fn mix_felts(&mut self, felts: &[SecureField]) {
+            self.ops.push(ChannelOp::MixFelts(felts.len()));
+            self.inner.mix_felts(felts);
+        }
+
+        fn mix_u64(&mut self, value: u64) {
+            self.ops.push(ChannelOp::MixU64);
+            self.inner.mix_u64(value);
+        }
+
+        fn draw_felt(&mut self) -> SecureField {
+            self.ops.push(ChannelOp::DrawFelts(1));
+            self.inner.draw_felt()
+        }
+
+        fn draw_felts(&mut self, n_felts: usize) -> Vec<SecureField> {
+            self.ops.push(ChannelOp::DrawFelts(n_felts));
+            self.inner.draw_felts(n_felts)
+        }
+
+        fn draw_random_bytes(&mut self) -> Vec<u8> {
+            self.inner.draw_random_bytes()
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingMerkleChannel;
+
+    impl MerkleChannel for RecordingMerkleChannel {
+        type C = RecordingChannel;
+        type H = Blake2sMerkleHasher;
+
+        fn mix_root(channel: &mut Self::C, root: <Self::H as MerkleHasher>::Hash) {
+            channel.ops.push(ChannelOp::MixRoot);
+            Blake2sMerkleChannel::mix_root(&mut channel.inner, root);
+        }
+    }
+
+    impl GrindOps<RecordingChannel> for SimdBackend {
+        fn grind(channel: &RecordingChannel, pow_bits: u32) -> u64 {
+            <SimdBackend as GrindOps<Blake2sChannel>>::grind(&channel.inner, pow_bits)
+        }
+    }
+
+    impl BackendForChannel<RecordingMerkleChannel> for SimdBackend {}
+
+    const LOG_SIZE: u32 = 5;
+
+    framework_eval!(ConstantEval, LOG_SIZE, LOG_SIZE + 1, |eval| {
+        let (curr, next) = eval.next_trace_mask_and_next();
+        eval.add_constraint(curr - next);
+    });
+
+    framework_eval!(LargeConstantEval, LOG_SIZE + 1, LOG_SIZE + 2, |eval| {
+        let (curr, next) = eval.next_trace_mask_and_next();
+        eval.add_constraint(curr - next);
+    });
+
+    fn constant_column(
+        log_size: u32,
+    ) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+        let column =
+            Col::<SimdBackend, BaseField>::from_iter(vec![BaseField::from(3); 1 << log_size]);
+        CircleEvaluation::new(CanonicCoset::new(log_size).circle_domain(), column)
+    }
+
+    #[test]
+    fn test_prove_and_verify_follow_channel_mix_sequence() {
+        let component = FrameworkComponent::new(
+            &mut TraceLocationAllocator::default(),
+            ConstantEval,
+            (SecureField::zero(), None),
+        );
+        let trace = TreeVec::new(vec![vec![], vec![constant_column(LOG_SIZE)]]);
+        let config = PcsConfig::default();
+        let script = Components {
+            components: vec![&component],
+            n_preprocessed_columns: 0,
+        }
+        .channel_mix_sequence();
+        let mut script_channel = RecordingChannel::default();
+        script
+            .run::<RecordingMerkleChannel>(
+                &mut script_channel,
+                [
+                    ChannelInput::Felts(vec![]),
+                    ChannelInput::Root(Default::default()),
+                ],
+            )
+            .unwrap();
+        let mut prover_channel = RecordingChannel::default();
+        let mut verifier_channel = RecordingChannel::default();
+
+        let proof = component
+            .prove_standalone::<RecordingMerkleChannel>(config, trace, &mut prover_channel)
+            .unwrap();
+        component
+            .verify_standalone::<RecordingMerkleChannel>(config, proof, &mut verifier_channel)
+            .unwrap();
+
+        // The composition stage follows the commitments to the preprocessed and original traces.
+        let composition_stage = 2..2 + script_channel.ops.len();
+        assert_eq!(prover_channel.ops[..2], [ChannelOp::MixRoot; 2]);
+        assert_eq!(
+            prover_channel.ops[composition_stage.clone()],
+            script_channel.ops
+        );
+        assert_eq!(verifier_channel.ops[composition_stage], script_channel.ops);
+        assert_eq!(prover_channel.ops, verifier_channel.ops);
+    }
 
     #[test]
     fn test_base_field_size_estimate() {
@@ -364,4 +575,32 @@ mod tests {
             4 * SECURE_EXTENSION_DEGREE
         );
     }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(
+        expected = "The components are not in the order of their committed trace columns."
+    )]
+    fn test_prove_reordered_components() {
+        let allocator = &mut TraceLocationAllocator::default();
+        let component =
+            FrameworkComponent::new(allocator, ConstantEval, (SecureField::zero(), None));
+        let large_component =
+            FrameworkComponent::new(allocator, LargeConstantEval, (SecureField::zero(), None));
+        let config = PcsConfig::default();
+        let twiddles = SimdBackend::precompute_twiddles(
+            CanonicCoset::new(LOG_SIZE + 2 + config.fri_config.log_blowup_factor)
+                .circle_domain()
+                .half_coset,
+        );
+        let channel = &mut Blake2sChannel::default();
+        let mut commitment_scheme =
+            CommitmentSchemeProver::<SimdBackend, Blake2sMerkleChannel>::new(config, &twiddles);
+        commitment_scheme.tree_builder().commit(channel);
+        let mut tree_builder = commitment_scheme.tree_builder();
+        tree_builder.extend_evals([constant_column(LOG_SIZE), constant_column(LOG_SIZE + 1)]);
+        tree_builder.commit(channel);
+
+        let _ = prove(&[&large_component, &component], channel, commitment_scheme);
+    }
 }
@@ -1,7 +1,15 @@
 use super::backend::cpu::CpuCircleEvaluation;
-use super::channel::Blake2sChannel;
+use super::backend::simd::SimdBackend;
+use super::backend::BackendForChannel;
+use super::channel::{Blake2sChannel, MerkleChannel};
 use super::fields::m31::BaseField;
 use super::fields::qm31::SecureField;
+use super::pcs::{PcsConfig, TreeVec};
+use super::poly::circle::CircleEvaluation;
+use super::poly::BitReversedOrder;
+use super::prover::VerificationError;
+use super::ColumnVec;
+use crate::constraint_framework::{FrameworkComponent, FrameworkEval};
 
 pub fn secure_eval_to_base_eval<EvalOrder>(
     eval: &CpuCircleEvaluation<SecureField, EvalOrder>,
@@ -15,3 +23,23 @@ pub fn secure_eval_to_base_eval<EvalOrder>(
 pub fn test_channel() -> Blake2sChannel {
     Blake2sChannel::default()
 }
+
+/// Proves `component` on `trace` with the Merkle channel `MC`, see
+/// [FrameworkComponent::prove_standalone], and verifies the proof.
+///
+/// # Panics
+///
+/// Panics if proving fails.
+pub fn prove_verify_roundtrip<MC: MerkleChannel, E: FrameworkEval + Sync>(
+    component: &FrameworkComponent<E>,
+    trace: TreeVec<ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>>,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    let config = PcsConfig::default();
+    let proof = component
+        .prove_standalone::<MC>(config, trace, &mut MC::C::default())
+        .expect("Failed to prove");
+    component.verify_standalone::<MC>(config, proof, &mut MC::C::default())
+}
@@ -165,16 +165,22 @@ pub fn bit_reverse_coset_to_circle_domain_order<T>(v: &mut [T]) {
     }
 }
 
-pub fn generate_secure_powers(felt: SecureField, n_powers: usize) -> Vec<SecureField> {
-    (0..n_powers)
+/// Returns `[1, base, base^2, ..., base^(n - 1)]`, computed by successive multiplication.
+pub fn powers_of(base: SecureField, n: usize) -> Vec<SecureField> {
+    (0..n)
         .scan(SecureField::one(), |acc, _| {
             let res = *acc;
-            *acc *= felt;
+            *acc *= base;
             Some(res)
         })
         .collect()
 }
 
+#[deprecated(note = "renamed to `powers_of`")]
+pub fn generate_secure_powers(felt: SecureField, n_powers: usize) -> Vec<SecureField> {
+    powers_of(felt, n_powers)
+}
+
 /// Securely combines the given values using the given random alpha and z.
 /// Alpha and z should be secure field elements for soundness.
 pub fn shifted_secure_combination<F: Copy, EF>(values: &[F], alpha: EF, z: EF) -> EF
@@ -218,11 +224,11 @@ mod tests {
     }
 
     #[test]
-    fn generate_secure_powers_works() {
+    fn powers_of_works() {
         let felt = qm31!(1, 2, 3, 4);
         let n_powers = 10;
 
-        let powers = super::generate_secure_powers(felt, n_powers);
+        let powers = super::powers_of(felt, n_powers);
 
         assert_eq!(powers.len(), n_powers);
         assert_eq!(powers[0], SecureField::one());
@@ -231,11 +237,33 @@ mod tests {
     }
 
     #[test]
-    fn generate_empty_secure_powers_works() {
+    #[allow(deprecated)]
+    fn generate_secure_powers_matches_powers_of() {
+        let felt = qm31!(1, 2, 3, 4);
+
+        assert_eq!(
+            super::generate_secure_powers(felt, 10),
+            super::powers_of(felt, 10)
+        );
+    }
+
+    #[test]
+    fn powers_of_matches_pow() {
+        let base = qm31!(5, 6, 7, 8);
+
+        let powers = super::powers_of(base, 64);
+
+        for (i, power) in powers.into_iter().enumerate() {
+            assert_eq!(power, base.pow(i as u128));
+        }
+    }
+
+    #[test]
+    fn empty_powers_of_works() {
         let felt = qm31!(1, 2, 3, 4);
         let max_log_size = 0;
 
-        let powers = super::generate_secure_powers(felt, max_log_size);
+        let powers = super::powers_of(felt, max_log_size);
 
         assert_eq!(powers, vec![]);
     }
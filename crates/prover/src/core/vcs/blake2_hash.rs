@@ -4,11 +4,23 @@ use blake2::{Blake2s256, Digest};
 use bytemuck::{Pod, Zeroable};
 use serde::{Deserialize, Serialize};
 
+use super::utils::ct_eq_bytes;
+
 // Wrapper for the blake2s hash type.
 #[repr(C, align(32))]
 #[derive(Clone, Copy, PartialEq, Default, Eq, Pod, Zeroable, Deserialize, Serialize)]
 pub struct Blake2sHash(pub [u8; 32]);
 
+impl Blake2sHash {
+    /// Returns whether the hashes are equal, in time independent of their content.
+    ///
+    /// Unlike `==`, doesn't stop at the first differing byte, e.g. for comparing against roots
+    /// from untrusted input.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq_bytes(&self.0, &other.0)
+    }
+}
+
 impl From<Blake2sHash> for Vec<u8> {
     fn from(value: Blake2sHash) -> Self {
         Vec::from(value.0)
@@ -128,4 +140,16 @@ mod tests {
         assert_eq!(hash.to_string(), Blake2sHasher::hash(b"ab").to_string());
         assert_eq!(hash_empty.to_string(), Blake2sHasher::hash(b"").to_string());
     }
+
+    #[test]
+    fn ct_eq_test() {
+        let hash = Blake2sHasher::hash(b"a");
+        for i in [0, 17, 31] {
+            let mut other = hash;
+            other.0[i] ^= 1;
+            assert_eq!(hash.ct_eq(&other), hash == other);
+            assert!(!hash.ct_eq(&other));
+        }
+        assert!(hash.ct_eq(&Blake2sHasher::hash(b"a")));
+    }
 }
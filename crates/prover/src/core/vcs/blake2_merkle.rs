@@ -63,7 +63,7 @@ mod tests {
     use crate::core::fields::m31::BaseField;
     use crate::core::vcs::blake2_merkle::{Blake2sHash, Blake2sMerkleHasher};
     use crate::core::vcs::test_utils::prepare_merkle;
-    use crate::core::vcs::verifier::MerkleVerificationError;
+    use crate::core::vcs::verifier::{MerkleVerificationError, MerkleVerifier};
 
     #[test]
     fn test_merkle_success() {
@@ -72,6 +72,19 @@ mod tests {
         verifier.verify(&queries, values, decommitment).unwrap();
     }
 
+    #[test]
+    fn test_merkle_serde_roundtrip() {
+        let (queries, decommitment, values, verifier) = prepare_merkle::<Blake2sMerkleHasher>();
+
+        let decommitment =
+            serde_json::from_str(&serde_json::to_string(&decommitment).unwrap()).unwrap();
+        let root: Blake2sHash =
+            serde_json::from_str(&serde_json::to_string(&verifier.root).unwrap()).unwrap();
+
+        let verifier = MerkleVerifier::<Blake2sMerkleHasher>::new(root, verifier.column_log_sizes);
+        verifier.verify(&queries, values, decommitment).unwrap();
+    }
+
     #[test]
     fn test_merkle_invalid_witness() {
         let (queries, mut decommitment, values, verifier) = prepare_merkle::<Blake2sMerkleHasher>();
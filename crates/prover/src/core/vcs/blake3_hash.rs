@@ -3,11 +3,22 @@ use std::fmt;
 use serde::{Deserialize, Serialize};
 
 use crate::core::vcs::hash::Hash;
+use crate::core::vcs::utils::ct_eq_bytes;
 
 // Wrapper for the blake3 hash type.
 #[derive(Clone, Copy, PartialEq, Default, Eq, Serialize, Deserialize)]
 pub struct Blake3Hash([u8; 32]);
 
+impl Blake3Hash {
+    /// Returns whether the hashes are equal, in time independent of their content.
+    ///
+    /// Unlike `==`, doesn't stop at the first differing byte, e.g. for comparing against roots
+    /// from untrusted input.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq_bytes(&self.0, &other.0)
+    }
+}
+
 impl From<Blake3Hash> for Vec<u8> {
     fn from(value: Blake3Hash) -> Self {
         Vec::from(value.0)
@@ -99,7 +110,7 @@ impl Blake3Hasher {
 
 #[cfg(test)]
 mod tests {
-    use crate::core::vcs::blake3_hash::Blake3Hasher;
+    use crate::core::vcs::blake3_hash::{Blake3Hash, Blake3Hasher};
 
     #[test]
     fn single_hash_test() {
@@ -121,4 +132,18 @@ mod tests {
         assert_eq!(hash.to_string(), Blake3Hasher::hash(b"ab").to_string());
         assert_eq!(hash_empty.to_string(), Blake3Hasher::hash(b"").to_string())
     }
+
+    #[test]
+    fn ct_eq_test() {
+        let hash = Blake3Hasher::hash(b"a");
+        let mut bytes: [u8; 32] = hash.as_ref().try_into().unwrap();
+        for i in [0, 17, 31] {
+            bytes[i] ^= 1;
+            let other = Blake3Hash::from(&bytes[..]);
+            assert_eq!(hash.ct_eq(&other), hash == other);
+            assert!(!hash.ct_eq(&other));
+            bytes[i] ^= 1;
+        }
+        assert!(hash.ct_eq(&Blake3Hash::from(&bytes[..])));
+    }
 }
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use super::blake3_hash::{Blake3Hash, Blake3Hasher};
+use super::ops::MerkleHasher;
+use crate::core::channel::{Blake3Channel, MerkleChannel};
+use crate::core::fields::m31::BaseField;
+
+/// A [MerkleHasher] with the natural 256-bit BLAKE3 digest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct Blake3MerkleHasher;
+impl MerkleHasher for Blake3MerkleHasher {
+    type Hash = Blake3Hash;
+
+    fn hash_node(
+        children_hashes: Option<(Self::Hash, Self::Hash)>,
+        column_values: &[BaseField],
+    ) -> Self::Hash {
+        let mut hasher = Blake3Hasher::new();
+        if let Some((left, right)) = children_hashes {
+            hasher.update(left.as_ref());
+            hasher.update(right.as_ref());
+        }
+        for value in column_values {
+            hasher.update(&value.0.to_le_bytes());
+        }
+        hasher.finalize()
+    }
+}
+
+#[derive(Default)]
+pub struct Blake3MerkleChannel;
+
+impl MerkleChannel for Blake3MerkleChannel {
+    type C = Blake3Channel;
+    type H = Blake3MerkleHasher;
+
+    fn mix_root(channel: &mut Self::C, root: <Self::H as MerkleHasher>::Hash) {
+        channel.update_digest(Blake3Hasher::concat_and_hash(&channel.digest(), &root));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::Zero;
+
+    use super::Blake3MerkleHasher;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::vcs::blake3_hash::{Blake3Hash, Blake3Hasher};
+    use crate::core::vcs::ops::{MerkleHash, MerkleHasher};
+    use crate::core::vcs::test_utils::prepare_merkle;
+    use crate::core::vcs::verifier::{MerkleVerificationError, MerkleVerifier};
+
+    #[test]
+    fn test_hash_node_is_blake3_of_values() {
+        let values = [BaseField::from(1), BaseField::from(2)];
+        let hash: MerkleHash<Blake3MerkleHasher> = Blake3MerkleHasher::hash_node(None, &values);
+
+        assert_eq!(hash.as_ref().len(), 32);
+        assert_eq!(hash, Blake3Hasher::hash(&[1, 0, 0, 0, 2, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_merkle_success() {
+        let (queries, decommitment, values, verifier) = prepare_merkle::<Blake3MerkleHasher>();
+
+        verifier.verify(&queries, values, decommitment).unwrap();
+    }
+
+    #[test]
+    fn test_merkle_serde_roundtrip() {
+        let (queries, decommitment, values, verifier) = prepare_merkle::<Blake3MerkleHasher>();
+
+        let decommitment =
+            serde_json::from_str(&serde_json::to_string(&decommitment).unwrap()).unwrap();
+        let root: Blake3Hash =
+            serde_json::from_str(&serde_json::to_string(&verifier.root).unwrap()).unwrap();
+
+        let verifier = MerkleVerifier::<Blake3MerkleHasher>::new(root, verifier.column_log_sizes);
+        verifier.verify(&queries, values, decommitment).unwrap();
+    }
+
+    #[test]
+    fn test_merkle_invalid_witness() {
+        let (queries, mut decommitment, values, verifier) = prepare_merkle::<Blake3MerkleHasher>();
+        decommitment.hash_witness[4] = Blake3Hash::default();
+
+        assert_eq!(
+            verifier.verify(&queries, values, decommitment).unwrap_err(),
+            MerkleVerificationError::RootMismatch
+        );
+    }
+
+    #[test]
+    fn test_merkle_invalid_value() {
+        let (queries, decommitment, mut values, verifier) = prepare_merkle::<Blake3MerkleHasher>();
+        values[6] = BaseField::zero();
+
+        assert_eq!(
+            verifier.verify(&queries, values, decommitment).unwrap_err(),
+            MerkleVerificationError::RootMismatch
+        );
+    }
+}
@@ -4,13 +4,18 @@ pub mod blake2_hash;
 pub mod blake2_merkle;
 pub mod blake2s_ref;
 pub mod blake3_hash;
+pub mod blake3_merkle;
 pub mod hash;
 pub mod ops;
+pub mod path;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod poseidon252_merkle;
+pub mod poseidon2_hash;
 pub mod prover;
 mod utils;
 pub mod verifier;
 
 #[cfg(test)]
 mod test_utils;
+#[cfg(test)]
+mod test_vectors;
@@ -22,6 +22,9 @@ pub trait MerkleHasher: Debug + Default + Clone {
     ) -> Self::Hash;
 }
 
+/// The hash type of a [MerkleHasher]. Its size is the natural digest size of the hasher.
+pub type MerkleHash<H> = <H as MerkleHasher>::Hash;
+
 /// Trait for performing Merkle operations on a commitment scheme.
 pub trait MerkleOps<H: MerkleHasher>:
     ColumnOps<BaseField> + ColumnOps<H::Hash> + for<'de> Deserialize<'de> + Serialize
@@ -0,0 +1,546 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::ops::MerkleHasher;
+use crate::core::channel::Channel;
+use crate::core::fields::m31::BaseField;
+use crate::core::queries::Queries;
+
+/// An authentication path of a single leaf in a Merkle tree whose columns are all of the same
+/// size.
+///
+/// The leaf is the hash of the column values at its index. Only the leaf layer has column values,
+/// so inner nodes are hashes of their children alone.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MerklePath<H: MerkleHasher> {
+    /// Sibling hashes from the leaf layer up to the children of the root.
+    pub siblings: Vec<H::Hash>,
+}
+
+impl<H: MerkleHasher> MerklePath<H> {
+    /// Computes the root implied by this path for the leaf at `index` with column values `leaf`.
+    pub fn compute_root(&self, index: usize, leaf: &[BaseField]) -> H::Hash {
+        fold_siblings::<H>(index, leaf, &self.siblings)
+    }
+
+    /// Returns the depth of the tree, which is the number of siblings.
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+
+    /// Verifies that `leaf` is at `index` in the tree committed to by `root`.
+    ///
+    /// See [verify_merkle_path].
+    pub fn verify(&self, root: H::Hash, index: usize, leaf: &[BaseField]) -> bool {
+        verify_merkle_path::<H>(root, index, leaf, &self.siblings)
+    }
+}
+
+fn fold_siblings<H: MerkleHasher>(
+    mut index: usize,
+    leaf: &[BaseField],
+    siblings: &[H::Hash],
+) -> H::Hash {
+    let mut hash = H::hash_node(None, leaf);
+    for sibling in siblings {
+        let children = if index & 1 == 0 {
+            (hash, *sibling)
+        } else {
+            (*sibling, hash)
+        };
+        hash = H::hash_node(Some(children), &[]);
+        index >>= 1;
+    }
+    hash
+}
+
+/// Verifies that `leaf` is at `index` in the tree committed to by `root`, where `path` holds the
+/// sibling hashes from the leaf layer up to the children of the root.
+///
+/// The tree depth is the length of `path`, so `index` must be smaller than `2^path.len()`. An
+/// empty path is a single-leaf tree, whose root is the hash of the leaf.
+pub fn verify_merkle_path<H: MerkleHasher>(
+    root: H::Hash,
+    index: usize,
+    leaf: &[BaseField],
+    path: &[H::Hash],
+) -> bool {
+    if path.len() < usize::BITS as usize && index >> path.len() != 0 {
+        return false;
+    }
+    fold_siblings::<H>(index, leaf, path) == root
+}
+
+/// A decommitment of several leaves of a Merkle tree whose columns are all of the same size.
+///
+/// Produced by [`MerkleProver::decommit_batch()`]. Each hash of the tree appears at most once.
+///
+/// [`MerkleProver::decommit_batch()`]: super::prover::MerkleProver::decommit_batch
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchMerkleProof<H: MerkleHasher> {
+    /// Sibling hashes that are not ancestors of an opened leaf, layer by layer from the leaves up,
+    /// in ascending index order within each layer.
+    pub siblings: Vec<H::Hash>,
+}
+
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum BatchDecommitmentError {
+    #[error("No indices to decommit.")]
+    NoIndices,
+    #[error("Index {0} is out of range.")]
+    IndexOutOfRange(usize),
+    #[error("Index {0} is decommitted more than once.")]
+    DuplicateIndex(usize),
+}
+
+/// Verifies a [BatchMerkleProof] of `leaves`, given as `(index, column values)` pairs, against
+/// `root` of a tree with `2^log_size` leaves.
+///
+/// The opened nodes of each layer are walked bottom-up. A parent is reconstructed from its
+/// children, and a hash is only read from the proof when a sibling is not itself opened.
+pub fn verify_batch<H: MerkleHasher>(
+    root: H::Hash,
+    log_size: u32,
+    leaves: &[(usize, Vec<BaseField>)],
+    proof: &BatchMerkleProof<H>,
+) -> bool {
+    let mut layer = leaves
+        .iter()
+        .map(|(index, leaf)| (*index, H::hash_node(None, leaf)))
+        .sorted_by_key(|(index, _)| *index)
+        .collect_vec();
+    // The same leaf may be opened more than once, but always to the same values.
+    if layer
+        .windows(2)
+        .any(|w| w[0].0 == w[1].0 && w[0].1 != w[1].1)
+    {
+        return false;
+    }
+    layer.dedup_by_key(|(index, _)| *index);
+    if layer
+        .last()
+        .is_some_and(|(index, _)| index >> log_size != 0)
+    {
+        return false;
+    }
+
+    let mut siblings = proof.siblings.iter().copied();
+    for _ in 0..log_size {
+        let mut nodes = layer.into_iter().peekable();
+        let mut next_layer = vec![];
+        while let Some((index, hash)) = nodes.next() {
+            let children = if index % 2 == 0 {
+                let right = match nodes.next_if(|(sibling, _)| *sibling == index + 1) {
+                    Some((_, right)) => right,
+                    None => match siblings.next() {
+                        Some(right) => right,
+                        None => return false,
+                    },
+                };
+                (hash, right)
+            } else {
+                let Some(left) = siblings.next() else {
+                    return false;
+                };
+                (left, hash)
+            };
+            next_layer.push((index >> 1, H::hash_node(Some(children), &[])));
+        }
+        layer = next_layer;
+    }
+
+    siblings.next().is_none() && matches!(layer[..], [(0, hash)] if hash == root)
+}
+
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum CommitmentError {
+    #[error("Opening {0} does not match the commitment root.")]
+    RootMismatch(usize),
+    #[error("The opened indices are not the ones drawn from the channel.")]
+    IndicesMismatch,
+}
+
+/// An opening of a single leaf: its index, its column values and its authentication path.
+pub type MerkleOpening<H> = (usize, Vec<BaseField>, MerklePath<H>);
+
+/// Checks that every opening `(index, leaf, path)` authenticates against `root`.
+///
+/// Only the paths are hashed; no tree is built.
+pub fn verify_commitment<H: MerkleHasher>(
+    root: H::Hash,
+    openings: &[MerkleOpening<H>],
+) -> Result<(), CommitmentError> {
+    for (i, (index, leaf, path)) in openings.iter().enumerate() {
+        if !verify_merkle_path::<H>(root, *index, leaf, &path.siblings) {
+            return Err(CommitmentError::RootMismatch(i));
+        }
+    }
+    Ok(())
+}
+
+/// Verifies the openings of a [`MerkleProver::spot_check()`] of `n_queries` leaves against `root`
+/// of a tree with `2^log_size` leaves.
+///
+/// `channel` must be in the same state as the prover's, so that the same indices are drawn.
+///
+/// [`MerkleProver::spot_check()`]: super::prover::MerkleProver::spot_check
+pub fn verify_spot_check<H: MerkleHasher>(
+    channel: &mut impl Channel,
+    root: H::Hash,
+    log_size: u32,
+    n_queries: usize,
+    openings: &[MerkleOpening<H>],
+) -> Result<(), CommitmentError> {
+    let queries = Queries::generate(channel, log_size, n_queries);
+    if !queries.iter().eq(openings.iter().map(|(index, ..)| index)) {
+        return Err(CommitmentError::IndicesMismatch);
+    }
+    for (i, (index, leaf, path)) in openings.iter().enumerate() {
+        if path.depth() != log_size as usize || !path.verify(root, *index, leaf) {
+            return Err(CommitmentError::RootMismatch(i));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{
+        verify_batch, verify_commitment, verify_merkle_path, verify_spot_check,
+        BatchDecommitmentError, CommitmentError, MerkleOpening, MerklePath,
+    };
+    use crate::core::backend::CpuBackend;
+    use crate::core::channel::{Blake2sChannel, Channel};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::fields::qm31::SecureField;
+    use crate::core::fields::secure_column::{SecureColumnByCoords, SECURE_EXTENSION_DEGREE};
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::ops::MerkleHasher;
+    use crate::core::vcs::prover::MerkleProver;
+
+    type H = Blake2sMerkleHasher;
+
+    /// Builds the openings of all leaves of a small tree by hand.
+    fn openings() -> (<H as MerkleHasher>::Hash, Vec<MerkleOpening<H>>) {
+        let leaves = (0..4)
+            .map(|i| vec![BaseField::from(i), BaseField::from(10 * i)])
+            .collect_vec();
+        let leaf_hashes = leaves.iter().map(|l| H::hash_node(None, l)).collect_vec();
+        let node_hashes = leaf_hashes
+            .chunks(2)
+            .map(|c| H::hash_node(Some((c[0], c[1])), &[]))
+            .collect_vec();
+        let root = H::hash_node(Some((node_hashes[0], node_hashes[1])), &[]);
+
+        let openings = leaves
+            .into_iter()
+            .enumerate()
+            .map(|(i, leaf)| {
+                let siblings = vec![leaf_hashes[i ^ 1], node_hashes[(i >> 1) ^ 1]];
+                (i, leaf, MerklePath { siblings })
+            })
+            .collect();
+        (root, openings)
+    }
+
+    #[test]
+    fn test_verify_commitment() {
+        let (root, openings) = openings();
+
+        verify_commitment(root, &openings).unwrap();
+    }
+
+    #[test]
+    fn test_path_root_matches_prover_root() {
+        let (root, _) = openings();
+        let columns = [
+            (0..4).map(BaseField::from).collect_vec(),
+            (0..4).map(|i| BaseField::from(10 * i)).collect_vec(),
+        ];
+
+        let prover = MerkleProver::<CpuBackend, H>::commit(columns.iter().collect());
+
+        assert_eq!(prover.root(), root);
+    }
+
+    #[test]
+    fn test_verify_commitment_tampered_leaf() {
+        let (root, mut openings) = openings();
+        openings[2].1[0] = BaseField::from(1234);
+
+        assert_eq!(
+            verify_commitment(root, &openings).unwrap_err(),
+            CommitmentError::RootMismatch(2)
+        );
+    }
+
+    #[test]
+    fn test_verify_merkle_path() {
+        let (root, openings) = openings();
+
+        for (index, leaf, path) in &openings {
+            assert!(verify_merkle_path::<H>(root, *index, leaf, &path.siblings));
+        }
+        let (index, leaf, path) = &openings[1];
+        assert!(!verify_merkle_path::<H>(
+            root,
+            index ^ 1,
+            leaf,
+            &path.siblings
+        ));
+    }
+
+    #[test]
+    fn test_update_leaf_matches_commit() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut columns: Vec<Vec<BaseField>> = [5, 5, 3, 0]
+            .map(|log_size| (0..1 << log_size).map(|_| rng.gen()).collect())
+            .to_vec();
+        let mut prover = MerkleProver::<CpuBackend, H>::commit(columns.iter().collect());
+
+        for index in [0, 13, 31] {
+            columns[1][index] = rng.gen();
+            columns[2][index >> 2] = rng.gen();
+            let root = prover.update_leaf(index, columns.iter().collect());
+
+            let fresh = MerkleProver::<CpuBackend, H>::commit(columns.iter().collect());
+            assert_eq!(root, fresh.root());
+            assert_eq!(prover.layers, fresh.layers);
+        }
+    }
+
+    #[test]
+    fn test_update_leaf_paths_remain_valid() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut columns: [Vec<BaseField>; 2] =
+            std::array::from_fn(|_| (0..16).map(|_| rng.gen()).collect());
+        let mut prover = MerkleProver::<CpuBackend, H>::commit(columns.iter().collect());
+
+        columns[0][6] = rng.gen();
+        let root = prover.update_leaf(6, columns.iter().collect());
+
+        for index in 0..16 {
+            let leaf = columns.iter().map(|column| column[index]).collect_vec();
+            let path = prover.decommit_path(index);
+            assert!(verify_merkle_path::<H>(root, index, &leaf, &path.siblings));
+        }
+    }
+
+    #[test]
+    fn test_verify_merkle_path_wrong_depth() {
+        let (root, openings) = openings();
+        let (_, leaf, path) = &openings[3];
+
+        // Index 7 implies a deeper tree than the path.
+        assert!(!verify_merkle_path::<H>(root, 7, leaf, &path.siblings));
+        assert!(!verify_merkle_path::<H>(root, 3, leaf, &path.siblings[..1]));
+    }
+
+    #[test]
+    fn test_merkle_path_verify_infers_depth() {
+        let (root, openings) = openings();
+        let (index, leaf, path) = &openings[0];
+
+        assert_eq!(path.depth(), 2);
+        assert!(path.verify(root, *index, leaf));
+        // `2^depth` is out of range, even though it folds the path like index 0.
+        assert!(!path.verify(root, 1 << path.depth(), leaf));
+    }
+
+    #[test]
+    fn test_verify_merkle_path_single_leaf() {
+        let leaf = [BaseField::from(5)];
+        let root = H::hash_node(None, &leaf);
+
+        assert!(verify_merkle_path::<H>(root, 0, &leaf, &[]));
+        assert!(!verify_merkle_path::<H>(root, 1, &leaf, &[]));
+    }
+
+    #[test]
+    fn test_commit_secure_leaf_opening() {
+        const LOG_SIZE: u32 = 4;
+        let mut rng = SmallRng::seed_from_u64(0);
+        let columns: [SecureColumnByCoords<CpuBackend>; 2] =
+            std::array::from_fn(|_| (0..1 << LOG_SIZE).map(|_| rng.gen()).collect());
+        let prover = MerkleProver::<CpuBackend, H>::commit_secure(&columns.each_ref());
+        let index = 5;
+
+        let leaf = columns
+            .iter()
+            .flat_map(|column| column.at(index).to_m31_array())
+            .collect_vec();
+        let path = prover.decommit_path(index);
+
+        assert!(path.verify(prover.root(), index, &leaf));
+        for (coords, column) in leaf.chunks_exact(SECURE_EXTENSION_DEGREE).zip(&columns) {
+            assert_eq!(
+                SecureField::from_m31_array(coords.try_into().unwrap()),
+                column.at(index)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Secure columns must all be of the same size")]
+    fn test_commit_secure_different_sizes() {
+        let columns = [2, 4].map(|size| {
+            (0..size)
+                .map(SecureField::from)
+                .collect::<SecureColumnByCoords<CpuBackend>>()
+        });
+
+        MerkleProver::<CpuBackend, H>::commit_secure(&columns.each_ref());
+    }
+
+    #[test]
+    fn test_batch_decommitment() {
+        const LOG_SIZE: u32 = 6;
+        let columns = (0..3)
+            .map(|c| {
+                (0..1 << LOG_SIZE)
+                    .map(|i| BaseField::from(c * 100 + i))
+                    .collect_vec()
+            })
+            .collect_vec();
+        let prover = MerkleProver::<CpuBackend, H>::commit(columns.iter().collect());
+        let indices = [8, 9, 10, 11, 13, 40];
+        let leaves = indices
+            .iter()
+            .map(|&i| (i, columns.iter().map(|c| c[i]).collect_vec()))
+            .collect_vec();
+
+        let proof = prover.decommit_batch(&indices).unwrap();
+
+        assert!(verify_batch(prover.root(), LOG_SIZE, &leaves, &proof));
+        let independent_paths_size = indices
+            .iter()
+            .map(|&i| prover.decommit_batch(&[i]).unwrap().siblings.len())
+            .sum::<usize>();
+        assert_eq!(independent_paths_size, indices.len() * LOG_SIZE as usize);
+        assert!(proof.siblings.len() < independent_paths_size / 2);
+    }
+
+    #[test]
+    fn test_batch_decommitment_invalid() {
+        const LOG_SIZE: u32 = 4;
+        let columns = [(0..1 << LOG_SIZE).map(BaseField::from).collect_vec()];
+        let prover = MerkleProver::<CpuBackend, H>::commit(columns.iter().collect());
+        let indices = [2, 3, 9];
+        let leaves = indices
+            .iter()
+            .map(|&i| (i, vec![columns[0][i]]))
+            .collect_vec();
+        let proof = prover.decommit_batch(&indices).unwrap();
+
+        let mut tampered_leaves = leaves.clone();
+        tampered_leaves[2].1[0] = BaseField::from(1234);
+        assert!(!verify_batch(
+            prover.root(),
+            LOG_SIZE,
+            &tampered_leaves,
+            &proof
+        ));
+        let mut short_proof = proof.clone();
+        short_proof.siblings.pop();
+        assert!(!verify_batch(
+            prover.root(),
+            LOG_SIZE,
+            &leaves,
+            &short_proof
+        ));
+        let mut long_proof = proof;
+        long_proof.siblings.push(H::hash_node(None, &[]));
+        assert!(!verify_batch(prover.root(), LOG_SIZE, &leaves, &long_proof));
+    }
+
+    #[test]
+    fn test_batch_decommitment_invalid_indices() {
+        const LOG_SIZE: u32 = 4;
+        let columns = [(0..1 << LOG_SIZE).map(BaseField::from).collect_vec()];
+        let prover = MerkleProver::<CpuBackend, H>::commit(columns.iter().collect());
+
+        assert_eq!(
+            prover.decommit_batch(&[]).unwrap_err(),
+            BatchDecommitmentError::NoIndices
+        );
+        assert_eq!(
+            prover.decommit_batch(&[3, 1 << LOG_SIZE]).unwrap_err(),
+            BatchDecommitmentError::IndexOutOfRange(1 << LOG_SIZE)
+        );
+        assert_eq!(
+            prover.decommit_batch(&[5, 2, 5]).unwrap_err(),
+            BatchDecommitmentError::DuplicateIndex(5)
+        );
+    }
+
+    #[test]
+    fn test_spot_check() {
+        const LOG_SIZE: u32 = 8;
+        const N_QUERIES: usize = 10;
+        let columns = (0..3)
+            .map(|c| {
+                (0..1 << LOG_SIZE)
+                    .map(|i| BaseField::from(c * 1000 + i))
+                    .collect_vec()
+            })
+            .collect_vec();
+        let columns = columns.iter().collect_vec();
+        let prover = MerkleProver::<CpuBackend, H>::commit(columns.clone());
+        let spot_check =
+            |channel: &mut Blake2sChannel| prover.spot_check(channel, &columns, N_QUERIES);
+
+        let openings = spot_check(&mut Blake2sChannel::default());
+
+        assert_eq!(openings, spot_check(&mut Blake2sChannel::default()));
+        let mut other_channel = Blake2sChannel::default();
+        other_channel.mix_u64(1);
+        assert_ne!(openings, spot_check(&mut other_channel));
+        verify_spot_check(
+            &mut Blake2sChannel::default(),
+            prover.root(),
+            LOG_SIZE,
+            N_QUERIES,
+            &openings,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_spot_check_invalid() {
+        const LOG_SIZE: u32 = 6;
+        let columns = [(0..1 << LOG_SIZE).map(BaseField::from).collect_vec()];
+        let columns = columns.iter().collect_vec();
+        let prover = MerkleProver::<CpuBackend, H>::commit(columns.clone());
+        let openings = prover.spot_check(&mut Blake2sChannel::default(), &columns, 4);
+        let verify = |openings: &[MerkleOpening<H>]| {
+            verify_spot_check(
+                &mut Blake2sChannel::default(),
+                prover.root(),
+                LOG_SIZE,
+                4,
+                openings,
+            )
+        };
+
+        let mut tampered_leaf = openings.clone();
+        tampered_leaf[1].1[0] += BaseField::from(1);
+        assert_eq!(
+            verify(&tampered_leaf).unwrap_err(),
+            CommitmentError::RootMismatch(1)
+        );
+        let mut other_index = openings.clone();
+        let (index, leaf, path) = &mut other_index[0];
+        *index ^= 1;
+        *leaf = vec![columns[0][*index]];
+        *path = prover.decommit_path(*index);
+        assert_eq!(
+            verify(&other_index).unwrap_err(),
+            CommitmentError::IndicesMismatch
+        );
+    }
+}
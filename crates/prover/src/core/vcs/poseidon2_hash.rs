@@ -0,0 +1,325 @@
+//! Poseidon2 hash over [BaseField], from <https://eprint.iacr.org/2023/323.pdf>.
+//!
+//! Column values are absorbed as field elements directly, which makes the resulting Merkle trees
+//! cheap to verify inside an M31 circuit.
+
+use std::fmt;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+use blake2::{Blake2s256, Digest};
+use bytemuck::{Pod, Zeroable};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::hash::Hash;
+use super::ops::MerkleHasher;
+use crate::core::fields::m31::BaseField;
+use crate::core::fields::FieldExpOps;
+
+/// Number of [BaseField] elements in a [Poseidon2Hash].
+pub const POSEIDON2_DIGEST_SIZE: usize = 8;
+
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Default, Eq, Pod, Zeroable, Deserialize, Serialize)]
+pub struct Poseidon2Hash(pub [BaseField; POSEIDON2_DIGEST_SIZE]);
+
+impl fmt::Display for Poseidon2Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.0.iter().join(", "))
+    }
+}
+
+impl Debug for Poseidon2Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <Poseidon2Hash as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl Hash for Poseidon2Hash {}
+
+/// Parameters of a Poseidon2 permutation over a state of `WIDTH` [BaseField] elements.
+///
+/// `WIDTH` must be a multiple of 4 and larger than [POSEIDON2_DIGEST_SIZE]. The S-box is `x^5`.
+pub trait Poseidon2Params<const WIDTH: usize>:
+    Debug + Default + Clone + Send + Sync + 'static
+{
+    /// Number of full rounds applied before (and after) the partial rounds.
+    const N_HALF_FULL_ROUNDS: usize;
+    const N_PARTIAL_ROUNDS: usize;
+
+    /// Round constants of the `2 * N_HALF_FULL_ROUNDS` full rounds.
+    fn external_round_constants() -> &'static [[BaseField; WIDTH]];
+    /// Round constants of the partial rounds, added to the first state element.
+    fn internal_round_constants() -> &'static [BaseField];
+    /// The internal round matrix is `diag(internal_matrix_diagonal()) + 1`, where `1` is the
+    /// all-ones matrix.
+    fn internal_matrix_diagonal() -> [BaseField; WIDTH];
+}
+
+/// Default Poseidon2 parameters: a state of 16 elements, 8 full rounds and 14 partial rounds.
+// TODO: Replace the generated round constants with standardized ones once they are published.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct Poseidon2M31Width16;
+
+impl Poseidon2Params<16> for Poseidon2M31Width16 {
+    const N_HALF_FULL_ROUNDS: usize = 4;
+    const N_PARTIAL_ROUNDS: usize = 14;
+
+    fn external_round_constants() -> &'static [[BaseField; 16]] {
+        static CONSTANTS: OnceLock<Vec<[BaseField; 16]>> = OnceLock::new();
+        CONSTANTS.get_or_init(|| {
+            (0..2 * Self::N_HALF_FULL_ROUNDS)
+                .map(|round| std::array::from_fn(|i| round_constant("external", 16 * round + i)))
+                .collect()
+        })
+    }
+
+    fn internal_round_constants() -> &'static [BaseField] {
+        static CONSTANTS: OnceLock<Vec<BaseField>> = OnceLock::new();
+        CONSTANTS.get_or_init(|| {
+            (0..Self::N_PARTIAL_ROUNDS)
+                .map(|round| round_constant("internal", round))
+                .collect()
+        })
+    }
+
+    fn internal_matrix_diagonal() -> [BaseField; 16] {
+        // mu_i = 2^{i+1}, see <https://eprint.iacr.org/2023/323.pdf> 5.2.
+        std::array::from_fn(|i| BaseField::from_u32_unchecked(1 << (i + 1)))
+    }
+}
+
+/// Derives a round constant deterministically from a Blake2s hash of its position.
+fn round_constant(kind: &str, index: usize) -> BaseField {
+    let mut hasher = Blake2s256::new();
+    hasher.update(b"poseidon2_m31");
+    hasher.update(kind.as_bytes());
+    hasher.update((index as u64).to_le_bytes());
+    let digest = hasher.finalize();
+    BaseField::from(u32::from_le_bytes(digest[..4].try_into().unwrap()))
+}
+
+/// Applies the M4 MDS matrix described in <https://eprint.iacr.org/2023/323.pdf> 5.1.
+fn apply_m4(x: [BaseField; 4]) -> [BaseField; 4] {
+    let t0 = x[0] + x[1];
+    let t02 = t0 + t0;
+    let t1 = x[2] + x[3];
+    let t12 = t1 + t1;
+    let t2 = x[1] + x[1] + t1;
+    let t3 = x[3] + x[3] + t0;
+    let t4 = t12 + t12 + t3;
+    let t5 = t02 + t02 + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+    [t6, t5, t7, t4]
+}
+
+/// Applies the external round matrix circ(2M4, M4, ..., M4).
+/// See <https://eprint.iacr.org/2023/323.pdf> 5.1 and Appendix B.
+fn apply_external_round_matrix<const WIDTH: usize>(state: &mut [BaseField; WIDTH]) {
+    for chunk in state.chunks_exact_mut(4) {
+        let m4 = apply_m4(chunk.try_into().unwrap());
+        chunk.copy_from_slice(&m4);
+    }
+    for j in 0..4 {
+        let s = state.iter().skip(j).step_by(4).copied().sum::<BaseField>();
+        state.iter_mut().skip(j).step_by(4).for_each(|x| *x += s);
+    }
+}
+
+fn apply_internal_round_matrix<const WIDTH: usize>(
+    state: &mut [BaseField; WIDTH],
+    diagonal: &[BaseField; WIDTH],
+) {
+    let sum = state.iter().copied().sum::<BaseField>();
+    state
+        .iter_mut()
+        .zip(diagonal)
+        .for_each(|(s, d)| *s = *s * *d + sum);
+}
+
+fn pow5(x: BaseField) -> BaseField {
+    let x2 = x.square();
+    x2.square() * x
+}
+
+/// Applies the Poseidon2 permutation defined by `P` to `state`.
+pub fn poseidon2_permute<const WIDTH: usize, P: Poseidon2Params<WIDTH>>(
+    state: &mut [BaseField; WIDTH],
+) {
+    assert!(WIDTH % 4 == 0 && WIDTH > POSEIDON2_DIGEST_SIZE);
+    let external_round_constants = P::external_round_constants();
+    let internal_round_constants = P::internal_round_constants();
+    let diagonal = P::internal_matrix_diagonal();
+    let full_round = |state: &mut [BaseField; WIDTH], round_constants: &[BaseField; WIDTH]| {
+        state
+            .iter_mut()
+            .zip(round_constants)
+            .for_each(|(s, c)| *s = pow5(*s + *c));
+        apply_external_round_matrix(state);
+    };
+
+    apply_external_round_matrix(state);
+    let (first_rounds, last_rounds) = external_round_constants.split_at(P::N_HALF_FULL_ROUNDS);
+    first_rounds.iter().for_each(|c| full_round(state, c));
+    for c in &internal_round_constants[..P::N_PARTIAL_ROUNDS] {
+        state[0] = pow5(state[0] + *c);
+        apply_internal_round_matrix(state, &diagonal);
+    }
+    last_rounds.iter().for_each(|c| full_round(state, c));
+}
+
+/// A Merkle hasher based on a Poseidon2 sponge over [BaseField].
+///
+/// The sponge has a rate of `WIDTH - POSEIDON2_DIGEST_SIZE` elements. The children hashes and the
+/// column values are absorbed as field elements, and the number of absorbed elements is written to
+/// the capacity for domain separation.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Poseidon2MerkleHasher<const WIDTH: usize, P: Poseidon2Params<WIDTH>>(PhantomData<P>);
+
+/// A [Poseidon2MerkleHasher] with the default [Poseidon2M31Width16] parameters.
+pub type Poseidon2M31MerkleHasher = Poseidon2MerkleHasher<16, Poseidon2M31Width16>;
+
+impl<const WIDTH: usize, P: Poseidon2Params<WIDTH>> MerkleHasher
+    for Poseidon2MerkleHasher<WIDTH, P>
+{
+    type Hash = Poseidon2Hash;
+
+    fn hash_node(
+        children_hashes: Option<(Self::Hash, Self::Hash)>,
+        column_values: &[BaseField],
+    ) -> Self::Hash {
+        let rate = WIDTH - POSEIDON2_DIGEST_SIZE;
+        let children = children_hashes
+            .into_iter()
+            .flat_map(|(left, right)| left.0.into_iter().chain(right.0));
+        let values = children.chain(column_values.iter().copied()).collect_vec();
+
+        let mut state = [BaseField::default(); WIDTH];
+        state[WIDTH - 1] = BaseField::from(values.len());
+        for chunk in values.chunks(rate) {
+            state.iter_mut().zip(chunk).for_each(|(s, v)| *s += *v);
+            poseidon2_permute::<WIDTH, P>(&mut state);
+        }
+        if values.is_empty() {
+            poseidon2_permute::<WIDTH, P>(&mut state);
+        }
+        Poseidon2Hash(state[..POSEIDON2_DIGEST_SIZE].try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::Zero;
+
+    use super::{poseidon2_permute, Poseidon2Hash, Poseidon2M31MerkleHasher, Poseidon2M31Width16};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::vcs::ops::MerkleHasher;
+    use crate::core::vcs::test_utils::prepare_merkle;
+    use crate::core::vcs::verifier::MerkleVerificationError;
+    use crate::m31;
+
+    #[test]
+    fn test_permutation_is_not_trivial() {
+        let mut state = [BaseField::zero(); 16];
+
+        poseidon2_permute::<16, Poseidon2M31Width16>(&mut state);
+
+        assert_ne!(state, [BaseField::zero(); 16]);
+    }
+
+    #[test]
+    fn test_hash_node_domain_separation() {
+        let hash_0 = Poseidon2M31MerkleHasher::hash_node(None, &[m31!(0)]);
+        let hash_00 = Poseidon2M31MerkleHasher::hash_node(None, &[m31!(0), m31!(0)]);
+        let hash_node = Poseidon2M31MerkleHasher::hash_node(Some((hash_0, hash_0)), &[]);
+
+        assert_ne!(hash_0, hash_00);
+        assert_ne!(hash_0, Poseidon2M31MerkleHasher::hash_node(None, &[]));
+        assert_ne!(
+            hash_node,
+            Poseidon2M31MerkleHasher::hash_node(Some((hash_0, hash_00)), &[])
+        );
+    }
+
+    #[test]
+    fn test_merkle_success() {
+        let (queries, decommitment, values, verifier) =
+            prepare_merkle::<Poseidon2M31MerkleHasher>();
+        verifier.verify(&queries, values, decommitment).unwrap();
+    }
+
+    #[test]
+    fn test_merkle_invalid_witness() {
+        let (queries, mut decommitment, values, verifier) =
+            prepare_merkle::<Poseidon2M31MerkleHasher>();
+        decommitment.hash_witness[4] = Poseidon2Hash::default();
+
+        assert_eq!(
+            verifier.verify(&queries, values, decommitment).unwrap_err(),
+            MerkleVerificationError::RootMismatch
+        );
+    }
+
+    #[test]
+    fn test_merkle_invalid_value() {
+        let (queries, decommitment, mut values, verifier) =
+            prepare_merkle::<Poseidon2M31MerkleHasher>();
+        values[6] = BaseField::zero();
+
+        assert_eq!(
+            verifier.verify(&queries, values, decommitment).unwrap_err(),
+            MerkleVerificationError::RootMismatch
+        );
+    }
+
+    #[test]
+    fn test_merkle_witness_too_short() {
+        let (queries, mut decommitment, values, verifier) =
+            prepare_merkle::<Poseidon2M31MerkleHasher>();
+        decommitment.hash_witness.pop();
+
+        assert_eq!(
+            verifier.verify(&queries, values, decommitment).unwrap_err(),
+            MerkleVerificationError::WitnessTooShort
+        );
+    }
+
+    #[test]
+    fn test_merkle_witness_too_long() {
+        let (queries, mut decommitment, values, verifier) =
+            prepare_merkle::<Poseidon2M31MerkleHasher>();
+        decommitment.hash_witness.push(Poseidon2Hash::default());
+
+        assert_eq!(
+            verifier.verify(&queries, values, decommitment).unwrap_err(),
+            MerkleVerificationError::WitnessTooLong
+        );
+    }
+
+    #[test]
+    fn test_merkle_values_too_long() {
+        let (queries, decommitment, mut values, verifier) =
+            prepare_merkle::<Poseidon2M31MerkleHasher>();
+        values.insert(3, BaseField::zero());
+
+        assert_eq!(
+            verifier.verify(&queries, values, decommitment).unwrap_err(),
+            MerkleVerificationError::TooManyQueriedValues
+        );
+    }
+
+    #[test]
+    fn test_merkle_values_too_short() {
+        let (queries, decommitment, mut values, verifier) =
+            prepare_merkle::<Poseidon2M31MerkleHasher>();
+        values.remove(3);
+
+        assert_eq!(
+            verifier.verify(&queries, values, decommitment).unwrap_err(),
+            MerkleVerificationError::TooFewQueriedValues
+        );
+    }
+}
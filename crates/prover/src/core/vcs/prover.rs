@@ -2,12 +2,21 @@ use std::cmp::Reverse;
 use std::collections::BTreeMap;
 
 use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::ops::{MerkleHasher, MerkleOps};
+use super::path::{BatchDecommitmentError, BatchMerkleProof, MerkleOpening, MerklePath};
 use super::utils::{next_decommitment_node, option_flatten_peekable};
 use crate::core::backend::{Col, Column};
+#[cfg(feature = "parallel")]
+use crate::core::backend::{ColumnOps, CpuBackend};
+use crate::core::channel::Channel;
 use crate::core::fields::m31::BaseField;
+use crate::core::fields::secure_column::SecureColumnByCoords;
+use crate::core::fields::FieldOps;
+use crate::core::queries::Queries;
 use crate::core::utils::PeekableExt;
 
 pub struct MerkleProver<B: MerkleOps<H>, H: MerkleHasher> {
@@ -37,9 +46,49 @@ impl<B: MerkleOps<H>, H: MerkleHasher> MerkleProver<B, H> {
     ///
     /// A new instance of `MerkleProver` with the committed layers.
     pub fn commit(columns: Vec<&Col<B, BaseField>>) -> Self {
+        Self::commit_with(columns, B::commit_on_layer)
+    }
+
+    /// Commits to secure field columns through their coordinate columns.
+    ///
+    /// Each secure column is committed as its four coordinate columns, in order, so the leaf of row
+    /// `i` holds the coordinates of `columns[0]` at `i`, followed by those of `columns[1]` at `i`,
+    /// and so on. A leaf opening thus recovers each value with
+    /// [`SecureField::from_m31_array`](crate::core::fields::qm31::SecureField::from_m31_array).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the columns are not all of the same size.
+    pub fn commit_secure(columns: &[&SecureColumnByCoords<B>]) -> Self
+    where
+        B: FieldOps<BaseField>,
+    {
+        assert!(
+            columns.iter().map(|column| column.len()).all_equal(),
+            "Secure columns must all be of the same size",
+        );
+        Self::commit(
+            columns
+                .iter()
+                .flat_map(|column| column.columns.iter())
+                .collect(),
+        )
+    }
+
+    /// Commits to columns, hashing each layer with `commit_on_layer`.
+    ///
+    /// See [MerkleOps::commit_on_layer] for the arguments of `commit_on_layer`.
+    fn commit_with(
+        columns: Vec<&Col<B, BaseField>>,
+        commit_on_layer: impl Fn(
+            u32,
+            Option<&Col<B, H::Hash>>,
+            &[&Col<B, BaseField>],
+        ) -> Col<B, H::Hash>,
+    ) -> Self {
         if columns.is_empty() {
             return Self {
-                layers: vec![B::commit_on_layer(0, None, &[])],
+                layers: vec![commit_on_layer(0, None, &[])],
             };
         }
 
@@ -57,7 +106,7 @@ impl<B: MerkleOps<H>, H: MerkleHasher> MerkleProver<B, H> {
                 .peek_take_while(|column| column.len().ilog2() == log_size)
                 .collect_vec();
 
-            layers.push(B::commit_on_layer(log_size, layers.last(), &layer_columns));
+            layers.push(commit_on_layer(log_size, layers.last(), &layer_columns));
         }
         layers.reverse();
         Self { layers }
@@ -154,9 +203,149 @@ impl<B: MerkleOps<H>, H: MerkleHasher> MerkleProver<B, H> {
         (queried_values, decommitment)
     }
 
+    /// Decommits to the leaves at `indices`, in a tree whose columns are all of the same size.
+    ///
+    /// Shared ancestors are not duplicated: a sibling hash is only added to the proof if the
+    /// sibling is not itself an ancestor of an opened leaf. See [`verify_batch()`].
+    ///
+    /// Fails if `indices` is empty, or has an index out of range or repeated.
+    ///
+    /// [`verify_batch()`]: super::path::verify_batch
+    pub fn decommit_batch(
+        &self,
+        indices: &[usize],
+    ) -> Result<BatchMerkleProof<H>, BatchDecommitmentError> {
+        let n_leaves = self.layers.last().unwrap().len();
+        if indices.is_empty() {
+            return Err(BatchDecommitmentError::NoIndices);
+        }
+        if let Some(&index) = indices.iter().find(|&&index| index >= n_leaves) {
+            return Err(BatchDecommitmentError::IndexOutOfRange(index));
+        }
+        let mut layer_indices = indices.iter().copied().sorted().collect_vec();
+        if let Some(pair) = layer_indices.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(BatchDecommitmentError::DuplicateIndex(pair[0]));
+        }
+
+        let mut siblings = vec![];
+        for layer in self.layers.iter().skip(1).rev() {
+            let mut layer_indices_iter = layer_indices.iter().copied().peekable();
+            while let Some(index) = layer_indices_iter.next() {
+                // Both children are known, no witness is needed.
+                if index % 2 == 0 && layer_indices_iter.next_if_eq(&(index + 1)).is_some() {
+                    continue;
+                }
+                siblings.push(layer.at(index ^ 1));
+            }
+            layer_indices = layer_indices
+                .iter()
+                .map(|index| index >> 1)
+                .dedup()
+                .collect();
+        }
+        Ok(BatchMerkleProof { siblings })
+    }
+
+    /// Returns the authentication path of the leaf at `index`, in a tree whose columns are all of
+    /// the same size.
+    pub fn decommit_path(&self, index: usize) -> MerklePath<H> {
+        let siblings = self
+            .layers
+            .iter()
+            .skip(1)
+            .rev()
+            .enumerate()
+            .map(|(depth, layer)| layer.at((index >> depth) ^ 1))
+            .collect();
+        MerklePath { siblings }
+    }
+
+    /// Opens `n_queries` random leaves drawn from `channel`, as a cheap check that the prover still
+    /// holds the committed `columns`, which must all be of the same size.
+    ///
+    /// Indices are drawn as in [Queries::generate], so repeated indices are opened once and the
+    /// openings are sorted by index. See [`verify_spot_check()`].
+    ///
+    /// [`verify_spot_check()`]: super::path::verify_spot_check
+    pub fn spot_check(
+        &self,
+        channel: &mut impl Channel,
+        columns: &[&Col<B, BaseField>],
+        n_queries: usize,
+    ) -> Vec<MerkleOpening<H>> {
+        let log_size = self.layers.len() as u32 - 1;
+        Queries::generate(channel, log_size, n_queries)
+            .iter()
+            .map(|&index| {
+                let leaf = columns.iter().map(|column| column.at(index)).collect();
+                (index, leaf, self.decommit_path(index))
+            })
+            .collect()
+    }
+
     pub fn root(&self) -> H::Hash {
         self.layers.first().unwrap().at(0)
     }
+
+    /// Rehashes the nodes on the path from the leaf at `index` to the root, and returns the new
+    /// root.
+    ///
+    /// `columns` are the committed columns, after updating their values at `index` (at
+    /// `index >> k` for columns `2^k` times smaller than the largest). The updated tree is the
+    /// same as a fresh [Self::commit] of `columns`, at the cost of one hash per layer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range of the leaf layer.
+    pub fn update_leaf(&mut self, index: usize, columns: Vec<&Col<B, BaseField>>) -> H::Hash {
+        assert!(
+            index < self.layers.last().unwrap().len(),
+            "Leaf index {index} out of range"
+        );
+        let columns = &mut columns
+            .into_iter()
+            .sorted_by_key(|c| Reverse(c.len()))
+            .peekable();
+        let mut node_index = index;
+        for log_size in (0..self.layers.len()).rev() {
+            let node_values = columns
+                .peek_take_while(|column| column.len().ilog2() == log_size as u32)
+                .map(|column| column.at(node_index))
+                .collect_vec();
+            let children_hashes = self.layers.get(log_size + 1).map(|prev_layer| {
+                (
+                    prev_layer.at(2 * node_index),
+                    prev_layer.at(2 * node_index + 1),
+                )
+            });
+            self.layers[log_size].set(node_index, H::hash_node(children_hashes, &node_values));
+            node_index >>= 1;
+        }
+        self.root()
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<H: MerkleHasher> MerkleProver<CpuBackend, H>
+where
+    CpuBackend: MerkleOps<H> + ColumnOps<H::Hash, Column = Vec<H::Hash>>,
+{
+    /// Like [Self::commit], but hashes the nodes of each layer in parallel.
+    ///
+    /// Produces the same tree as [Self::commit].
+    pub fn commit_parallel(columns: Vec<&Vec<BaseField>>) -> Self {
+        Self::commit_with(columns, |log_size, prev_layer, columns| {
+            (0..1 << log_size)
+                .into_par_iter()
+                .map(|i| {
+                    H::hash_node(
+                        prev_layer.map(|prev_layer| (prev_layer[2 * i], prev_layer[2 * i + 1])),
+                        &columns.iter().map(|column| column[i]).collect_vec(),
+                    )
+                })
+                .collect()
+        })
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd)]
@@ -178,3 +367,45 @@ impl<H: MerkleHasher> MerkleDecommitment<H> {
         }
     }
 }
+
+#[cfg(all(test, feature = "parallel"))]
+mod tests {
+    use itertools::Itertools;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::core::backend::{ColumnOps, CpuBackend};
+    use crate::core::fields::m31::BaseField;
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::blake3_merkle::Blake3MerkleHasher;
+    use crate::core::vcs::ops::{MerkleHasher, MerkleOps};
+    use crate::core::vcs::prover::MerkleProver;
+
+    fn assert_commit_parallel_matches_commit<H: MerkleHasher>()
+    where
+        CpuBackend: MerkleOps<H> + ColumnOps<H::Hash, Column = Vec<H::Hash>>,
+    {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let n_columns = rng.gen_range(0..6);
+            let columns = (0..n_columns)
+                .map(|_| {
+                    let log_size = rng.gen_range(0..10);
+                    (0..1 << log_size).map(|_| rng.gen()).collect_vec()
+                })
+                .collect::<Vec<Vec<BaseField>>>();
+
+            let sequential = MerkleProver::<CpuBackend, H>::commit(columns.iter().collect());
+            let parallel = MerkleProver::<CpuBackend, H>::commit_parallel(columns.iter().collect());
+
+            assert_eq!(parallel.root(), sequential.root());
+            assert_eq!(parallel.layers, sequential.layers);
+        }
+    }
+
+    #[test]
+    fn test_commit_parallel_matches_commit() {
+        assert_commit_parallel_matches_commit::<Blake2sMerkleHasher>();
+        assert_commit_parallel_matches_commit::<Blake3MerkleHasher>();
+    }
+}
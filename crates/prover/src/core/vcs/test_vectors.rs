@@ -0,0 +1,154 @@
+//! Golden outputs of the Blake2s Merkle hasher, for implementers of the verifier in other
+//! languages.
+//!
+//! The expected outputs are committed in `test_vectors.json`. All values are given as canonical
+//! `u32` representations of [BaseField] elements, and all digests as lowercase hex strings.
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::blake2_hash::Blake2sHash;
+use super::blake2_merkle::Blake2sMerkleHasher;
+use super::ops::MerkleHasher;
+use super::prover::MerkleProver;
+use crate::core::backend::CpuBackend;
+use crate::core::fields::m31::{BaseField, P};
+
+/// A single invocation of [MerkleHasher::hash_node].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeHashVector {
+    pub children: Option<(String, String)>,
+    pub column_values: Vec<u32>,
+    pub digest: String,
+}
+
+/// The root of a Merkle tree committing to `columns`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleRootVector {
+    pub columns: Vec<Vec<u32>>,
+    pub root: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVectors {
+    pub node_hashes: Vec<NodeHashVector>,
+    pub merkle_root: MerkleRootVector,
+}
+
+/// Hashes a node with the given children digests and column values.
+pub fn hash_node(
+    children: Option<(Blake2sHash, Blake2sHash)>,
+    column_values: &[u32],
+) -> Blake2sHash {
+    let column_values = column_values
+        .iter()
+        .map(|&v| BaseField::from(v))
+        .collect_vec();
+    Blake2sMerkleHasher::hash_node(children, &column_values)
+}
+
+/// Returns the root of a Merkle tree committing to `columns`, all of which must have a power of
+/// two length.
+pub fn merkle_root(columns: &[Vec<u32>]) -> Blake2sHash {
+    let columns = columns
+        .iter()
+        .map(|column| column.iter().map(|&v| BaseField::from(v)).collect_vec())
+        .collect_vec();
+    MerkleProver::<CpuBackend, Blake2sMerkleHasher>::commit(columns.iter().collect()).root()
+}
+
+/// Computes the test vectors from their fixed inputs.
+pub fn generate() -> TestVectors {
+    let leaf = |column_values: Vec<u32>| {
+        let digest = hash_node(None, &column_values);
+        (digest, column_values)
+    };
+    let leaves = [
+        leaf(vec![]),
+        leaf(vec![1]),
+        // Exactly one compression block.
+        leaf((0..16).collect()),
+        // A full block followed by a padded one.
+        leaf((0..17).collect()),
+        leaf(vec![P - 1; 3]),
+    ];
+
+    let mut node_hashes = leaves
+        .iter()
+        .map(|(digest, column_values)| NodeHashVector {
+            children: None,
+            column_values: column_values.clone(),
+            digest: hex::encode(digest),
+        })
+        .collect_vec();
+    for column_values in [vec![], vec![5, 6, 7]] {
+        let children = (leaves[1].0, leaves[2].0);
+        node_hashes.push(NodeHashVector {
+            children: Some((hex::encode(children.0), hex::encode(children.1))),
+            digest: hex::encode(hash_node(Some(children), &column_values)),
+            column_values,
+        });
+    }
+
+    let columns = vec![
+        (0..8).collect_vec(),
+        (0..8).map(|i| P - 1 - i).collect_vec(),
+    ];
+    let merkle_root = MerkleRootVector {
+        root: hex::encode(merkle_root(&columns)),
+        columns,
+    };
+
+    TestVectors {
+        node_hashes,
+        merkle_root,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::{generate, TestVectors};
+    use crate::core::backend::simd::SimdBackend;
+    use crate::core::backend::Col;
+    use crate::core::fields::m31::BaseField;
+    use crate::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+    use crate::core::vcs::prover::MerkleProver;
+
+    const TEST_VECTORS_JSON: &str = include_str!("test_vectors.json");
+
+    #[test]
+    fn test_vectors_match_committed_json() {
+        let expected: TestVectors = serde_json::from_str(TEST_VECTORS_JSON).unwrap();
+
+        assert_eq!(
+            generate(),
+            expected,
+            "Regenerate with: {}",
+            serde_json::to_string_pretty(&generate()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_simd_merkle_root_matches_vector() {
+        let vectors = generate();
+        let columns = vectors
+            .merkle_root
+            .columns
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .map(|&v| BaseField::from(v))
+                    .collect::<Col<SimdBackend, BaseField>>()
+            })
+            .collect_vec();
+
+        let root =
+            MerkleProver::<SimdBackend, Blake2sMerkleHasher>::commit(columns.iter().collect())
+                .root();
+
+        assert_eq!(hex::encode(root), vectors.merkle_root.root);
+    }
+}
@@ -18,3 +18,10 @@ pub fn option_flatten_peekable<'a, I: IntoIterator<Item = &'a usize>>(
 ) -> Peekable<std::iter::Copied<std::iter::Flatten<<Option<I> as IntoIterator>::IntoIter>>> {
     a.into_iter().flatten().copied().peekable()
 }
+
+/// Compares two byte arrays, going over all the bytes whatever their content.
+pub fn ct_eq_bytes<const N: usize>(a: &[u8; N], b: &[u8; N]) -> bool {
+    let diff = std::iter::zip(a, b).fold(0, |acc, (a, b)| acc | (a ^ b));
+    // Keeps the compiler from short-circuiting the fold.
+    std::hint::black_box(diff) == 0
+}
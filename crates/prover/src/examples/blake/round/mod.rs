@@ -22,6 +22,10 @@ pub struct BlakeRoundEval {
 }
 
 impl FrameworkEval for BlakeRoundEval {
+    fn name(&self) -> String {
+        "BlakeRoundEval".to_string()
+    }
+
     fn log_size(&self) -> u32 {
         self.log_size
     }
@@ -23,6 +23,10 @@ pub struct BlakeSchedulerEval {
     pub total_sum: SecureField,
 }
 impl FrameworkEval for BlakeSchedulerEval {
+    fn name(&self) -> String {
+        "BlakeSchedulerEval".to_string()
+    }
+
     fn log_size(&self) -> u32 {
         self.log_size
     }
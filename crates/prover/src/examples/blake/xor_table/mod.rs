@@ -113,6 +113,9 @@ macro_rules! xor_table_component {
         impl<const ELEM_BITS: u32, const EXPAND_BITS: u32> FrameworkEval
             for XorTableEval<ELEM_BITS, EXPAND_BITS>
         {
+            fn name(&self) -> String {
+                format!("XorTableEval<{ELEM_BITS}, {EXPAND_BITS}>")
+            }
             fn log_size(&self) -> u32 {
                 column_bits::<$elem_bits, $expand_bits>()
             }
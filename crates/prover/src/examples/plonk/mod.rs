@@ -40,6 +40,10 @@ pub struct PlonkEval {
 }
 
 impl FrameworkEval for PlonkEval {
+    fn name(&self) -> String {
+        "PlonkEval".to_string()
+    }
+
     fn log_size(&self) -> u32 {
         self.log_n_rows
     }
@@ -266,13 +270,46 @@ pub fn prove_fibonacci_plonk(
 mod tests {
     use std::env;
 
+    use num_traits::Zero;
+
     use crate::core::air::Component;
     use crate::core::channel::Blake2sChannel;
     use crate::core::fri::FriConfig;
     use crate::core::pcs::{CommitmentSchemeVerifier, PcsConfig};
-    use crate::core::prover::verify;
-    use crate::core::vcs::blake2_merkle::Blake2sMerkleChannel;
-    use crate::examples::plonk::{prove_fibonacci_plonk, PlonkLookupElements};
+    use crate::core::prover::{verify, verify_with_logup_balance, StarkProof, VerificationError};
+    use crate::core::vcs::blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher};
+    use crate::examples::plonk::{prove_fibonacci_plonk, PlonkComponent, PlonkLookupElements};
+
+    /// Commits to the trees of `proof` on a new verifier channel, as the prover did.
+    fn commit_to_proof(
+        component: &PlonkComponent,
+        proof: &StarkProof<Blake2sMerkleHasher>,
+        config: PcsConfig,
+    ) -> (
+        Blake2sChannel,
+        CommitmentSchemeVerifier<Blake2sMerkleChannel>,
+    ) {
+        // TODO: Create Air instance independently.
+        let mut channel = Blake2sChannel::default();
+        let mut commitment_scheme = CommitmentSchemeVerifier::<Blake2sMerkleChannel>::new(config);
+
+        // Decommit.
+        // Retrieve the expected column sizes in each commitment interaction, from the AIR.
+        let sizes = component.trace_log_degree_bounds();
+
+        // Preprocessed columns.
+        commitment_scheme.commit(proof.commitments[0], &sizes[0], &mut channel);
+
+        // Trace columns.
+        commitment_scheme.commit(proof.commitments[1], &sizes[1], &mut channel);
+        // Draw lookup element.
+        let lookup_elements = PlonkLookupElements::draw(&mut channel);
+        assert_eq!(lookup_elements, component.lookup_elements);
+        // Interaction columns.
+        commitment_scheme.commit(proof.commitments[2], &sizes[2], &mut channel);
+
+        (channel, commitment_scheme)
+    }
 
     #[test_log::test]
     fn test_simd_plonk_prove() {
@@ -290,25 +327,21 @@ mod tests {
         let (component, proof) = prove_fibonacci_plonk(log_n_instances, config);
 
         // Verify.
-        // TODO: Create Air instance independently.
-        let channel = &mut Blake2sChannel::default();
-        let commitment_scheme = &mut CommitmentSchemeVerifier::<Blake2sMerkleChannel>::new(config);
-
-        // Decommit.
-        // Retrieve the expected column sizes in each commitment interaction, from the AIR.
-        let sizes = component.trace_log_degree_bounds();
-
-        // Preprocessed columns.
-        commitment_scheme.commit(proof.commitments[0], &sizes[0], channel);
-
-        // Trace columns.
-        commitment_scheme.commit(proof.commitments[1], &sizes[1], channel);
-        // Draw lookup element.
-        let lookup_elements = PlonkLookupElements::draw(channel);
-        assert_eq!(lookup_elements, component.lookup_elements);
-        // Interaction columns.
-        commitment_scheme.commit(proof.commitments[2], &sizes[2], channel);
+        let (mut channel, mut commitment_scheme) = commit_to_proof(&component, &proof, config);
+        verify(&[&component], &mut channel, &mut commitment_scheme, proof).unwrap();
+    }
 
-        verify(&[&component], channel, commitment_scheme, proof).unwrap();
+    #[test]
+    fn test_simd_plonk_verify_with_logup_balance() {
+        let config = PcsConfig::default();
+        let (component, proof) = prove_fibonacci_plonk(6, config);
+        let (mut channel, mut commitment_scheme) = commit_to_proof(&component, &proof, config);
+
+        // The plonk lookups are not balanced by any other component.
+        assert!(!component.claimed_logup_sum().unwrap().is_zero());
+        assert!(matches!(
+            verify_with_logup_balance(&[&component], &mut channel, &mut commitment_scheme, proof),
+            Err(VerificationError::UnbalancedLogup)
+        ));
     }
 }
@@ -54,6 +54,10 @@ pub struct PoseidonEval {
     pub total_sum: SecureField,
 }
 impl FrameworkEval for PoseidonEval {
+    fn name(&self) -> String {
+        "PoseidonEval".to_string()
+    }
+
     fn log_size(&self) -> u32 {
         self.log_n_rows
     }
@@ -40,6 +40,10 @@ pub struct StateTransitionEval<const COORDINATE: usize> {
 }
 
 impl<const COORDINATE: usize> FrameworkEval for StateTransitionEval<COORDINATE> {
+    fn name(&self) -> String {
+        format!("StateTransitionEval<{COORDINATE}>")
+    }
+
     fn log_size(&self) -> u32 {
         self.log_n_rows
     }
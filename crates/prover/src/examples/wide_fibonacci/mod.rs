@@ -12,10 +12,21 @@ use crate::core::ColumnVec;
 
 pub type WideFibonacciComponent<const N: usize> = FrameworkComponent<WideFibonacciEval<N>>;
 
+/// The first two values of the Fibonacci sequences of [PackedBaseField]'s lanes.
 pub struct FibInput {
     a: PackedBaseField,
     b: PackedBaseField,
 }
+impl FibInput {
+    pub const fn new(a: PackedBaseField, b: PackedBaseField) -> Self {
+        Self { a, b }
+    }
+
+    /// Starts the sequences of all lanes from the same two values.
+    pub fn from_seeds(a: BaseField, b: BaseField) -> Self {
+        Self::new(PackedBaseField::broadcast(a), PackedBaseField::broadcast(b))
+    }
+}
 
 /// A component that enforces the Fibonacci sequence.
 /// Each row contains a seperate Fibonacci sequence of length `N`.
@@ -24,6 +35,10 @@ pub struct WideFibonacciEval<const N: usize> {
     pub log_n_rows: u32,
 }
 impl<const N: usize> FrameworkEval for WideFibonacciEval<N> {
+    fn name(&self) -> String {
+        format!("WideFibonacciEval<{N}>")
+    }
+
     fn log_size(&self) -> u32 {
         self.log_n_rows
     }
@@ -71,25 +86,31 @@ pub fn generate_trace<const N: usize>(
 mod tests {
     use itertools::Itertools;
     use num_traits::{One, Zero};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
 
     use super::WideFibonacciEval;
     use crate::constraint_framework::{
-        assert_constraints, AssertEvaluator, FrameworkEval, TraceLocationAllocator,
+        assert_constraints, random_trace, try_assert_constraints, FrameworkEval,
+        TraceLocationAllocator,
     };
-    use crate::core::air::Component;
+    use crate::core::air::{Component, Components};
     use crate::core::backend::simd::m31::{PackedBaseField, LOG_N_LANES};
     use crate::core::backend::simd::SimdBackend;
-    use crate::core::backend::Column;
-    use crate::core::channel::Blake2sChannel;
+    use crate::core::backend::{BackendForChannel, Column};
     #[cfg(not(target_arch = "wasm32"))]
     use crate::core::channel::Poseidon252Channel;
+    use crate::core::channel::{Blake2sChannel, MerkleChannel};
     use crate::core::fields::m31::BaseField;
     use crate::core::fields::qm31::SecureField;
+    use crate::core::fri::FriConfig;
     use crate::core::pcs::{CommitmentSchemeProver, CommitmentSchemeVerifier, PcsConfig, TreeVec};
     use crate::core::poly::circle::{CanonicCoset, CircleEvaluation, PolyOps};
     use crate::core::poly::BitReversedOrder;
-    use crate::core::prover::{prove, verify};
+    use crate::core::prover::{prove, verify, ProvingError, VerificationError};
+    use crate::core::test_utils::prove_verify_roundtrip;
     use crate::core::vcs::blake2_merkle::Blake2sMerkleChannel;
+    use crate::core::vcs::blake3_merkle::Blake3MerkleChannel;
     #[cfg(not(target_arch = "wasm32"))]
     use crate::core::vcs::poseidon252_merkle::Poseidon252MerkleChannel;
     use crate::core::ColumnVec;
@@ -131,25 +152,37 @@ mod tests {
         generate_trace::<FIB_SEQUENCE_LENGTH>(log_n_instances, &inputs)
     }
 
-    fn fibonacci_constraint_evaluator<const N: u32>(eval: AssertEvaluator<'_>) {
-        WideFibonacciEval::<FIB_SEQUENCE_LENGTH> { log_n_rows: N }.evaluate(eval);
-    }
-
-    #[test]
-    fn test_wide_fibonacci_constraints() {
-        const LOG_N_INSTANCES: u32 = 6;
-        let traces = TreeVec::new(vec![vec![], generate_test_trace(LOG_N_INSTANCES)]);
+    fn assert_wide_fibonacci_constraints(
+        trace: ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        log_n_instances: u32,
+    ) {
+        let traces = TreeVec::new(vec![vec![], trace]);
         let trace_polys =
             traces.map(|trace| trace.into_iter().map(|c| c.interpolate()).collect_vec());
 
         assert_constraints(
             &trace_polys,
-            CanonicCoset::new(LOG_N_INSTANCES),
-            fibonacci_constraint_evaluator::<LOG_N_INSTANCES>,
+            CanonicCoset::new(log_n_instances),
+            |eval| {
+                WideFibonacciEval::<FIB_SEQUENCE_LENGTH> {
+                    log_n_rows: log_n_instances,
+                }
+                .evaluate(eval);
+            },
             (SecureField::zero(), None),
         );
     }
 
+    #[test]
+    fn test_wide_fibonacci_constraints() {
+        for log_n_instances in [5, 8, 10] {
+            assert_wide_fibonacci_constraints(
+                generate_test_trace(log_n_instances),
+                log_n_instances,
+            );
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_wide_fibonacci_constraints_fails() {
@@ -158,18 +191,215 @@ mod tests {
         let mut trace = generate_test_trace(LOG_N_INSTANCES);
         // Modify the trace such that a constraint fail.
         trace[17].values.set(2, BaseField::one());
-        let traces = TreeVec::new(vec![vec![], trace]);
-        let trace_polys =
-            traces.map(|trace| trace.into_iter().map(|c| c.interpolate()).collect_vec());
 
-        assert_constraints(
+        assert_wide_fibonacci_constraints(trace, LOG_N_INSTANCES);
+    }
+
+    #[test]
+    fn test_wide_fibonacci_random_trace() {
+        const LOG_N_INSTANCES: u32 = 5;
+        let eval = WideFibonacciEval::<FIB_SEQUENCE_LENGTH> {
+            log_n_rows: LOG_N_INSTANCES,
+        };
+
+        let trace =
+            random_trace::<SimdBackend>(&eval, LOG_N_INSTANCES, &mut SmallRng::seed_from_u64(0));
+
+        assert_eq!(
+            trace.as_ref().map(|tree| tree.len()).0,
+            [0, FIB_SEQUENCE_LENGTH]
+        );
+        let trace_polys = trace.map(|tree| tree.into_iter().map(|c| c.interpolate()).collect_vec());
+        let violations = try_assert_constraints(
             &trace_polys,
             CanonicCoset::new(LOG_N_INSTANCES),
-            fibonacci_constraint_evaluator::<LOG_N_INSTANCES>,
+            |assert_eval| {
+                eval.evaluate(assert_eval);
+            },
             (SecureField::zero(), None),
+        )
+        .unwrap_err();
+        assert!(!violations.is_empty());
+    }
+
+    #[test]
+    fn test_wide_fibonacci_trace_from_seeds() {
+        const LOG_N_INSTANCES: u32 = 5;
+        let (a, b) = (BaseField::from(3), BaseField::from(5));
+        let inputs = (0..1 << (LOG_N_INSTANCES - LOG_N_LANES))
+            .map(|_| FibInput::from_seeds(a, b))
+            .collect_vec();
+
+        let trace = generate_trace::<FIB_SEQUENCE_LENGTH>(LOG_N_INSTANCES, &inputs);
+
+        for row in 0..1 << LOG_N_INSTANCES {
+            assert_eq!(trace[0].values.at(row), a);
+            assert_eq!(trace[1].values.at(row), b);
+            assert_eq!(trace[2].values.at(row), a * a + b * b);
+        }
+        assert_wide_fibonacci_constraints(trace, LOG_N_INSTANCES);
+    }
+
+    #[test]
+    fn test_wide_fibonacci_n_constraints() {
+        let eval = WideFibonacciEval::<FIB_SEQUENCE_LENGTH> { log_n_rows: 4 };
+
+        assert_eq!(FrameworkEval::n_constraints(&eval), FIB_SEQUENCE_LENGTH - 2);
+        let component = WideFibonacciComponent::new(
+            &mut TraceLocationAllocator::default(),
+            eval,
+            (SecureField::zero(), None),
+        );
+        assert_eq!(
+            Component::n_constraints(&component),
+            FIB_SEQUENCE_LENGTH - 2
         );
     }
 
+    #[test]
+    fn test_wide_fib_prove_standalone() {
+        const LOG_N_INSTANCES: u32 = 5;
+        let config = PcsConfig::default();
+        let component = WideFibonacciComponent::new(
+            &mut TraceLocationAllocator::default(),
+            WideFibonacciEval::<FIB_SEQUENCE_LENGTH> {
+                log_n_rows: LOG_N_INSTANCES,
+            },
+            (SecureField::zero(), None),
+        );
+        let trace = TreeVec::new(vec![vec![], generate_test_trace(LOG_N_INSTANCES)]);
+
+        let proof = component
+            .prove_standalone::<Blake2sMerkleChannel>(config, trace, &mut Blake2sChannel::default())
+            .unwrap();
+
+        component
+            .verify_standalone::<Blake2sMerkleChannel>(
+                config,
+                proof,
+                &mut Blake2sChannel::default(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_wide_fib_verify_standalone_tampered_sampled_value() {
+        const LOG_N_INSTANCES: u32 = 5;
+        let config = PcsConfig::default();
+        let component = WideFibonacciComponent::new(
+            &mut TraceLocationAllocator::default(),
+            WideFibonacciEval::<FIB_SEQUENCE_LENGTH> {
+                log_n_rows: LOG_N_INSTANCES,
+            },
+            (SecureField::zero(), None),
+        );
+        let trace = TreeVec::new(vec![vec![], generate_test_trace(LOG_N_INSTANCES)]);
+        let mut proof = component
+            .prove_standalone::<Blake2sMerkleChannel>(config, trace, &mut Blake2sChannel::default())
+            .unwrap();
+
+        // Claim a different value of the last trace column at the OODS point.
+        let claimed_value = proof.0.sampled_values[1].last_mut().unwrap();
+        claimed_value[0] += SecureField::one();
+        let result = component.verify_standalone::<Blake2sMerkleChannel>(
+            config,
+            proof,
+            &mut Blake2sChannel::default(),
+        );
+
+        assert!(matches!(result, Err(VerificationError::OodsNotMatching)));
+    }
+
+    #[test]
+    fn test_wide_fib_expected_fri_layer_log_sizes() {
+        const LOG_N_INSTANCES: u32 = 5;
+        let config = PcsConfig {
+            pow_bits: 5,
+            fri_config: FriConfig::new(2, 1, 3),
+        };
+        let component = WideFibonacciComponent::new(
+            &mut TraceLocationAllocator::default(),
+            WideFibonacciEval::<FIB_SEQUENCE_LENGTH> {
+                log_n_rows: LOG_N_INSTANCES,
+            },
+            (SecureField::zero(), None),
+        );
+        let trace = TreeVec::new(vec![vec![], generate_test_trace(LOG_N_INSTANCES)]);
+        let components = Components {
+            components: vec![&component],
+            n_preprocessed_columns: 0,
+        };
+
+        let proof = component
+            .prove_standalone::<Blake2sMerkleChannel>(config, trace, &mut Blake2sChannel::default())
+            .unwrap();
+        let layer_log_sizes = components.expected_fri_layer_log_sizes(&config.fri_config);
+
+        assert_eq!(layer_log_sizes, [7, 6, 5, 4, 3]);
+        let fri_proof = &proof.0.fri_proof;
+        assert_eq!(fri_proof.inner_layers.len(), layer_log_sizes.len() - 2);
+        assert_eq!(
+            fri_proof.last_layer_poly.len(),
+            1 << (layer_log_sizes.last().unwrap() - config.fri_config.log_blowup_factor)
+        );
+    }
+
+    fn wide_fib_prove_verify_roundtrip<MC: MerkleChannel>()
+    where
+        SimdBackend: BackendForChannel<MC>,
+    {
+        const LOG_N_INSTANCES: u32 = 5;
+        let component = WideFibonacciComponent::new(
+            &mut TraceLocationAllocator::default(),
+            WideFibonacciEval::<FIB_SEQUENCE_LENGTH> {
+                log_n_rows: LOG_N_INSTANCES,
+            },
+            (SecureField::zero(), None),
+        );
+        let trace = TreeVec::new(vec![vec![], generate_test_trace(LOG_N_INSTANCES)]);
+
+        prove_verify_roundtrip::<MC, _>(&component, trace).unwrap();
+    }
+
+    #[test]
+    fn test_wide_fib_prove_verify_roundtrip_with_blake2s() {
+        wide_fib_prove_verify_roundtrip::<Blake2sMerkleChannel>();
+    }
+
+    #[test]
+    fn test_wide_fib_prove_verify_roundtrip_with_blake3() {
+        wide_fib_prove_verify_roundtrip::<Blake3MerkleChannel>();
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_wide_fib_prove_verify_roundtrip_with_poseidon252() {
+        wide_fib_prove_verify_roundtrip::<Poseidon252MerkleChannel>();
+    }
+
+    #[test]
+    fn test_wide_fib_prove_standalone_tampered_trace() {
+        const LOG_N_INSTANCES: u32 = 5;
+        let config = PcsConfig::default();
+        let component = WideFibonacciComponent::new(
+            &mut TraceLocationAllocator::default(),
+            WideFibonacciEval::<FIB_SEQUENCE_LENGTH> {
+                log_n_rows: LOG_N_INSTANCES,
+            },
+            (SecureField::zero(), None),
+        );
+        let mut trace = generate_test_trace(LOG_N_INSTANCES);
+        trace[17].values.set(2, BaseField::one());
+
+        let result = component.prove_standalone::<Blake2sMerkleChannel>(
+            config,
+            TreeVec::new(vec![vec![], trace]),
+            &mut Blake2sChannel::default(),
+        );
+
+        assert!(matches!(result, Err(ProvingError::ConstraintsNotSatisfied)));
+    }
+
     #[test_log::test]
     fn test_wide_fib_prove_with_blake() {
         for log_n_instances in 2..=6 {
@@ -10,7 +10,7 @@ use crate::core::circle::M31_CIRCLE_LOG_ORDER;
 use crate::core::fields::m31::BaseField;
 use crate::core::fields::qm31::SecureField;
 use crate::core::lookups::mle::Mle;
-use crate::core::utils::generate_secure_powers;
+use crate::core::utils::powers_of;
 
 pub const MIN_LOG_BLOWUP_FACTOR: u32 = 1;
 
@@ -59,7 +59,7 @@ fn mle_random_linear_combination(
     assert!(!mles.is_empty());
     let n_variables = mles[0].n_variables();
     assert!(mles.iter().all(|mle| mle.n_variables() == n_variables));
-    let coeff_powers = generate_secure_powers(random_coeff, mles.len());
+    let coeff_powers = powers_of(random_coeff, mles.len());
     let mut mle_and_coeff = zip(mles, coeff_powers.into_iter().rev());
 
     // The last value can initialize the accumulator.
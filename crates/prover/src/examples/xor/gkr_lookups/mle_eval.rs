@@ -20,7 +20,7 @@ use crate::core::backend::simd::prefix_sum::inclusive_prefix_sum;
 use crate::core::backend::simd::qm31::PackedSecureField;
 use crate::core::backend::simd::very_packed_m31::{VeryPackedBaseField, LOG_N_VERY_PACKED_ELEMS};
 use crate::core::backend::simd::SimdBackend;
-use crate::core::backend::{Col, Column};
+use crate::core::backend::{Col, Column, MaybeSync};
 use crate::core::circle::{CirclePoint, Coset};
 use crate::core::constraints::{coset_vanishing, point_vanishing};
 use crate::core::fields::m31::BaseField;
@@ -117,6 +117,11 @@ impl<'twiddles, 'oracle, O: MleCoeffColumnOracle> MleEvalProverComponent<'twiddl
 impl<'twiddles, 'oracle, O: MleCoeffColumnOracle> Component
     for MleEvalProverComponent<'twiddles, 'oracle, O>
 {
+    // Matches the name of the verifier component.
+    fn name(&self) -> String {
+        "MleEvalComponent".to_string()
+    }
+
     fn n_constraints(&self) -> usize {
         self.eval_info().n_constraints
     }
@@ -149,6 +154,10 @@ impl<'twiddles, 'oracle, O: MleCoeffColumnOracle> Component
         vec![]
     }
 
+    fn allocated_locations(&self) -> Option<TreeVec<TreeSubspan>> {
+        Some(self.trace_locations.clone())
+    }
+
     fn evaluate_constraint_quotients_at_point(
         &self,
         point: CirclePoint<SecureField>,
@@ -190,7 +199,7 @@ impl<'twiddles, 'oracle, O: MleCoeffColumnOracle> Component
     }
 }
 
-impl<'twiddles, 'oracle, O: MleCoeffColumnOracle> ComponentProver<SimdBackend>
+impl<'twiddles, 'oracle, O: MleCoeffColumnOracle + MaybeSync> ComponentProver<SimdBackend>
     for MleEvalProverComponent<'twiddles, 'oracle, O>
 {
     fn evaluate_constraint_quotients_on_domain(
@@ -330,6 +339,10 @@ impl<'oracle, O: MleCoeffColumnOracle> MleEvalVerifierComponent<'oracle, O> {
 }
 
 impl<'oracle, O: MleCoeffColumnOracle> Component for MleEvalVerifierComponent<'oracle, O> {
+    fn name(&self) -> String {
+        "MleEvalComponent".to_string()
+    }
+
     fn n_constraints(&self) -> usize {
         self.eval_info().n_constraints
     }
@@ -362,6 +375,10 @@ impl<'oracle, O: MleCoeffColumnOracle> Component for MleEvalVerifierComponent<'o
         vec![]
     }
 
+    fn allocated_locations(&self) -> Option<TreeVec<TreeSubspan>> {
+        Some(self.trace_location.clone())
+    }
+
     fn evaluate_constraint_quotients_at_point(
         &self,
         point: CirclePoint<SecureField>,
@@ -1222,6 +1239,10 @@ mod tests {
         }
 
         impl FrameworkEval for MleCoeffColumnEval {
+            fn name(&self) -> String {
+                "MleCoeffColumnEval".to_string()
+            }
+
             fn log_size(&self) -> u32 {
                 self.n_variables as u32
             }